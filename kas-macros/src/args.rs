@@ -195,6 +195,7 @@ mod kw {
     custom_keyword!(down);
     custom_keyword!(up);
     custom_keyword!(grid);
+    custom_keyword!(flow);
     custom_keyword!(halign);
     custom_keyword!(valign);
     custom_keyword!(key_nav);
@@ -206,6 +207,11 @@ mod kw {
     custom_keyword!(noauto);
     custom_keyword!(children);
     custom_keyword!(column);
+    custom_keyword!(min_width);
+    custom_keyword!(max_width);
+    custom_keyword!(min_height);
+    custom_keyword!(max_height);
+    custom_keyword!(aspect);
 }
 
 #[derive(Debug)]
@@ -217,6 +223,8 @@ pub struct WidgetAttrArgs {
     pub halign: Option<Ident>,
     pub valign: Option<Ident>,
     pub handler: Option<Ident>,
+    /// Size constraints: `(name, value)` pairs
+    pub constraints: Vec<(Ident, Lit)>,
 }
 
 #[derive(Debug)]
@@ -273,6 +281,23 @@ impl WidgetAttrArgs {
             Ok(None)
         }
     }
+    pub fn constraints_toks(&self) -> Result<Option<TokenStream>> {
+        if self.constraints.is_empty() {
+            return Ok(None);
+        }
+
+        let mut toks = quote! { kas::layout::Constraints::new() };
+        for (ident, lit) in &self.constraints {
+            let value: f32 = match lit {
+                Lit::Int(li) => li.base10_parse()?,
+                Lit::Float(lf) => lf.base10_parse()?,
+                _ => return Err(Error::new(lit.span(), "expected numeric literal")),
+            };
+            let method = Ident::new(&format!("with_{}", ident), ident.span());
+            toks.append_all(quote! { .#method(#value) });
+        }
+        Ok(Some(toks))
+    }
 }
 
 impl Parse for WidgetAttrArgs {
@@ -285,6 +310,7 @@ impl Parse for WidgetAttrArgs {
             halign: None,
             valign: None,
             handler: None,
+            constraints: vec![],
         };
         if input.is_empty() {
             return Ok(args);
@@ -327,6 +353,18 @@ impl Parse for WidgetAttrArgs {
                 let _: kw::handler = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.handler = Some(content.parse()?);
+            } else if lookahead.peek(kw::min_width)
+                || lookahead.peek(kw::max_width)
+                || lookahead.peek(kw::min_height)
+                || lookahead.peek(kw::max_height)
+                || lookahead.peek(kw::aspect)
+            {
+                let ident: Ident = content.parse()?;
+                if args.constraints.iter().any(|(i, _)| *i == ident) {
+                    return Err(Error::new(ident.span(), "duplicate constraint"));
+                }
+                let _: Eq = content.parse()?;
+                args.constraints.push((ident, content.parse()?));
             } else {
                 return Err(lookahead.error());
             }
@@ -350,6 +388,7 @@ impl ToTokens for WidgetAttrArgs {
             || self.halign.is_some()
             || self.valign.is_some()
             || self.handler.is_some()
+            || !self.constraints.is_empty()
         {
             let comma = TokenTree::from(Punct::new(',', Spacing::Alone));
             let mut args = TokenStream::new();
@@ -388,10 +427,16 @@ impl ToTokens for WidgetAttrArgs {
             }
             if let Some(ref ident) = self.handler {
                 if !args.is_empty() {
-                    args.append(comma);
+                    args.append(comma.clone());
                 }
                 args.append_all(quote! { handler = #ident });
             }
+            for (ident, lit) in &self.constraints {
+                if !args.is_empty() {
+                    args.append(comma.clone());
+                }
+                args.append_all(quote! { #ident = #lit });
+            }
             tokens.append_all(quote! { ( #args ) });
         }
     }
@@ -547,12 +592,13 @@ pub enum LayoutType {
     Down,
     Up,
     Grid,
+    Flow,
 }
 
 impl ToTokens for LayoutType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.append_all(match self {
-            LayoutType::Single | LayoutType::Grid => unreachable!(),
+            LayoutType::Single | LayoutType::Grid | LayoutType::Flow => unreachable!(),
             LayoutType::Right => quote! { kas::dir::Right },
             LayoutType::Left => quote! { kas::dir::Left },
             LayoutType::Down => quote! { kas::dir::Down },
@@ -610,6 +656,9 @@ impl Parse for LayoutArgs {
         } else if lookahead.peek(kw::grid) {
             let _: kw::grid = content.parse()?;
             LayoutType::Grid
+        } else if lookahead.peek(kw::flow) {
+            let _: kw::flow = content.parse()?;
+            LayoutType::Flow
         } else {
             return Err(lookahead.error());
         };
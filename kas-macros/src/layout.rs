@@ -32,7 +32,7 @@ pub(crate) fn data_type(children: &Vec<Child>, layout: &LayoutArgs) -> Result<To
 
         match layout.layout {
             LayoutType::Single => (),
-            LayoutType::Right | LayoutType::Left => {
+            LayoutType::Right | LayoutType::Left | LayoutType::Flow => {
                 cols += 1;
                 rows = 1;
             }
@@ -119,6 +119,21 @@ pub(crate) fn data_type(children: &Vec<Child>, layout: &LayoutArgs) -> Result<To
                 Self::Data,
             >;
         },
+        LayoutType::Flow => quote! {
+            type Data = kas::layout::FixedGridStorage::<
+                [kas::layout::SizeRules; #cols + 1],
+                [kas::layout::SizeRules; #cols + 1],
+                [i32; #cols],
+                [i32; #cols],
+            >;
+            type Solver = kas::layout::FlowSolver::<
+                Self::Data,
+            >;
+            type Setter = kas::layout::FlowSetter::<
+                #col_temp,
+                Self::Data,
+            >;
+        },
     })
 }
 
@@ -158,7 +173,7 @@ pub(crate) fn derive(
 
         let child_info = match layout.layout {
             LayoutType::Single => quote! { () },
-            LayoutType::Right | LayoutType::Left => {
+            LayoutType::Right | LayoutType::Left | LayoutType::Flow => {
                 let col = cols;
                 cols += 1;
                 rows = 1;
@@ -190,14 +205,30 @@ pub(crate) fn derive(
             }
         };
 
-        size.append_all(quote! {
-            let child = &mut self.#ident;
-            solver.for_child(
-                &mut #data,
-                #child_info,
-                |axis| child.size_rules(sh, axis)
-            );
-        });
+        let constraints = args.constraints_toks()?;
+
+        if let Some(ref constraints) = constraints {
+            size.append_all(quote! {
+                let child = &mut self.#ident;
+                solver.for_child(
+                    &mut #data,
+                    #child_info,
+                    |axis| {
+                        let rules = child.size_rules(sh, axis);
+                        #constraints.apply_rules(rules, axis, sh.scale_factor())
+                    }
+                );
+            });
+        } else {
+            size.append_all(quote! {
+                let child = &mut self.#ident;
+                solver.for_child(
+                    &mut #data,
+                    #child_info,
+                    |axis| child.size_rules(sh, axis)
+                );
+            });
+        }
 
         set_rect.append_all(quote! { let mut align2 = align; });
         if let Some(toks) = args.halign_toks()? {
@@ -207,7 +238,16 @@ pub(crate) fn derive(
             set_rect.append_all(quote! { align2.vert = Some(#toks); });
         }
         set_rect.append_all(quote! {
-            self.#ident.set_rect(_mgr, setter.child_rect(&mut #data, #child_info), align2);
+            let rect2 = setter.child_rect(&mut #data, #child_info);
+        });
+        if let Some(ref constraints) = constraints {
+            set_rect.append_all(quote! {
+                let scale_factor = _mgr.size_handle(|sh| sh.scale_factor());
+                let rect2 = #constraints.apply_rect(rect2, align2, scale_factor);
+            });
+        }
+        set_rect.append_all(quote! {
+            self.#ident.set_rect(_mgr, rect2, align2);
         });
 
         draw.append_all(quote! {
@@ -233,6 +273,7 @@ pub(crate) fn derive(
         LayoutType::Down => quote! { (kas::dir::Down, #rows) },
         LayoutType::Up => quote! { (kas::dir::Up, #rows) },
         LayoutType::Grid => quote! { (#cols, #rows) },
+        LayoutType::Flow => quote! { #cols },
     };
 
     let find_id_body = find_id_area.unwrap_or_else(|| {
@@ -24,10 +24,18 @@ pub struct ThemeColours {
     pub bg_error: Colour,
     /// Text colour in an `EditBox`
     pub text: Colour,
+    /// Placeholder text colour in an empty `EditBox`
+    pub text_placeholder: Colour,
     /// Selected tect colour
     pub text_sel: Colour,
     /// Selected text background colour
     pub text_sel_bg: Colour,
+    /// Background colour of search matches
+    pub text_find_bg: Colour,
+    /// Background colour of the current line in a code editor
+    pub text_line_bg: Colour,
+    /// Colour of wavy underlines marking errors (e.g. spelling)
+    pub text_squiggle: Colour,
     /// Text colour in a `Label`
     pub label_text: Colour,
     /// Text colour on a `TextButton`
@@ -73,8 +81,12 @@ impl ThemeColours {
             bg_disabled: Colour::grey(0.85),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(0.0),
+            text_placeholder: Colour::grey(0.5),
             text_sel: Colour::grey(1.0),
             text_sel_bg: Colour::new(0.15, 0.525, 0.75),
+            text_find_bg: Colour::new(1.0, 0.9, 0.5),
+            text_line_bg: Colour::grey(0.93),
+            text_squiggle: Colour::new(0.9, 0.1, 0.1),
             label_text: Colour::grey(0.0),
             button_text: Colour::grey(1.0),
             nav_focus: Colour::new(1.0, 0.7, 0.5),
@@ -102,8 +114,12 @@ impl ThemeColours {
             bg_disabled: Colour::grey(0.85),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(0.0),
+            text_placeholder: Colour::grey(0.5),
             text_sel: Colour::grey(0.0),
             text_sel_bg: Colour::new(0.8, 0.72, 0.24),
+            text_find_bg: Colour::new(0.7, 0.85, 1.0),
+            text_line_bg: Colour::new(0.97, 0.95, 0.85),
+            text_squiggle: Colour::new(0.9, 0.1, 0.1),
             label_text: Colour::grey(0.0),
             button_text: Colour::grey(0.0),
             nav_focus: Colour::new(1.0, 0.7, 0.5),
@@ -124,8 +140,12 @@ impl ThemeColours {
             bg_disabled: Colour::grey(0.3),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(1.0),
+            text_placeholder: Colour::grey(0.55),
             text_sel: Colour::grey(1.0),
             text_sel_bg: Colour::new(0.6, 0.3, 0.1),
+            text_find_bg: Colour::new(0.4, 0.4, 0.15),
+            text_line_bg: Colour::grey(0.18),
+            text_squiggle: Colour::new(1.0, 0.35, 0.3),
             label_text: Colour::grey(1.0),
             button_text: Colour::grey(1.0),
             nav_focus: Colour::new(1.0, 0.7, 0.5),
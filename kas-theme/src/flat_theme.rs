@@ -291,6 +291,20 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
             .text(self.pass, pos.into(), bounds, offset.into(), text, col);
     }
 
+    fn text_placeholder(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        _: TextClass,
+    ) {
+        let pos = pos + self.offset;
+        let col = self.cols.text_placeholder;
+        self.draw
+            .text(self.pass, pos.into(), bounds, offset.into(), text, col);
+    }
+
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.draw.text_col_effects(
             self.pass,
@@ -303,6 +317,34 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         );
     }
 
+    fn text_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        let pos = Vec2::from(pos + self.offset);
+        let offset = Vec2::from(offset);
+        if effects.first().map(|e| e.start == 0).unwrap_or(false) {
+            self.draw
+                .text_effects(self.pass, pos, bounds, offset, text, effects);
+        } else {
+            // Text before the first effect uses the default colour
+            let mut list = Vec::with_capacity(effects.len() + 1);
+            list.push(Effect {
+                start: 0,
+                flags: Default::default(),
+                aux: self.cols.text_class(class),
+            });
+            list.extend_from_slice(effects);
+            self.draw
+                .text_effects(self.pass, pos, bounds, offset, text, &list);
+        }
+    }
+
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         let pos = Vec2::from(pos + self.offset);
         let offset = Vec2::ZERO;
@@ -326,10 +368,28 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         text: &TextDisplay,
         range: Range<usize>,
         class: TextClass,
+    ) {
+        let col = self.cols.text_class(class);
+        let effects = [Effect {
+            start: 0,
+            flags: Default::default(),
+            aux: col,
+        }];
+        self.text_selected_col_effects(pos, bounds, offset, text, range, &effects, class);
+    }
+
+    fn text_selected_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        range: Range<usize>,
+        effects: &[Effect<Colour>],
+        class: TextClass,
     ) {
         let pos = Vec2::from(pos + self.offset);
         let offset = Vec2::from(offset);
-        let col = self.cols.text_class(class);
 
         // Draw background:
         for (p1, p2) in &text.highlight_lines(range.clone()) {
@@ -345,25 +405,108 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
             self.draw.rect(self.pass, quad, self.cols.text_sel_bg);
         }
 
-        let effects = [
-            Effect {
-                start: 0,
-                flags: Default::default(),
-                aux: col,
-            },
-            Effect {
-                start: range.start.cast(),
-                flags: Default::default(),
-                aux: self.cols.text_sel,
-            },
-            Effect {
-                start: range.end.cast(),
-                flags: Default::default(),
-                aux: col,
-            },
-        ];
+        // Merge the selection into effects: the selection colour applies
+        // within range, while flags (e.g. underline) are kept
+        let (sel_start, sel_end): (u32, u32) = (range.start.cast(), range.end.cast());
+        let mut starts: Vec<u32> = effects.iter().map(|e| e.start).collect();
+        starts.extend_from_slice(&[0, sel_start, sel_end]);
+        starts.sort_unstable();
+        starts.dedup();
+
+        let mut next = 0;
+        let mut flags = Default::default();
+        let mut aux = self.cols.text_class(class);
+        let mut list = Vec::with_capacity(starts.len());
+        for start in starts {
+            while next < effects.len() && effects[next].start <= start {
+                flags = effects[next].flags;
+                aux = effects[next].aux;
+                next += 1;
+            }
+            let aux = match sel_start <= start && start < sel_end {
+                true => self.cols.text_sel,
+                false => aux,
+            };
+            list.push(Effect { start, flags, aux });
+        }
         self.draw
-            .text_effects(self.pass, pos, bounds, offset, text, &effects);
+            .text_effects(self.pass, pos, bounds, offset, text, &list);
+    }
+
+    fn text_highlights(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        let pos = Vec2::from(pos + self.offset);
+        let offset = Vec2::from(offset);
+
+        for range in ranges {
+            for (p1, p2) in &text.highlight_lines(range.clone()) {
+                let mut p1 = Vec2::from(*p1) - offset;
+                let mut p2 = Vec2::from(*p2) - offset;
+                if !p2.gt(Vec2::ZERO) || !p1.lt(bounds) {
+                    continue;
+                }
+                p1 = p1.max(Vec2::ZERO);
+                p2 = p2.min(bounds);
+
+                let quad = Quad::with_coords(pos + p1, pos + p2);
+                self.draw.rect(self.pass, quad, self.cols.text_find_bg);
+            }
+        }
+    }
+
+    fn text_squiggles(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        let pos = Vec2::from(pos + self.offset);
+        let offset = Vec2::from(offset);
+        let width = self.window.dims.font_marker_width;
+        let step = 2.0 * width;
+        let col = self.cols.text_squiggle;
+
+        for range in ranges {
+            for (p1, p2) in &text.highlight_lines(range.clone()) {
+                let p1 = Vec2::from(*p1) - offset;
+                let p2 = Vec2::from(*p2) - offset;
+                // The squiggle is drawn just above the bottom of the line
+                let y = p2.1 - step;
+                if p2.0 <= 0.0 || p1.0 >= bounds.0 || y < 0.0 || y > bounds.1 {
+                    continue;
+                }
+                let (x1, x2) = (p1.0.max(0.0), p2.0.min(bounds.0));
+
+                // Zig-zag between y - step/2 and y + step/2
+                let mut x = x1;
+                let mut up = true;
+                while x < x2 {
+                    let next = (x + step).min(x2);
+                    let (ya, yb) = match up {
+                        true => (y + 0.5 * step, y - 0.5 * step),
+                        false => (y - 0.5 * step, y + 0.5 * step),
+                    };
+                    let a = pos + Vec2(x, ya);
+                    let b = pos + Vec2(next, yb);
+                    self.draw.rounded_line(self.pass, a, b, 0.5 * width, col);
+                    x = next;
+                    up = !up;
+                }
+            }
+        }
+    }
+
+    fn edit_line_highlight(&mut self, rect: Rect) {
+        let quad = Quad::from(rect + self.offset);
+        self.draw.rect(self.pass, quad, self.cols.text_line_bg);
     }
 
     fn edit_marker(
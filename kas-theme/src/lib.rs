@@ -59,5 +59,6 @@ fn relative_region_depth(class: ClipRegion) -> f32 {
     match class {
         ClipRegion::Popup => 0.01,
         ClipRegion::Scroll => -1e-5,
+        ClipRegion::Overlay => 1e-4,
     }
 }
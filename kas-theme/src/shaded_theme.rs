@@ -15,7 +15,7 @@ use kas::draw::{
     Pass, SizeHandle, TextClass, ThemeAction, ThemeApi,
 };
 use kas::geom::*;
-use kas::text::{AccelString, Text, TextApi, TextDisplay};
+use kas::text::{AccelString, Effect, Text, TextApi, TextDisplay};
 
 /// A theme using simple shading to give apparent depth to elements
 #[derive(Clone, Debug)]
@@ -303,10 +303,35 @@ where
         self.as_flat().text_offset(pos, bounds, offset, text, class);
     }
 
+    fn text_placeholder(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        class: TextClass,
+    ) {
+        self.as_flat()
+            .text_placeholder(pos, bounds, offset, text, class);
+    }
+
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.as_flat().text_effects(pos, offset, text, class);
     }
 
+    fn text_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.as_flat()
+            .text_col_effects(pos, bounds, offset, text, effects, class);
+    }
+
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.as_flat().text_accel(pos, text, state, class);
     }
@@ -324,6 +349,48 @@ where
             .text_selected_range(pos, bounds, offset, text, range, class);
     }
 
+    fn text_selected_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        range: Range<usize>,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.as_flat()
+            .text_selected_col_effects(pos, bounds, offset, text, range, effects, class);
+    }
+
+    fn text_highlights(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.as_flat()
+            .text_highlights(pos, bounds, offset, text, ranges);
+    }
+
+    fn text_squiggles(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.as_flat()
+            .text_squiggles(pos, bounds, offset, text, ranges);
+    }
+
+    fn edit_line_highlight(&mut self, rect: Rect) {
+        self.as_flat().edit_line_highlight(rect);
+    }
+
     fn edit_marker(
         &mut self,
         pos: Coord,
@@ -0,0 +1,89 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! System clipboard
+
+use ::clipboard::{ClipboardContext, ClipboardProvider};
+use kas::clipboard::{Clipboard, ClipboardContent, MemoryClipboard, TEXT_PLAIN};
+use log::warn;
+
+/// System clipboard, via the `clipboard` crate
+///
+/// The system clipboard only supports plain text. Full content set by this
+/// application is kept locally and remains available for as long as the
+/// system clipboard still holds the same text; otherwise (i.e. when another
+/// application has set the clipboard) only plain text is available.
+pub struct SystemClipboard {
+    context: Option<ClipboardContext>,
+    local: MemoryClipboard,
+}
+
+impl SystemClipboard {
+    pub fn new() -> Self {
+        let context = match ClipboardContext::new() {
+            Ok(cb) => Some(cb),
+            Err(e) => {
+                warn!("Unable to open clipboard: {:?}", e);
+                None
+            }
+        };
+        SystemClipboard {
+            context,
+            local: MemoryClipboard::new(),
+        }
+    }
+
+    fn system_text(&mut self) -> Option<String> {
+        self.context
+            .as_mut()
+            .and_then(|cb| match cb.get_contents() {
+                Ok(c) => Some(c),
+                Err(e) => {
+                    warn!("Failed to get clipboard contents: {:?}", e);
+                    None
+                }
+            })
+    }
+
+    /// True if the local content matches the system clipboard
+    fn is_local(&self, text: &Option<String>) -> bool {
+        // Content without text is published as an empty string
+        let local = self.local.content().text().unwrap_or("");
+        self.context.is_none() || text.as_deref() == Some(local)
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn types(&mut self) -> Vec<String> {
+        let text = self.system_text();
+        if self.is_local(&text) {
+            self.local.types()
+        } else if text.is_some() {
+            vec![TEXT_PLAIN.to_string()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn get(&mut self, mime: &str) -> Option<Vec<u8>> {
+        let text = self.system_text();
+        if self.is_local(&text) {
+            self.local.get(mime)
+        } else if mime == TEXT_PLAIN || mime == "text/plain" {
+            text.map(|s| s.into_bytes())
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, content: ClipboardContent) {
+        if let Some(cb) = self.context.as_mut() {
+            let text = content.text().unwrap_or("").to_string();
+            cb.set_contents(text)
+                .unwrap_or_else(|e| warn!("Failed to set clipboard contents: {:?}", e));
+        }
+        self.local.set(content);
+    }
+}
@@ -237,6 +237,11 @@ where
                         window.theme_resize(&mut self.shared);
                     }
                 }
+                PendingAction::Reconfigure => {
+                    for (_, window) in self.windows.iter_mut() {
+                        window.reconfigure(&mut self.shared);
+                    }
+                }
                 PendingAction::RedrawAll => {
                     for (_, window) in self.windows.iter_mut() {
                         window.window.request_redraw();
@@ -9,7 +9,9 @@
 //! GPU-accelerated rendering.
 //!
 //! Windowing is provided by [winit].
-//! Clipboard functionality is (currently) provided by the [clipboard] crate.
+//! System clipboard functionality is (currently) provided by the [clipboard]
+//! crate; this supports only plain text. Without the `clipboard` feature, an
+//! in-memory clipboard is used.
 //!
 //! [WebGPU]: https://github.com/gfx-rs/wgpu-rs
 //! [winit]: https://github.com/rust-windowing/winit
@@ -17,9 +19,12 @@
 
 #![cfg_attr(feature = "gat", feature(generic_associated_types))]
 
+#[cfg(feature = "clipboard")]
+mod clipboard;
 pub mod draw;
 mod event_loop;
 pub mod options;
+mod primary;
 mod shared;
 mod window;
 
@@ -28,6 +33,7 @@ use std::rc::Rc;
 use thiserror::Error;
 
 use kas::event::UpdateHandle;
+use kas::i18n::Catalogue;
 use kas::WindowId;
 use kas_theme::Theme;
 use winit::error::OsError;
@@ -95,6 +101,16 @@ where
     pub fn new(theme: T) -> Result<Self, Error> {
         Self::new_custom((), theme, Options::from_env())
     }
+
+    /// Construct a new instance with default options and an application id
+    ///
+    /// This is like [`Toolkit::new`], but additionally reads per-application
+    /// config from a sub-directory named `app_id` of the platform-default
+    /// config directory. See [`Options::app_id`] and [`Options::config_dirs`].
+    #[inline]
+    pub fn new_app<S: ToString>(app_id: S, theme: T) -> Result<Self, Error> {
+        Self::new_custom((), theme, Options::from_env().with_app_id(app_id))
+    }
 }
 
 impl<C: CustomPipe + 'static, T: Theme<DrawPipe<C>> + 'static> Toolkit<C, T>
@@ -181,6 +197,24 @@ where
         Ok(self)
     }
 
+    /// Set the message catalogue
+    ///
+    /// This sets the initial language; existing windows are re-configured.
+    /// See also [`kas::event::Manager::set_language`].
+    pub fn set_catalogue(&mut self, catalogue: Option<Rc<dyn Catalogue>>) {
+        self.shared.set_catalogue(catalogue);
+        for window in &mut self.windows {
+            window.reconfigure(&mut self.shared);
+        }
+    }
+
+    /// Set the message catalogue, inline
+    #[inline]
+    pub fn with_catalogue(mut self, catalogue: Rc<dyn Catalogue>) -> Self {
+        self.set_catalogue(Some(catalogue));
+        self
+    }
+
     /// Create a proxy which can be used to update the UI from another thread
     pub fn create_proxy(&self) -> ToolkitProxy {
         ToolkitProxy {
@@ -6,9 +6,12 @@
 //! Options
 
 use super::Error;
-use log::warn;
-use std::env::var;
-use std::path::PathBuf;
+use kas::event::{Config, ConfigFormat};
+use log::{info, warn};
+use std::env::{var, var_os};
+#[cfg(not(windows))]
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 pub use wgpu::{BackendBit, PowerPreference};
 
 /// Config mode
@@ -27,21 +30,34 @@ pub enum ConfigMode {
 pub struct Options {
     /// Config file path. Default: empty. See `KAS_CONFIG` doc.
     pub config_path: PathBuf,
+    /// Application identifier. Default: empty.
+    ///
+    /// If not empty, this is used as the name of a sub-directory of the
+    /// platform-default config directory, providing per-application config.
+    /// It should therefore be a valid file name (e.g. `my-app`).
+    pub app_id: String,
     /// Config mode. Default: Read.
     pub config_mode: ConfigMode,
     /// Adapter power preference. Default value: low power.
     pub power_preference: PowerPreference,
     /// Adapter backend. Default value: PRIMARY (Vulkan/Metal/DX12).
     pub backends: BackendBit,
+    /// Show the layout debugging overlay. Default value: false.
+    ///
+    /// The overlay may also be toggled with <kbd>Ctrl+Shift+F12</kbd>.
+    /// See [`kas::layout::DebugOverlay`].
+    pub debug_layout: bool,
 }
 
 impl Default for Options {
     fn default() -> Self {
         Options {
             config_path: PathBuf::new(),
+            app_id: String::new(),
             config_mode: ConfigMode::Read,
             power_preference: PowerPreference::LowPower,
             backends: BackendBit::PRIMARY,
+            debug_layout: false,
         }
     }
 }
@@ -58,15 +74,21 @@ impl Options {
     ///
     /// WARNING: file formats are unstable!
     ///
-    /// If `KAS_CONFIG` is not set, platform-default configuration is used
-    /// without reading or writing. This may change to use a platform-specific
-    /// default path in future versions.
+    /// If `KAS_CONFIG` is not set, configuration is discovered from
+    /// platform-default locations (see [`Options::config_dirs`]). Within each
+    /// directory, the first of `config.yaml`, `config.json` and `config.toml`
+    /// which exists and whose format is supported (see feature flags) is used.
+    /// Files are applied as layers over the built-in defaults: later layers
+    /// override individual items of earlier layers.
     ///
     /// The `KAS_CONFIG_MODE` variable determines the read/write mode:
     ///
     /// -   `Read` (default): read-only
     /// -   `WriteDefault`: generate platform-default configuration, and write
-    ///     it to the config path, overwriting any existing config
+    ///     it to the config path, overwriting any existing config. If
+    ///     `KAS_CONFIG` is not set, the application config directory is used
+    ///     (see [`Options::config_dirs`]) when [`Options::app_id`] is set;
+    ///     otherwise nothing is written (shared config is never overwritten).
     ///
     /// Note: in the future, the default will likely change to a read-write mode,
     /// allowing changes to be written out.
@@ -90,6 +112,11 @@ impl Options {
     /// -   `DX12`
     /// -   `PRIMARY`: any of Vulkan, Metal or DX12
     /// -   `SECONDARY`: any of GL or DX11
+    ///
+    /// ### Layout debugging
+    ///
+    /// The `KAS_DEBUG_LAYOUT` variable enables the layout debugging overlay
+    /// (see [`Options::debug_layout`]) when set to `1`, `true` or `yes`.
     pub fn from_env() -> Self {
         let mut options = Options::default();
 
@@ -141,6 +168,18 @@ impl Options {
             }
         }
 
+        if let Ok(mut v) = var("KAS_DEBUG_LAYOUT") {
+            v.make_ascii_uppercase();
+            options.debug_layout = match v.as_str() {
+                "1" | "TRUE" | "YES" => true,
+                "" | "0" | "FALSE" | "NO" => false,
+                other => {
+                    warn!("Unexpected environment value: KAS_DEBUG_LAYOUT={}", other);
+                    options.debug_layout
+                }
+            }
+        }
+
         options
     }
 
@@ -155,22 +194,195 @@ impl Options {
         self.backends
     }
 
+    /// Set the application identifier
+    ///
+    /// See [`Options::app_id`].
+    pub fn with_app_id<S: ToString>(mut self, app_id: S) -> Self {
+        self.app_id = app_id.to_string();
+        self
+    }
+
+    /// Platform-default config directories
+    ///
+    /// Directories are returned in order of increasing priority. Not all
+    /// directories need exist.
+    ///
+    /// On Unix-like systems (following the XDG Base Directory specification):
+    ///
+    /// 1.  System-wide: `kas` within each directory of `$XDG_CONFIG_DIRS`
+    ///     (default: `/etc/xdg`), least important first
+    /// 2.  User: `$XDG_CONFIG_HOME/kas` (default: `~/.config/kas`)
+    /// 3.  Application: `<user>/<app_id>`, if [`Options::app_id`] is not empty
+    ///
+    /// On Windows, `%PROGRAMDATA%\kas` and `%APPDATA%\kas` are used as the
+    /// system-wide and user directories respectively.
+    pub fn config_dirs(&self) -> Vec<PathBuf> {
+        collect_config_dirs(system_config_dirs(), user_config_dir(), &self.app_id)
+    }
+
     /// Load KAS config
-    pub fn config(&self) -> Result<kas::event::Config, Error> {
+    pub fn config(&self) -> Result<Config, Error> {
         if !self.config_path.as_os_str().is_empty() {
             match self.config_mode {
-                ConfigMode::Read => Ok(kas::event::Config::from_path(
-                    &self.config_path,
-                    Default::default(),
-                )?),
+                ConfigMode::Read => Ok(Config::from_path(&self.config_path, Default::default())?),
                 ConfigMode::WriteDefault => {
-                    let config: kas::event::Config = Default::default();
+                    let config: Config = Default::default();
                     config.write_path(&self.config_path, Default::default())?;
                     Ok(config)
                 }
             }
         } else {
-            Ok(Default::default())
+            match self.config_mode {
+                ConfigMode::Read => {
+                    let mut config = Config::default();
+                    for dir in self.config_dirs() {
+                        if let Some((path, format)) = find_config_file(&dir) {
+                            info!("Reading config: {}", path.display());
+                            config.apply_path(&path, format)?;
+                        }
+                    }
+                    Ok(config)
+                }
+                ConfigMode::WriteDefault => {
+                    let config: Config = Default::default();
+                    if self.app_id.is_empty() {
+                        warn!("Not writing config: requires KAS_CONFIG or an app_id");
+                        return Ok(config);
+                    }
+                    let dir = user_config_dir().map(|dir| dir.join(&self.app_id));
+                    let format = FORMATS.iter().cloned().find(|f| f.is_supported());
+                    if let (Some(dir), Some(format)) = (dir, format) {
+                        let mut path = dir.join("config");
+                        path.set_extension(format.extension().unwrap());
+                        info!("Writing config: {}", path.display());
+                        std::fs::create_dir_all(&dir).map_err(kas::event::ConfigError::from)?;
+                        config.write_path(&path, format)?;
+                    } else {
+                        warn!("Unable to write config: no config directory or supported format");
+                    }
+                    Ok(config)
+                }
+            }
+        }
+    }
+}
+
+/// Config formats, in order of preference
+const FORMATS: [ConfigFormat; 3] = [ConfigFormat::Yaml, ConfigFormat::Json, ConfigFormat::Toml];
+
+/// Find a config file within `dir`
+fn find_config_file(dir: &Path) -> Option<(PathBuf, ConfigFormat)> {
+    for format in FORMATS.iter().cloned().filter(|f| f.is_supported()) {
+        let mut path = dir.join("config");
+        path.set_extension(format.extension().unwrap());
+        if path.is_file() {
+            return Some((path, format));
+        }
+    }
+    None
+}
+
+/// Combine system and user config directories (see [`Options::config_dirs`])
+fn collect_config_dirs(
+    mut dirs: Vec<PathBuf>,
+    user_dir: Option<PathBuf>,
+    app_id: &str,
+) -> Vec<PathBuf> {
+    if let Some(mut dir) = user_dir {
+        dirs.push(dir.clone());
+        if !app_id.is_empty() {
+            dir.push(app_id);
+            dirs.push(dir);
         }
     }
+    dirs
+}
+
+#[cfg(not(windows))]
+fn system_config_dirs() -> Vec<PathBuf> {
+    xdg_system_config_dirs(var_os("XDG_CONFIG_DIRS"))
+}
+
+/// System config dirs given the value of `$XDG_CONFIG_DIRS`
+#[cfg(not(windows))]
+fn xdg_system_config_dirs(xdg_config_dirs: Option<OsString>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match xdg_config_dirs {
+        Some(v) if !v.is_empty() => std::env::split_paths(&v)
+            .filter(|p| p.is_absolute())
+            .collect(),
+        _ => vec!["/etc/xdg".into()],
+    };
+    // XDG lists directories in order of decreasing importance:
+    dirs.reverse();
+    for dir in &mut dirs {
+        dir.push("kas");
+    }
+    dirs
+}
+
+#[cfg(windows)]
+fn system_config_dirs() -> Vec<PathBuf> {
+    var_os("PROGRAMDATA")
+        .map(|v| vec![PathBuf::from(v).join("kas")])
+        .unwrap_or_default()
+}
+
+#[cfg(not(windows))]
+fn user_config_dir() -> Option<PathBuf> {
+    xdg_user_config_dir(var_os("XDG_CONFIG_HOME"), var_os("HOME"))
+}
+
+/// User config dir given the values of `$XDG_CONFIG_HOME` and `$HOME`
+#[cfg(not(windows))]
+fn xdg_user_config_dir(
+    xdg_config_home: Option<OsString>,
+    home: Option<OsString>,
+) -> Option<PathBuf> {
+    match xdg_config_home {
+        Some(v) if Path::new(&v).is_absolute() => Some(PathBuf::from(v).join("kas")),
+        _ => home
+            .filter(|v| !v.is_empty())
+            .map(|v| PathBuf::from(v).join(".config").join("kas")),
+    }
+}
+
+#[cfg(windows)]
+fn user_config_dir() -> Option<PathBuf> {
+    var_os("APPDATA").map(|v| PathBuf::from(v).join("kas"))
+}
+
+#[cfg(not(windows))]
+#[test]
+fn config_dirs() {
+    let system = xdg_system_config_dirs(Some("/etc/first:relative:/etc/second".into()));
+    let user = xdg_user_config_dir(Some("/home/user/.config".into()), None);
+    let dirs = collect_config_dirs(system.clone(), user.clone(), "my-app");
+    let expected: Vec<PathBuf> = vec![
+        "/etc/second/kas".into(),
+        "/etc/first/kas".into(),
+        "/home/user/.config/kas".into(),
+        "/home/user/.config/kas/my-app".into(),
+    ];
+    assert_eq!(dirs, expected);
+
+    let dirs = collect_config_dirs(system, user, "");
+    assert_eq!(dirs, &expected[..3]);
+}
+
+#[cfg(not(windows))]
+#[test]
+fn config_dirs_defaults() {
+    let system = xdg_system_config_dirs(None);
+    assert_eq!(system, vec![PathBuf::from("/etc/xdg/kas")]);
+    assert_eq!(xdg_system_config_dirs(Some("".into())), system);
+
+    let home = Some("/home/user".into());
+    let expected = Some(PathBuf::from("/home/user/.config/kas"));
+    assert_eq!(xdg_user_config_dir(None, home.clone()), expected);
+    // Relative paths are ignored
+    assert_eq!(xdg_user_config_dir(Some("cfg".into()), home), expected);
+    assert_eq!(xdg_user_config_dir(None, Some("".into())), None);
+    assert_eq!(xdg_user_config_dir(None, None), None);
+
+    assert_eq!(collect_config_dirs(system.clone(), None, "my-app"), system);
 }
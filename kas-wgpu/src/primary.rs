@@ -0,0 +1,116 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Primary selection
+
+#[cfg(all(
+    feature = "clipboard",
+    unix,
+    not(any(target_os = "macos", target_os = "android"))
+))]
+mod system {
+    use ::clipboard::{
+        x11_clipboard::{Primary, X11ClipboardContext},
+        ClipboardProvider,
+    };
+    use log::warn;
+
+    /// The system's primary selection
+    pub struct Context(X11ClipboardContext<Primary>);
+
+    impl Context {
+        pub fn new() -> Option<Self> {
+            match X11ClipboardContext::new() {
+                Ok(cb) => Some(Context(cb)),
+                Err(e) => {
+                    warn!("Unable to open primary selection: {:?}", e);
+                    None
+                }
+            }
+        }
+
+        pub fn get(&mut self) -> Option<String> {
+            match self.0.get_contents() {
+                Ok(c) => Some(c),
+                Err(e) => {
+                    warn!("Failed to get primary selection: {:?}", e);
+                    None
+                }
+            }
+        }
+
+        pub fn set(&mut self, content: String) {
+            self.0
+                .set_contents(content)
+                .unwrap_or_else(|e| warn!("Failed to set primary selection: {:?}", e));
+        }
+    }
+}
+
+#[cfg(not(all(
+    feature = "clipboard",
+    unix,
+    not(any(target_os = "macos", target_os = "android"))
+)))]
+mod system {
+    /// Placeholder: no system primary selection is available
+    pub enum Context {}
+
+    impl Context {
+        pub fn new() -> Option<Self> {
+            None
+        }
+
+        pub fn get(&mut self) -> Option<String> {
+            match *self {}
+        }
+
+        pub fn set(&mut self, _: String) {
+            match *self {}
+        }
+    }
+}
+
+/// Primary selection
+///
+/// With the `clipboard` feature on X11 platforms (including Wayland via
+/// XWayland), this uses the system's primary selection. Otherwise, the
+/// selection is available only within this application.
+#[derive(Default)]
+pub struct PrimarySelection {
+    context: Option<system::Context>,
+    local: Option<String>,
+}
+
+impl PrimarySelection {
+    pub fn new() -> Self {
+        PrimarySelection {
+            context: system::Context::new(),
+            local: None,
+        }
+    }
+
+    pub fn get(&mut self) -> Option<String> {
+        if let Some(cb) = self.context.as_mut() {
+            return cb.get();
+        }
+        self.local.clone()
+    }
+
+    pub fn set(&mut self, content: String) {
+        if let Some(cb) = self.context.as_mut() {
+            cb.set(content.clone());
+        }
+        self.local = Some(content);
+    }
+}
+
+#[test]
+fn local_selection() {
+    let mut primary = PrimarySelection::default();
+    assert_eq!(primary.get(), None);
+    primary.set("text".to_string());
+    assert_eq!(primary.get(), Some("text".to_string()));
+}
@@ -12,18 +12,19 @@ use std::num::NonZeroU32;
 use std::rc::Rc;
 
 use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow, ShaderManager};
+use crate::primary::PrimarySelection;
 use crate::{Error, Options, WindowId};
+use kas::clipboard::Clipboard;
 use kas::data::SharedData;
 use kas::event::UpdateHandle;
+use kas::i18n::Catalogue;
 use kas_theme::Theme;
 
-#[cfg(feature = "clipboard")]
-use clipboard::{ClipboardContext, ClipboardProvider};
-
 /// State shared between windows
 pub struct SharedState<C: CustomPipe, T> {
-    #[cfg(feature = "clipboard")]
-    clipboard: Option<ClipboardContext>,
+    clipboard: Box<dyn Clipboard>,
+    primary: PrimarySelection,
+    catalogue: Option<Rc<dyn Catalogue>>,
     data_updates: HashMap<UpdateHandle, Vec<Rc<dyn SharedData>>>,
     pub instance: wgpu::Instance,
     pub device: wgpu::Device,
@@ -36,6 +37,8 @@ pub struct SharedState<C: CustomPipe, T> {
     /// Newly created windows need to know the scale_factor *before* they are
     /// created. This is used to estimate ideal window size.
     pub scale_factor: f64,
+    /// Whether new windows show the layout debugging overlay
+    pub debug_layout: bool,
     window_id: u32,
 }
 
@@ -52,13 +55,9 @@ where
         scale_factor: f64,
     ) -> Result<Self, Error> {
         #[cfg(feature = "clipboard")]
-        let clipboard = match ClipboardContext::new() {
-            Ok(cb) => Some(cb),
-            Err(e) => {
-                warn!("Unable to open clipboard: {:?}", e);
-                None
-            }
-        };
+        let clipboard = Box::new(crate::clipboard::SystemClipboard::new());
+        #[cfg(not(feature = "clipboard"))]
+        let clipboard = Box::new(kas::clipboard::MemoryClipboard::new());
 
         let instance = wgpu::Instance::new(options.backend());
         let adapter_options = options.adapter_options();
@@ -81,10 +80,12 @@ where
         let mut draw = DrawPipe::new(custom, &device, &shaders);
 
         theme.init(&mut draw);
+        let debug_layout = options.debug_layout;
 
         Ok(SharedState {
-            #[cfg(feature = "clipboard")]
             clipboard,
+            primary: PrimarySelection::new(),
+            catalogue: None,
             data_updates: Default::default(),
             instance,
             device,
@@ -95,6 +96,7 @@ where
             config,
             pending: vec![],
             scale_factor,
+            debug_layout,
             window_id: 0,
         })
     }
@@ -119,35 +121,30 @@ where
         );
     }
 
-    #[cfg(not(feature = "clipboard"))]
     #[inline]
-    pub fn get_clipboard(&mut self) -> Option<String> {
-        None
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        &mut *self.clipboard
     }
 
-    #[cfg(feature = "clipboard")]
-    pub fn get_clipboard(&mut self) -> Option<String> {
-        self.clipboard
-            .as_mut()
-            .and_then(|cb| match cb.get_contents() {
-                Ok(c) => Some(c),
-                Err(e) => {
-                    warn!("Failed to get clipboard contents: {:?}", e);
-                    None
-                }
-            })
+    #[inline]
+    pub fn get_primary(&mut self) -> Option<String> {
+        self.primary.get()
+    }
+
+    #[inline]
+    pub fn set_primary(&mut self, content: String) {
+        self.primary.set(content);
+    }
+
+    #[inline]
+    pub fn catalogue(&self) -> Option<Rc<dyn Catalogue>> {
+        self.catalogue.clone()
     }
 
-    #[cfg(not(feature = "clipboard"))]
+    /// Set the catalogue without reconfiguring windows
     #[inline]
-    pub fn set_clipboard<'c>(&mut self, _: std::borrow::Cow<'c, str>) {}
-
-    #[cfg(feature = "clipboard")]
-    pub fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
-        self.clipboard.as_mut().map(|cb| {
-            cb.set_contents(content.into())
-                .unwrap_or_else(|e| warn!("Failed to set clipboard contents: {:?}", e))
-        });
+    pub fn set_catalogue(&mut self, catalogue: Option<Rc<dyn Catalogue>>) {
+        self.catalogue = catalogue;
     }
 
     pub fn update_shared_data(&mut self, handle: UpdateHandle, data: Rc<dyn SharedData>) {
@@ -198,6 +195,7 @@ pub enum PendingAction {
     AddWindow(WindowId, Box<dyn kas::Window>),
     CloseWindow(WindowId),
     ThemeResize,
+    Reconfigure,
     RedrawAll,
     Update(kas::event::UpdateHandle, u64),
 }
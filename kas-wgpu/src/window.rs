@@ -9,17 +9,20 @@ use log::{debug, error, info, trace};
 use std::rc::Rc;
 use std::time::Instant;
 
+use kas::clipboard::Clipboard;
 use kas::conv::Cast;
 use kas::data::SharedData;
+use kas::dir::LayoutDir;
 use kas::draw::{SizeHandle, ThemeAction, ThemeApi};
-use kas::event::{CursorIcon, ManagerState, UpdateHandle};
+use kas::event::{CursorIcon, ManagerState, ModifiersState, UpdateHandle};
 use kas::geom::{Coord, Rect, Size};
-use kas::layout::SolveCache;
+use kas::i18n::Catalogue;
+use kas::layout::{DebugOverlay, SolveCache};
 use kas::{TkAction, WindowId};
 use kas_theme::Theme;
 use winit::dpi::PhysicalSize;
 use winit::error::OsError;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, KeyboardInput, VirtualKeyCode, WindowEvent};
 use winit::event_loop::EventLoopWindowTarget;
 use winit::window::WindowBuilder;
 
@@ -40,6 +43,7 @@ pub(crate) struct Window<CW: CustomWindow, TW> {
     swap_chain: wgpu::SwapChain,
     draw: DrawWindow<CW>,
     theme_window: TW,
+    debug_overlay: Option<DebugOverlay>,
 }
 
 // Public functions, for use by the toolkit
@@ -66,8 +70,14 @@ where
         let mut draw = shared.draw.new_window(&mut shared.device, Size::ZERO);
         let mut theme_window = shared.theme.new_window(&mut draw, scale_factor);
 
+        // The window may override this; if so, rules are re-solved on resize
+        let dir = shared
+            .catalogue()
+            .map(|cat| LayoutDir::from_language(cat.language()))
+            .unwrap_or_default();
         let mut size_handle = unsafe { theme_window.size_handle() };
-        let solve_cache = SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
+        let solve_cache =
+            SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle, dir);
         // Opening a zero-size window causes a crash, so force at least 1x1:
         let ideal = solve_cache.ideal(true).max(Size(1, 1));
         drop(size_handle);
@@ -105,6 +115,11 @@ where
         let mut tkw = TkWindow::new(shared, &window, &mut theme_window);
         mgr.configure(&mut tkw, &mut *widget);
 
+        let debug_overlay = match shared.debug_layout {
+            false => None,
+            true => Some(DebugOverlay::new()),
+        };
+
         let mut r = Window {
             widget,
             window_id,
@@ -116,6 +131,7 @@ where
             swap_chain,
             draw,
             theme_window,
+            debug_overlay,
         };
         r.apply_size(shared);
 
@@ -159,6 +175,17 @@ where
                 self.solve_cache.invalidate_rule_cache();
                 self.do_resize(shared, *new_inner_size);
             }
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        state: ElementState::Pressed,
+                        virtual_keycode: Some(VirtualKeyCode::F12),
+                        ..
+                    },
+                ..
+            } if self.mgr.modifiers() == ModifiersState::CTRL | ModifiersState::SHIFT => {
+                self.toggle_debug_overlay(shared);
+            }
             event @ _ => {
                 let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
                 let widget = &mut *self.widget;
@@ -205,6 +232,15 @@ where
             self.window.request_redraw();
         }
 
+        if let Some(overlay) = self.debug_overlay.as_mut() {
+            if overlay.update_hover(&self.mgr) {
+                self.window.request_redraw();
+            }
+        }
+        if self.mgr.wants_animation_frame() {
+            self.window.request_redraw();
+        }
+
         (action, self.mgr.next_resume())
     }
 
@@ -298,7 +334,21 @@ where
         Size::new(self.sc_desc.width.cast(), self.sc_desc.height.cast())
     }
 
-    fn reconfigure<C, T>(&mut self, shared: &mut SharedState<C, T>)
+    fn toggle_debug_overlay<C, T>(&mut self, shared: &mut SharedState<C, T>)
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        self.debug_overlay = match self.debug_overlay {
+            Some(_) => None,
+            None => Some(DebugOverlay::new()),
+        };
+        info!("Layout debug overlay: {}", self.debug_overlay.is_some());
+        // Collects size rules (if enabled) and redraws
+        self.apply_size(shared);
+    }
+
+    pub fn reconfigure<C, T>(&mut self, shared: &mut SharedState<C, T>)
     where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
@@ -326,8 +376,15 @@ where
         let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
         let solve_cache = &mut self.solve_cache;
         let widget = &mut self.widget;
+        let debug_overlay = &mut self.debug_overlay;
         self.mgr.with(&mut tkw, |mgr| {
             solve_cache.apply_rect(widget.as_widget_mut(), mgr, rect, true);
+            if let Some(overlay) = debug_overlay.as_mut() {
+                // Collection requires widget rects and leaves size rules as
+                // solved above; we need only re-apply rects.
+                overlay.collect(widget.as_widget_mut(), mgr);
+                solve_cache.apply_rect(widget.as_widget_mut(), mgr, rect, true);
+            }
             widget.resize_popups(mgr);
         });
 
@@ -383,6 +440,14 @@ where
         let time = Instant::now();
         let rect = Rect::new(Coord::ZERO, self.sc_size());
 
+        if self.mgr.wants_animation_frame() {
+            let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut tkw, |mgr| {
+                mgr.update_animation_frame(widget);
+            });
+        }
+
         unsafe {
             // Safety: we must drop draw_handle after draw call (wrong lifetime)
             let mut draw_handle =
@@ -391,6 +456,14 @@ where
                     .draw_handle(&mut self.draw, &mut self.theme_window, rect);
             self.widget.draw(&mut draw_handle, &self.mgr, false);
         }
+        // Widgets request further frames while drawing
+        if self.mgr.wants_animation_frame() {
+            self.window.request_redraw();
+        }
+
+        if let Some(overlay) = self.debug_overlay.as_mut() {
+            overlay.draw(&mut self.draw, self.widget.as_widget(), rect);
+        }
 
         let time2 = Instant::now();
         let frame = match self.swap_chain.get_current_frame() {
@@ -497,13 +570,28 @@ where
     }
 
     #[inline]
-    fn get_clipboard(&mut self) -> Option<String> {
-        self.shared.get_clipboard()
+    fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.shared.clipboard()
     }
 
     #[inline]
-    fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
-        self.shared.set_clipboard(content);
+    fn get_primary(&mut self) -> Option<String> {
+        self.shared.get_primary()
+    }
+
+    #[inline]
+    fn set_primary(&mut self, content: String) {
+        self.shared.set_primary(content);
+    }
+
+    #[inline]
+    fn catalogue(&self) -> Option<Rc<dyn Catalogue>> {
+        self.shared.catalogue()
+    }
+
+    fn set_catalogue(&mut self, catalogue: Option<Rc<dyn Catalogue>>) {
+        self.shared.set_catalogue(catalogue);
+        self.shared.pending.push(PendingAction::Reconfigure);
     }
 
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
@@ -0,0 +1,307 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Animation support
+//!
+//! Animations are driven by frame callbacks: a widget which is animating calls
+//! [`ManagerState::request_animation_frame`] from its [`Layout::draw`] method,
+//! upon which the window is redrawn on the next frame and the widget receives
+//! [`Event::AnimationFrame`] immediately before this redraw. Animated values
+//! are a function of time, thus the widget need only query the current value
+//! (see [`Animated::value`]) when drawing.
+//!
+//! When [`Config::reduce_motion`] is set, widgets should not animate: use of
+//! [`Animated::animate`] ensures this.
+//!
+//! [`ManagerState::request_animation_frame`]: crate::event::ManagerState::request_animation_frame
+//! [`Layout::draw`]: crate::Layout::draw
+//! [`Event::AnimationFrame`]: crate::event::Event::AnimationFrame
+//! [`Config::reduce_motion`]: crate::event::Config::reduce_motion
+
+use std::time::{Duration, Instant};
+
+use crate::conv::ConvFloat;
+use crate::draw::Colour;
+use crate::event::Manager;
+use crate::geom::{Coord, Offset, Rect, Size};
+use crate::WidgetId;
+
+/// The default duration of transitions
+pub const DEFAULT_DURATION: Duration = Duration::from_millis(200);
+
+/// An easing curve
+///
+/// This maps linear progress `t` (from 0 to 1) to eased progress (also from 0
+/// to 1). Curves other than `Linear` are cubic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Start slow, end fast
+    EaseIn,
+    /// Start fast, end slow
+    EaseOut,
+    /// Start and end slow
+    EaseInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseOut
+    }
+}
+
+impl Easing {
+    /// Apply to linear progress `t`
+    ///
+    /// The input is clamped to the range `0.0..=1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - 0.5 * u * u * u
+                }
+            }
+        }
+    }
+}
+
+/// Types supporting interpolation
+pub trait Tween: Copy {
+    /// Interpolate between `a` and `b`
+    ///
+    /// The result should equal `a` when `t == 0.0` and `b` when `t == 1.0`.
+    fn tween(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Tween for f32 {
+    #[inline]
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Tween for i32 {
+    #[inline]
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        a + i32::conv_nearest((b - a) as f32 * t)
+    }
+}
+
+impl Tween for Colour {
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        Colour {
+            r: f32::tween(a.r, b.r, t),
+            g: f32::tween(a.g, b.g, t),
+            b: f32::tween(a.b, b.b, t),
+            a: f32::tween(a.a, b.a, t),
+        }
+    }
+}
+
+impl Tween for Coord {
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        Coord(i32::tween(a.0, b.0, t), i32::tween(a.1, b.1, t))
+    }
+}
+
+impl Tween for Size {
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        Size(i32::tween(a.0, b.0, t), i32::tween(a.1, b.1, t))
+    }
+}
+
+impl Tween for Offset {
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        Offset(i32::tween(a.0, b.0, t), i32::tween(a.1, b.1, t))
+    }
+}
+
+impl Tween for Rect {
+    fn tween(a: Self, b: Self, t: f32) -> Self {
+        Rect::new(
+            Coord::tween(a.pos, b.pos, t),
+            Size::tween(a.size, b.size, t),
+        )
+    }
+}
+
+/// A tweened value
+///
+/// This represents a value transitioning from one state to another over a
+/// fixed duration. Initially (and after each transition completes) the value
+/// is constant.
+#[derive(Clone, Debug)]
+pub struct Animated<T: Tween> {
+    from: T,
+    to: T,
+    start: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl<T: Tween + Default> Default for Animated<T> {
+    fn default() -> Self {
+        Animated::new(T::default())
+    }
+}
+
+impl<T: Tween> Animated<T> {
+    /// Construct with a constant `value`
+    pub fn new(value: T) -> Self {
+        Animated {
+            from: value,
+            to: value,
+            start: Instant::now(),
+            duration: Duration::default(),
+            easing: Easing::default(),
+        }
+    }
+
+    /// Set the easing curve (inline)
+    #[inline]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the easing curve
+    ///
+    /// This affects both the current and future transitions.
+    #[inline]
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    /// Get the target value
+    ///
+    /// This is the final value of the current transition, if any.
+    #[inline]
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    /// Get the value at time `now`
+    pub fn value_at(&self, now: Instant) -> T {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= self.duration {
+            return self.to;
+        }
+        let t = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        T::tween(self.from, self.to, self.easing.apply(t))
+    }
+
+    /// Get the current value
+    #[inline]
+    pub fn value(&self) -> T {
+        self.value_at(Instant::now())
+    }
+
+    /// Get linear progress of the current transition at time `now`
+    ///
+    /// This is a value from 0 to 1, before easing is applied. When not
+    /// animating, this is 1.
+    pub fn progress_at(&self, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= self.duration {
+            return 1.0;
+        }
+        elapsed.as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    /// True if a transition is in progress at time `now`
+    #[inline]
+    pub fn is_running_at(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) < self.duration
+    }
+
+    /// True if a transition is currently in progress
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.is_running_at(Instant::now())
+    }
+
+    /// Start a transition to `target` over `duration`
+    ///
+    /// The transition starts from the current value (which may itself be
+    /// mid-transition).
+    ///
+    /// This does not check [`Manager::config_reduce_motion`] or request an
+    /// animation frame; see also [`Animated::animate`].
+    pub fn set(&mut self, target: T, duration: Duration) {
+        let now = Instant::now();
+        self.from = self.value_at(now);
+        self.to = target;
+        self.start = now;
+        self.duration = duration;
+    }
+
+    /// Start a transition from `from` to `target` over `duration`
+    pub fn set_from(&mut self, from: T, target: T, duration: Duration) {
+        self.from = from;
+        self.to = target;
+        self.start = Instant::now();
+        self.duration = duration;
+    }
+
+    /// Set the value immediately, cancelling any transition
+    pub fn set_immediate(&mut self, value: T) {
+        self.from = value;
+        self.to = value;
+        self.duration = Duration::default();
+    }
+
+    /// Start a transition to `target` over `duration`, unless motion is reduced
+    ///
+    /// If [`Manager::config_reduce_motion`] is true, the value is set
+    /// immediately. Otherwise, this starts a transition (see
+    /// [`Animated::set`]) and requests an animation frame for widget `id`.
+    ///
+    /// In both cases, the widget should [request a redraw](Manager::redraw).
+    pub fn animate(&mut self, mgr: &mut Manager, id: WidgetId, target: T, duration: Duration) {
+        if mgr.config_reduce_motion() || duration == Duration::default() {
+            self.set_immediate(target);
+        } else {
+            self.set(target, duration);
+            mgr.request_animation_frame(id);
+        }
+    }
+}
+
+#[test]
+fn easing() {
+    for easing in &[
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+        assert_eq!(easing.apply(2.0), 1.0);
+        assert!(easing.apply(0.3) < easing.apply(0.6));
+    }
+    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    assert!(Easing::EaseIn.apply(0.5) < 0.5);
+    assert!(Easing::EaseOut.apply(0.5) > 0.5);
+
+    let a = Rect::new(Coord(0, 0), Size(10, 20));
+    let b = Rect::new(Coord(10, -10), Size(20, 20));
+    assert_eq!(
+        Rect::tween(a, b, 0.5),
+        Rect::new(Coord(5, -5), Size(15, 20))
+    );
+    assert_eq!(Offset::tween(Offset(0, 0), Offset(3, 4), 1.0), Offset(3, 4));
+}
@@ -0,0 +1,193 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Clipboard API
+//!
+//! Clipboard content is typed by [MIME type]. When setting the clipboard,
+//! multiple representations of the same data may be provided at once (e.g.
+//! plain text and HTML); a reader may query available types then fetch the
+//! most appropriate representation.
+//!
+//! Widgets usually access the clipboard through [`kas::event::Manager`]; shells
+//! provide an implementation of [`Clipboard`] via
+//! [`kas::ShellWindow::clipboard`]. [`MemoryClipboard`] is a simple
+//! implementation suitable for testing and headless usage.
+//!
+//! [MIME type]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types
+
+/// MIME type for UTF-8 plain text
+pub const TEXT_PLAIN: &str = "text/plain;charset=utf-8";
+/// MIME type for HTML
+pub const TEXT_HTML: &str = "text/html";
+/// MIME type for PNG images
+pub const IMAGE_PNG: &str = "image/png";
+
+/// Clipboard content
+///
+/// This is a list of representations of some content, each identified by
+/// MIME type. Representations should be ordered by preference (usually most
+/// descriptive first).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClipboardContent {
+    items: Vec<(String, Vec<u8>)>,
+}
+
+impl ClipboardContent {
+    /// Construct empty content
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct from plain text
+    #[inline]
+    pub fn from_text<S: Into<String>>(text: S) -> Self {
+        Self::new().with(TEXT_PLAIN, text.into())
+    }
+
+    /// Add a representation (inline)
+    ///
+    /// See [`ClipboardContent::insert`].
+    #[inline]
+    pub fn with<D: Into<Vec<u8>>>(mut self, mime: &str, data: D) -> Self {
+        self.insert(mime, data);
+        self
+    }
+
+    /// Add plain text (inline)
+    #[inline]
+    pub fn with_text<S: Into<String>>(self, text: S) -> Self {
+        self.with(TEXT_PLAIN, text.into())
+    }
+
+    /// Add HTML (inline)
+    #[inline]
+    pub fn with_html<S: Into<String>>(self, html: S) -> Self {
+        self.with(TEXT_HTML, html.into())
+    }
+
+    /// Add a representation
+    ///
+    /// If a representation of type `mime` already exists, it is replaced.
+    pub fn insert<D: Into<Vec<u8>>>(&mut self, mime: &str, data: D) {
+        let data = data.into();
+        match self.items.iter_mut().find(|item| item.0 == mime) {
+            Some(item) => item.1 = data,
+            None => self.items.push((mime.to_string(), data)),
+        }
+    }
+
+    /// True if there are no representations
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over available MIME types
+    pub fn mime_types(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|item| item.0.as_str())
+    }
+
+    /// Get the representation of type `mime`, if available
+    pub fn get(&self, mime: &str) -> Option<&[u8]> {
+        let mut iter = self.items.iter();
+        iter.find(|item| item.0 == mime)
+            .map(|item| item.1.as_slice())
+    }
+
+    /// Get the plain text representation, if available
+    ///
+    /// This accepts `text/plain` as well as [`TEXT_PLAIN`], but only if the
+    /// content is valid UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        let data = self.get(TEXT_PLAIN).or_else(|| self.get("text/plain"))?;
+        std::str::from_utf8(data).ok()
+    }
+}
+
+/// A clipboard provider
+///
+/// Implementations may fail to access the clipboard, in which case
+/// [`Clipboard::types`] returns an empty list, [`Clipboard::get`] returns
+/// `None` and [`Clipboard::set`] does nothing. The implementation may wish to
+/// log an appropriate warning message.
+pub trait Clipboard {
+    /// Get the list of available MIME types
+    fn types(&mut self) -> Vec<String>;
+
+    /// Get the representation of type `mime`, if available
+    fn get(&mut self, mime: &str) -> Option<Vec<u8>>;
+
+    /// Set the clipboard content, replacing all representations
+    fn set(&mut self, content: ClipboardContent);
+
+    /// Get plain text, if available
+    ///
+    /// The default implementation tries [`TEXT_PLAIN`] then `text/plain`.
+    fn get_text(&mut self) -> Option<String> {
+        let data = self.get(TEXT_PLAIN).or_else(|| self.get("text/plain"))?;
+        String::from_utf8(data).ok()
+    }
+
+    /// Set the clipboard content to plain text
+    fn set_text(&mut self, text: String) {
+        self.set(ClipboardContent::from_text(text));
+    }
+}
+
+/// An in-memory clipboard
+///
+/// This clipboard is not connected to the system clipboard; its content is
+/// available only to users of this instance.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    content: ClipboardContent,
+}
+
+impl MemoryClipboard {
+    /// Construct an empty clipboard
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Access the content
+    #[inline]
+    pub fn content(&self) -> &ClipboardContent {
+        &self.content
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn types(&mut self) -> Vec<String> {
+        self.content.mime_types().map(|s| s.to_string()).collect()
+    }
+
+    fn get(&mut self, mime: &str) -> Option<Vec<u8>> {
+        self.content.get(mime).map(|data| data.to_vec())
+    }
+
+    fn set(&mut self, content: ClipboardContent) {
+        self.content = content;
+    }
+}
+
+#[test]
+fn memory_clipboard() {
+    let mut cb = MemoryClipboard::new();
+    assert!(cb.types().is_empty());
+    assert_eq!(cb.get_text(), None);
+
+    let html = "<b>bold</b>";
+    cb.set(ClipboardContent::from_text("bold").with_html(html));
+    assert_eq!(cb.types(), vec![TEXT_PLAIN, TEXT_HTML]);
+    assert_eq!(cb.get(TEXT_HTML).as_deref(), Some(html.as_bytes()));
+    assert_eq!(cb.get(IMAGE_PNG), None);
+    assert_eq!(cb.get_text().as_deref(), Some("bold"));
+
+    cb.set_text("plain".to_string());
+    assert_eq!(cb.types(), vec![TEXT_PLAIN]);
+    assert_eq!(cb.content().text(), Some("plain"));
+}
@@ -125,7 +125,8 @@ pub trait LayoutData {
 /// A pop-up is a special widget drawn either as a layer over the existing
 /// window or in a new borderless window. It should be precisely positioned
 /// *next to* it's `parent`'s `rect`, in the specified `direction` (or, if not
-/// possible, in the opposite direction).
+/// possible, in the opposite direction). This is a physical direction; logical
+/// directions should be resolved via [`kas::dir::LayoutDir::resolve`].
 ///
 /// A pop-up is in some ways an ordinary child widget and in some ways not.
 /// The pop-up widget should be a permanent child of its parent, but is not
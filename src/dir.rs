@@ -100,3 +100,73 @@ impl Directional for Direction {
         self
     }
 }
+
+/// Horizontal layout direction
+///
+/// This is the direction in which rows are laid out: left-to-right for most
+/// scripts, but right-to-left for scripts such as Arabic and Hebrew.
+///
+/// Layout code treats the horizontal directions as *logical*: [`Right`]
+/// means "towards the end" and [`Left`] "towards the start". Likewise, for
+/// horizontal alignment [`Align::TL`] means the start and [`Align::BR`]
+/// the end. With [`LayoutDir::Rtl`], these resolve to the opposite physical
+/// directions.
+///
+/// [`Align::TL`]: crate::layout::Align::TL
+/// [`Align::BR`]: crate::layout::Align::BR
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutDir {
+    /// Left-to-right
+    Ltr,
+    /// Right-to-left
+    Rtl,
+}
+
+impl Default for LayoutDir {
+    fn default() -> Self {
+        LayoutDir::Ltr
+    }
+}
+
+impl LayoutDir {
+    /// Get the layout direction for a language tag (e.g. `en` or `ar-EG`)
+    ///
+    /// Only the primary language subtag is used.
+    pub fn from_language(language: &str) -> Self {
+        let lang = language.split(|c| c == '-' || c == '_').next().unwrap();
+        match lang {
+            "ar" | "dv" | "fa" | "he" | "ks" | "ku" | "ps" | "sd" | "ug" | "ur" | "yi" => {
+                LayoutDir::Rtl
+            }
+            _ => LayoutDir::Ltr,
+        }
+    }
+
+    /// True if right-to-left
+    #[inline]
+    pub fn is_rtl(self) -> bool {
+        self == LayoutDir::Rtl
+    }
+
+    /// Resolve a logical direction to a physical direction
+    ///
+    /// In right-to-left mode, `Right` and `Left` are swapped.
+    #[inline]
+    pub fn resolve<D: Directional>(self, dir: D) -> Direction {
+        let dir = dir.as_direction();
+        match (self, dir) {
+            (LayoutDir::Rtl, Direction::Right) => Direction::Left,
+            (LayoutDir::Rtl, Direction::Left) => Direction::Right,
+            (_, dir) => dir,
+        }
+    }
+
+    /// True if `dir` is reversed after resolving
+    ///
+    /// This is equivalent to `self.resolve(dir).is_reversed()`.
+    #[inline]
+    pub fn is_reversed<D: Directional>(self, dir: D) -> bool {
+        self.resolve(dir).is_reversed()
+    }
+}
@@ -9,10 +9,10 @@ use std::convert::AsRef;
 use std::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
 
 use kas::dir::Direction;
-use kas::draw::{Draw, Pass};
+use kas::draw::{Colour, Draw, Pass};
 use kas::geom::{Coord, Offset, Rect, Size, Vec2};
 use kas::layout::{AxisInfo, FrameRules, Margins, SizeRules};
-use kas::text::{format::FormattableText, AccelString, Text, TextApi, TextDisplay};
+use kas::text::{format::FormattableText, AccelString, Effect, Text, TextApi, TextDisplay};
 
 // for doc use
 #[allow(unused)]
@@ -20,8 +20,14 @@ use kas::text::TextApiExt;
 
 /// Classification of a clip region
 pub enum ClipRegion {
+    /// A pop-up, drawn above all other content
     Popup,
+    /// A scroll region, drawn below its parent
     Scroll,
+    /// An overlay, drawn above its parent but below pop-ups
+    ///
+    /// Each nested overlay region is drawn above its parent.
+    Overlay,
 }
 
 /// Input and highlighting state of a widget
@@ -312,6 +318,19 @@ pub trait DrawHandle {
         class: TextClass,
     );
 
+    /// Draw placeholder text
+    ///
+    /// This is like [`DrawHandle::text_offset`], but uses a dimmed colour.
+    /// It is used to draw a hint in an empty edit field.
+    fn text_placeholder(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        class: TextClass,
+    );
+
     /// Draw text with effects
     ///
     /// [`DrawHandle::text_offset`] already supports *font* effects: bold,
@@ -319,6 +338,22 @@ pub trait DrawHandle {
     /// strikethrough effects.
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass);
 
+    /// Draw text with colours and effects
+    ///
+    /// The `effects` list provides colour and underline/strikethrough
+    /// information for ranges of `text`, ordered by `start`. Text before the
+    /// first entry uses the default colour for `class`. This is used to draw
+    /// syntax highlighting.
+    fn text_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    );
+
     /// Draw an `AccelString` text
     ///
     /// The `text` is drawn within the rect from `pos` to `text.env().bounds`.
@@ -338,6 +373,59 @@ pub trait DrawHandle {
         class: TextClass,
     );
 
+    /// Draw text with colours and effects, with a subset selected
+    ///
+    /// This is like [`DrawHandle::text_col_effects`], except that the selected
+    /// `range` is drawn as by [`DrawHandleExt::text_selected`], taking
+    /// precedence over the colours of `effects`.
+    ///
+    /// The default implementation ignores `effects`.
+    #[allow(clippy::too_many_arguments)]
+    fn text_selected_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        range: Range<usize>,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        let _ = effects;
+        self.text_selected_range(pos, bounds, offset, text, range, class);
+    }
+
+    /// Draw highlighting behind the given `ranges` of `text`
+    ///
+    /// This is used to mark search matches. Only the background is drawn; the
+    /// text itself should be drawn afterwards, e.g. via
+    /// [`DrawHandle::text_offset`].
+    fn text_highlights(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    );
+
+    /// Draw a wavy underline below the given `ranges` of `text`
+    ///
+    /// This is used to mark errors such as misspelled words.
+    fn text_squiggles(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    );
+
+    /// Draw a highlight behind the current line of an editor
+    ///
+    /// The `rect` covers the whole width of the line.
+    fn edit_line_highlight(&mut self, rect: Rect);
+
     /// Draw an edit marker at the given `byte` index on this `text`
     fn edit_marker(
         &mut self,
@@ -621,9 +709,32 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
         self.deref_mut()
             .text_offset(pos, bounds, offset, text, class)
     }
+    fn text_placeholder(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_placeholder(pos, bounds, offset, text, class)
+    }
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.deref_mut().text_effects(pos, offset, text, class);
     }
+    fn text_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_col_effects(pos, bounds, offset, text, effects, class);
+    }
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.deref_mut().text_accel(pos, text, state, class);
     }
@@ -639,6 +750,44 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
         self.deref_mut()
             .text_selected_range(pos, bounds, offset, text, range, class);
     }
+    fn text_selected_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        range: Range<usize>,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_selected_col_effects(pos, bounds, offset, text, range, effects, class);
+    }
+    fn text_highlights(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.deref_mut()
+            .text_highlights(pos, bounds, offset, text, ranges);
+    }
+    fn text_squiggles(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.deref_mut()
+            .text_squiggles(pos, bounds, offset, text, ranges);
+    }
+    fn edit_line_highlight(&mut self, rect: Rect) {
+        self.deref_mut().edit_line_highlight(rect)
+    }
     fn edit_marker(
         &mut self,
         pos: Coord,
@@ -723,9 +872,32 @@ where
         self.deref_mut()
             .text_offset(pos, bounds, offset, text, class)
     }
+    fn text_placeholder(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_placeholder(pos, bounds, offset, text, class)
+    }
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.deref_mut().text_effects(pos, offset, text, class);
     }
+    fn text_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_col_effects(pos, bounds, offset, text, effects, class);
+    }
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.deref_mut().text_accel(pos, text, state, class);
     }
@@ -741,6 +913,44 @@ where
         self.deref_mut()
             .text_selected_range(pos, bounds, offset, text, range, class);
     }
+    fn text_selected_col_effects(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        range: Range<usize>,
+        effects: &[Effect<Colour>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_selected_col_effects(pos, bounds, offset, text, range, effects, class);
+    }
+    fn text_highlights(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.deref_mut()
+            .text_highlights(pos, bounds, offset, text, ranges);
+    }
+    fn text_squiggles(
+        &mut self,
+        pos: Coord,
+        bounds: Vec2,
+        offset: Offset,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+    ) {
+        self.deref_mut()
+            .text_squiggles(pos, bounds, offset, text, ranges);
+    }
+    fn edit_line_highlight(&mut self, rect: Rect) {
+        self.deref_mut().edit_line_highlight(rect)
+    }
     fn edit_marker(
         &mut self,
         pos: Coord,
@@ -5,7 +5,7 @@
 
 //! Event handling components
 
-use super::{Event, GrabMode, Manager, PressSource};
+use super::{Command, Event, GrabMode, Manager, PressSource};
 use crate::geom::{Coord, Offset};
 #[allow(unused)]
 use crate::text::SelectionHelper;
@@ -135,3 +135,84 @@ impl TextInput {
         }
     }
 }
+
+/// Tracks when to set the primary selection from a text selection
+///
+/// The primary selection should only be set once a selection is complete:
+/// when a press ends, on select-all, or when a keyboard selection is followed
+/// by other input. Usage, when handling an `event`:
+///
+/// 1.  If [`PrimaryTracker::pre_event`] returns true, set the primary selection
+///     (this is done first since the event may clear the selection)
+/// 2.  Handle the event
+/// 3.  If [`PrimaryTracker::post_event`] returns true, set the primary selection
+#[derive(Clone, Debug, Default)]
+pub struct PrimaryTracker {
+    // Selection changed but not yet set as the primary selection
+    pending: bool,
+    // The current event completes a selection
+    finish: bool,
+}
+
+impl PrimaryTracker {
+    /// Call before handling `event`
+    ///
+    /// Returns true if the primary selection should be set now.
+    pub fn pre_event(&mut self, event: &Event) -> bool {
+        self.finish = false;
+        match event {
+            Event::Command(Command::SelectAll, _) | Event::PressEnd { .. } => {
+                self.finish = true;
+                false
+            }
+            Event::Command(_, false)
+            | Event::ReceivedCharacter(_)
+            | Event::LostCharFocus
+            | Event::LostSelFocus
+            | Event::PressStart { .. } => std::mem::take(&mut self.pending),
+            _ => false,
+        }
+    }
+
+    /// Call after handling an event, noting whether the selection `changed`
+    ///
+    /// Returns true if the primary selection should be set now.
+    pub fn post_event(&mut self, changed: bool) -> bool {
+        self.pending |= changed;
+        std::mem::take(&mut self.finish) && std::mem::take(&mut self.pending)
+    }
+}
+
+#[test]
+fn primary_tracker() {
+    let mut t = PrimaryTracker::default();
+    let shift_right = Event::Command(Command::Right, true);
+    let end = Event::PressEnd {
+        source: PressSource::Touch(0),
+        end_id: None,
+        coord: Coord::ZERO,
+    };
+
+    // Keyboard selection: set on the next non-selecting input
+    assert!(!t.pre_event(&shift_right));
+    assert!(!t.post_event(true));
+    assert!(!t.pre_event(&shift_right));
+    assert!(!t.post_event(true));
+    assert!(t.pre_event(&Event::Command(Command::Home, false)));
+    assert!(!t.post_event(false));
+    assert!(!t.pre_event(&Event::ReceivedCharacter('a')));
+    assert!(!t.post_event(false));
+
+    // Press selection: set once the press ends
+    assert!(!t.pre_event(&end));
+    assert!(!t.post_event(false));
+    assert!(!t.pre_event(&Event::LostSelFocus));
+    assert!(!t.post_event(true));
+    assert!(t.pre_event(&Event::LostCharFocus));
+    assert!(!t.post_event(false));
+
+    assert!(!t.pre_event(&Event::Command(Command::SelectAll, false)));
+    assert!(t.post_event(true));
+    assert!(!t.pre_event(&end));
+    assert!(!t.post_event(false));
+}
@@ -21,6 +21,12 @@ pub enum ConfigError {
     #[cfg(feature = "json")]
     #[error("config (de)serialisation to JSON failed")]
     Json(#[from] serde_json::Error),
+    #[cfg(feature = "toml")]
+    #[error("config deserialisation from TOML failed")]
+    TomlDe(#[from] dep_toml::de::Error),
+    #[cfg(feature = "toml")]
+    #[error("config serialisation to TOML failed")]
+    TomlSer(#[from] dep_toml::ser::Error),
     #[error("error reading / writing config file")]
     IoError(#[from] std::io::Error),
     #[error("format not supported: {0}")]
@@ -48,6 +54,33 @@ pub enum ConfigFormat {
     Unknown,
 }
 
+impl ConfigFormat {
+    /// True if reading and writing this format is supported
+    ///
+    /// Support for each format depends on the corresponding feature flag.
+    pub fn is_supported(self) -> bool {
+        match self {
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => true,
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => true,
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => true,
+            _ => false,
+        }
+    }
+
+    /// The usual file extension for this format, if any
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            ConfigFormat::Json => Some("json"),
+            ConfigFormat::Toml => Some("toml"),
+            ConfigFormat::Yaml => Some("yaml"),
+            _ => None,
+        }
+    }
+}
+
 impl Default for ConfigFormat {
     fn default() -> Self {
         ConfigFormat::None
@@ -85,6 +118,15 @@ pub struct Config {
     #[cfg_attr(feature = "serde", serde(default = "defaults::mouse_text_pan"))]
     pub mouse_text_pan: MousePan,
 
+    /// Reduce motion: disable animated transitions
+    ///
+    /// When true, animations (see [`crate::anim`]) jump directly to their
+    /// target value.
+    #[cfg_attr(feature = "serde", serde(default = "defaults::reduce_motion"))]
+    pub reduce_motion: bool,
+
+    // NOTE: TOML requires that tables (maps) follow all simple values, thus
+    // this field must remain last.
     #[cfg_attr(feature = "serde", serde(default = "Shortcuts::platform_defaults"))]
     pub shortcuts: Shortcuts,
 }
@@ -97,6 +139,7 @@ impl Default for Config {
             pan_dist_thresh: defaults::pan_dist_thresh(),
             mouse_pan: defaults::mouse_pan(),
             mouse_text_pan: defaults::mouse_text_pan(),
+            reduce_motion: defaults::reduce_motion(),
             shortcuts: Shortcuts::platform_defaults(),
         }
     }
@@ -131,23 +174,43 @@ impl Config {
     }
 
     /// Read from a path
-    pub fn from_path(path: &Path, mut format: ConfigFormat) -> Result<Self, ConfigError> {
-        if format == ConfigFormat::None {
-            format = Self::guess_format(path);
-        }
+    pub fn from_path(path: &Path, format: ConfigFormat) -> Result<Self, ConfigError> {
+        read_path(path, format)
+    }
 
-        match format {
-            #[cfg(feature = "json")]
-            ConfigFormat::Json => {
-                let r = std::io::BufReader::new(std::fs::File::open(path)?);
-                Ok(serde_json::from_reader(r)?)
-            }
-            #[cfg(feature = "yaml")]
-            ConfigFormat::Yaml => {
-                let r = std::io::BufReader::new(std::fs::File::open(path)?);
-                Ok(serde_yaml::from_reader(r)?)
-            }
-            _ => Err(ConfigError::UnsupportedFormat(format)),
+    /// Read from a path, applying over existing configuration
+    ///
+    /// Unlike [`Config::from_path`], this only replaces those items present in
+    /// the file. Shortcuts are replaced individually. This allows layering of
+    /// multiple config sources, e.g. system-wide then user then application
+    /// configuration, where later layers override individual keys.
+    pub fn apply_path(&mut self, path: &Path, format: ConfigFormat) -> Result<(), ConfigError> {
+        let layer: ConfigLayer = read_path(path, format)?;
+        self.apply_layer(layer);
+        Ok(())
+    }
+
+    fn apply_layer(&mut self, layer: ConfigLayer) {
+        if let Some(v) = layer.menu_delay_ns {
+            self.menu_delay_ns = v;
+        }
+        if let Some(v) = layer.touch_text_sel_delay_ns {
+            self.touch_text_sel_delay_ns = v;
+        }
+        if let Some(v) = layer.pan_dist_thresh {
+            self.pan_dist_thresh = v;
+        }
+        if let Some(v) = layer.mouse_pan {
+            self.mouse_pan = v;
+        }
+        if let Some(v) = layer.mouse_text_pan {
+            self.mouse_text_pan = v;
+        }
+        if let Some(v) = layer.reduce_motion {
+            self.reduce_motion = v;
+        }
+        if let Some(shortcuts) = layer.shortcuts {
+            self.shortcuts.extend(shortcuts);
         }
     }
 
@@ -170,12 +233,70 @@ impl Config {
                 serde_yaml::to_writer(w, self)?;
                 Ok(())
             }
-            // NOTE: Toml is not supported since the `toml` crate does not support enums as map keys
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => {
+                let s = dep_toml::to_string_pretty(self)?;
+                std::fs::write(path, s)?;
+                Ok(())
+            }
             _ => Err(ConfigError::UnsupportedFormat(format)),
         }
     }
 }
 
+/// A partial [`Config`]: all items are optional
+///
+/// Used by [`Config::apply_path`].
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(not(feature = "serde"), allow(unused))]
+struct ConfigLayer {
+    menu_delay_ns: Option<u32>,
+    touch_text_sel_delay_ns: Option<u32>,
+    pan_dist_thresh: Option<i32>,
+    mouse_pan: Option<MousePan>,
+    mouse_text_pan: Option<MousePan>,
+    reduce_motion: Option<bool>,
+    shortcuts: Option<Shortcuts>,
+}
+
+/// Types readable by [`read_path`]
+///
+/// Without the `serde` feature no format is supported, thus no bound is needed.
+#[cfg(feature = "serde")]
+trait Readable: serde::de::DeserializeOwned {}
+#[cfg(feature = "serde")]
+impl<T: serde::de::DeserializeOwned> Readable for T {}
+#[cfg(not(feature = "serde"))]
+trait Readable {}
+#[cfg(not(feature = "serde"))]
+impl<T> Readable for T {}
+
+fn read_path<T: Readable>(path: &Path, mut format: ConfigFormat) -> Result<T, ConfigError> {
+    if format == ConfigFormat::None {
+        format = Config::guess_format(path);
+    }
+
+    match format {
+        #[cfg(feature = "json")]
+        ConfigFormat::Json => {
+            let r = std::io::BufReader::new(std::fs::File::open(path)?);
+            Ok(serde_json::from_reader(r)?)
+        }
+        #[cfg(feature = "yaml")]
+        ConfigFormat::Yaml => {
+            let r = std::io::BufReader::new(std::fs::File::open(path)?);
+            Ok(serde_yaml::from_reader(r)?)
+        }
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => {
+            let s = std::fs::read_to_string(path)?;
+            Ok(dep_toml::from_str(&s)?)
+        }
+        _ => Err(ConfigError::UnsupportedFormat(format)),
+    }
+}
+
 /// When mouse-panning is enabled (click+drag to scroll)
 ///
 /// For *text* objects, this may conflict with text selection, hence it is
@@ -184,6 +305,9 @@ impl Config {
 /// For non-text cases, this does not conflict with other event handlers since
 /// panning is only possible when events are otherwise unused, thus `Always` is
 /// acceptable (equivalent to touch scrolling).
+///
+/// When (de)serialised, values are represented by a plain string (the variant
+/// name), which is compatible with all supported formats including TOML.
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MousePan {
@@ -234,4 +358,49 @@ mod defaults {
             MousePan::WithCtrl
         }
     }
+    pub fn reduce_motion() -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn apply_layer() {
+    use super::{Command, VirtualKeyCode as VK};
+    let mut config = Config::default();
+    config.mouse_pan = MousePan::Never;
+    let layer: ConfigLayer = dep_toml::from_str(
+        r#"
+        menu_delay_ns = 1000
+
+        [shortcuts.ctrl]
+        Q = "Exit"
+        "#,
+    )
+    .unwrap();
+    config.apply_layer(layer);
+    assert_eq!(config.menu_delay_ns, 1000);
+    assert_eq!(config.pan_dist_thresh, defaults::pan_dist_thresh());
+    assert!(!config.mouse_pan.is_enabled_with(ModifiersState::CTRL));
+    let ctrl = ModifiersState::CTRL;
+    assert_eq!(config.shortcuts.get(ctrl, VK::Q), Some(Command::Exit));
+
+    // A later layer may override or unbind individual keys
+    let layer: ConfigLayer = dep_toml::from_str(
+        r#"
+        [shortcuts.ctrl]
+        W = "Exit"
+
+        [shortcuts.none]
+        Escape = "none"
+        "#,
+    )
+    .unwrap();
+    config.apply_layer(layer);
+    assert_eq!(config.menu_delay_ns, 1000);
+    assert_eq!(config.shortcuts.get(ctrl, VK::Q), Some(Command::Exit));
+    assert_eq!(config.shortcuts.get(ctrl, VK::W), Some(Command::Exit));
+    let none = ModifiersState::empty();
+    assert_eq!(config.shortcuts.get(none, VK::Escape), None);
+    assert_eq!(config.shortcuts.get(none, VK::Tab), Some(Command::Tab));
 }
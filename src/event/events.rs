@@ -146,6 +146,12 @@ pub enum Event {
     /// A user-defined payload is passed. Interpretation of this payload is
     /// user-defined and unfortunately not type safe.
     HandleUpdate { handle: UpdateHandle, payload: u64 },
+    /// Animation frame
+    ///
+    /// This event is received once before the next frame is drawn after
+    /// calling [`Manager::request_animation_frame`]. Widgets should update
+    /// any animation state here and request another frame if required.
+    AnimationFrame,
     /// Notification that a new popup has been created
     ///
     /// This is sent to the parent of each open popup when a new popup is
@@ -19,6 +19,7 @@ use std::u16;
 
 use super::*;
 use crate::conv::Cast;
+use crate::dir::LayoutDir;
 use crate::geom::Coord;
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
@@ -122,12 +123,15 @@ pub struct ManagerState {
     popups: SmallVec<[(WindowId, kas::Popup); 16]>,
     new_popups: SmallVec<[WidgetId; 16]>,
     popup_removed: SmallVec<[(WidgetId, WindowId); 16]>,
+    layout_dir: Option<LayoutDir>,
 
     time_start: Instant,
     time_updates: Vec<(Instant, WidgetId, u64)>,
     // TODO(opt): consider other containers, e.g. C++ multimap
     // or sorted Vec with binary search yielding a range
     handle_updates: HashMap<UpdateHandle, LinearSet<WidgetId>>,
+    // RefCell since animation frames may be requested while drawing
+    anim_frames: RefCell<SmallVec<[WidgetId; 4]>>,
     pending: SmallVec<[Pending; 8]>,
     action: TkAction,
 }
@@ -6,13 +6,17 @@
 //! Event manager — public API
 
 use log::{debug, trace, warn};
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 use std::u16;
 
 use super::*;
+use crate::clipboard::ClipboardContent;
 use crate::data::SharedData;
+use crate::dir::LayoutDir;
 use crate::draw::{SizeHandle, ThemeAction, ThemeApi};
 use crate::geom::Coord;
+use crate::i18n::{Catalogue, Message};
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
 use crate::{TkAction, WidgetId, WindowId};
@@ -64,6 +68,42 @@ impl ManagerState {
         self.mouse_grab.is_none() && self.hover == Some(w_id)
     }
 
+    /// Get the current modifier state
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Get the widget under the mouse cursor, if any
+    #[inline]
+    pub fn hover(&self) -> Option<WidgetId> {
+        self.hover
+    }
+
+    /// Is "reduce motion" enabled?
+    ///
+    /// When true, widgets should not animate transitions.
+    #[inline]
+    pub fn config_reduce_motion(&self) -> bool {
+        self.config.borrow().reduce_motion
+    }
+
+    /// Request an animation frame
+    ///
+    /// The widget will receive [`Event::AnimationFrame`] before the next frame
+    /// is drawn, and the window will be redrawn. Each request is for a single
+    /// frame only; widgets should request frames from [`crate::Layout::draw`]
+    /// while animating.
+    ///
+    /// Unlike most methods on `ManagerState`, this may be called while
+    /// drawing (it uses interior mutability).
+    pub fn request_animation_frame(&self, w_id: WidgetId) {
+        let mut frames = self.anim_frames.borrow_mut();
+        if !frames.contains(&w_id) {
+            frames.push(w_id);
+        }
+    }
+
     /// Check whether the given widget is visually depressed
     #[inline]
     pub fn is_depressed(&self, w_id: WidgetId) -> bool {
@@ -112,9 +152,15 @@ impl<'a> Manager<'a> {
             .is_enabled_with(self.modifiers())
     }
 
+    /// Is "reduce motion" enabled?
+    #[inline]
+    pub fn config_reduce_motion(&self) -> bool {
+        self.config().reduce_motion
+    }
+
     /// Schedule an update
     ///
-    /// Widgets requiring animation should schedule an update; as a result,
+    /// Widgets may use timers to schedule updates; as a result,
     /// the widget will receive [`Event::TimerUpdate`] (with this `payload`)
     /// at approximately `time = now + delay`.
     ///
@@ -157,6 +203,15 @@ impl<'a> Manager<'a> {
         self.state.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
     }
 
+    /// Request an animation frame
+    ///
+    /// The widget will receive [`Event::AnimationFrame`] before the next frame
+    /// is drawn. See [`ManagerState::request_animation_frame`].
+    #[inline]
+    pub fn request_animation_frame(&mut self, w_id: WidgetId) {
+        self.state.request_animation_frame(w_id);
+    }
+
     /// Subscribe to an update handle
     ///
     /// All widgets subscribed to an update handle will be sent
@@ -302,19 +357,104 @@ impl<'a> Manager<'a> {
         self.shell.trigger_update(handle, payload);
     }
 
-    /// Attempt to get clipboard contents
+    /// Attempt to get clipboard contents as plain text
     ///
     /// In case of failure, paste actions will simply fail. The implementation
     /// may wish to log an appropriate warning message.
     #[inline]
     pub fn get_clipboard(&mut self) -> Option<String> {
-        self.shell.get_clipboard()
+        self.shell.clipboard().get_text()
     }
 
-    /// Attempt to set clipboard contents
+    /// Attempt to set clipboard contents to plain text
     #[inline]
     pub fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
-        self.shell.set_clipboard(content)
+        self.shell.clipboard().set_text(content.into_owned())
+    }
+
+    /// Attempt to get the primary selection
+    ///
+    /// See [`ShellWindow::get_primary`](crate::ShellWindow::get_primary).
+    #[inline]
+    pub fn get_primary(&mut self) -> Option<String> {
+        self.shell.get_primary()
+    }
+
+    /// Attempt to set the primary selection
+    ///
+    /// Widgets should call this whenever their selection changes to a
+    /// non-empty selection (except for sensitive content such as passwords).
+    #[inline]
+    pub fn set_primary<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
+        self.shell.set_primary(content.into_owned())
+    }
+
+    /// Get the active message catalogue, if any
+    #[inline]
+    pub fn catalogue(&self) -> Option<Rc<dyn Catalogue>> {
+        self.shell.catalogue()
+    }
+
+    /// Translate and format a message using the active catalogue
+    #[inline]
+    pub fn translate(&self, message: &Message) -> String {
+        message.resolve(self.shell.catalogue().as_deref())
+    }
+
+    /// Switch language
+    ///
+    /// This sets the active message catalogue (or removes it, reverting to
+    /// untranslated text) and re-configures all windows, thus updating all
+    /// localised text and layout.
+    #[inline]
+    pub fn set_language(&mut self, catalogue: Option<Rc<dyn Catalogue>>) {
+        self.shell.set_catalogue(catalogue);
+    }
+
+    /// Get the layout direction
+    ///
+    /// This is the window's direction if set via [`Manager::set_layout_dir`],
+    /// otherwise the application's direction (usually inferred from the active
+    /// catalogue's language; see
+    /// [`ShellWindow::layout_dir`](crate::ShellWindow::layout_dir)).
+    #[inline]
+    pub fn layout_dir(&self) -> LayoutDir {
+        self.state
+            .layout_dir
+            .unwrap_or_else(|| self.shell.layout_dir())
+    }
+
+    /// Set the window's layout direction
+    ///
+    /// If `None`, the window follows the application's direction.
+    /// The window is resized to apply the new direction.
+    pub fn set_layout_dir(&mut self, dir: Option<LayoutDir>) {
+        if dir != self.state.layout_dir {
+            self.state.layout_dir = dir;
+            self.send_action(TkAction::RESIZE);
+        }
+    }
+
+    /// Get the MIME types of available clipboard contents
+    #[inline]
+    pub fn clipboard_types(&mut self) -> Vec<String> {
+        self.shell.clipboard().types()
+    }
+
+    /// Attempt to get clipboard contents of type `mime`
+    ///
+    /// See [`kas::clipboard`] for common MIME types.
+    #[inline]
+    pub fn get_clipboard_data(&mut self, mime: &str) -> Option<Vec<u8>> {
+        self.shell.clipboard().get(mime)
+    }
+
+    /// Attempt to set clipboard contents
+    ///
+    /// All representations are set at once, replacing any previous contents.
+    #[inline]
+    pub fn set_clipboard_content(&mut self, content: ClipboardContent) {
+        self.shell.clipboard().set(content)
     }
 
     /// Adjust the theme
@@ -53,10 +53,12 @@ impl ManagerState {
             popups: Default::default(),
             new_popups: Default::default(),
             popup_removed: Default::default(),
+            layout_dir: None,
 
             time_start: Instant::now(),
             time_updates: vec![],
             handle_updates: HashMap::new(),
+            anim_frames: Default::default(),
             pending: SmallVec::new(),
             action: TkAction::empty(),
         }
@@ -73,7 +75,7 @@ impl ManagerState {
     /// renamed and removed widgets.
     pub fn configure<W>(&mut self, shell: &mut dyn ShellWindow, widget: &mut W)
     where
-        W: Widget<Msg = VoidMsg> + ?Sized,
+        W: Widget + ?Sized,
     {
         debug!("Manager::configure");
         self.action = TkAction::empty();
@@ -123,6 +125,11 @@ impl ManagerState {
 
         self.sel_focus = self.sel_focus.and_then(|id| renames.get(&id).cloned());
         self.nav_focus = self.nav_focus.and_then(|id| renames.get(&id).cloned());
+        let anim_frames = self.anim_frames.get_mut();
+        *anim_frames = anim_frames
+            .drain(..)
+            .filter_map(|id| renames.get(&id).cloned())
+            .collect();
         self.mouse_grab = self.mouse_grab.as_ref().and_then(|grab| {
             renames.get(&grab.start_id).map(|id| MouseGrab {
                 button: grab.button,
@@ -259,6 +266,16 @@ impl ManagerState {
         self.time_updates.last().map(|time| time.0)
     }
 
+    /// True if an animation frame has been requested
+    ///
+    /// If true, the shell should call [`Manager::update_animation_frame`]
+    /// then redraw the window as soon as possible (usually synchronised with
+    /// the display's refresh rate).
+    #[inline]
+    pub fn wants_animation_frame(&self) -> bool {
+        !self.anim_frames.borrow().is_empty()
+    }
+
     /// Set an action
     ///
     /// Since this is a commonly used operation, an operator overload is
@@ -397,6 +414,17 @@ impl<'a> Manager<'a> {
         self.state.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
     }
 
+    /// Update widgets for the next animation frame
+    ///
+    /// This sends [`Event::AnimationFrame`] to each widget which requested a
+    /// frame. It should be called immediately before drawing.
+    pub fn update_animation_frame<W: Widget + ?Sized>(&mut self, widget: &mut W) {
+        let ids = self.state.anim_frames.replace(Default::default());
+        for id in ids {
+            self.send_event(widget, id, Event::AnimationFrame);
+        }
+    }
+
     /// Update widgets due to handle
     pub fn update_handle<W: Widget + ?Sized>(
         &mut self,
@@ -8,7 +8,9 @@
 use super::{Command, ModifiersState, VirtualKeyCode};
 use linear_map::LinearMap;
 #[cfg(feature = "serde")]
-use serde::de::{self, Deserialize, Deserializer, MapAccess, Unexpected, Visitor};
+use serde::de::{
+    self, Deserialize, Deserializer, IntoDeserializer, MapAccess, Unexpected, Visitor,
+};
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeMap, Serializer};
 use std::collections::HashMap;
@@ -16,9 +18,18 @@ use std::collections::HashMap;
 use std::fmt;
 
 /// Shortcut manager
+///
+/// Each binding maps a key (with modifiers) to a [`Command`], or to `None` to
+/// unbind the key (including from any lower config layer; see
+/// [`Shortcuts::extend`]).
+///
+/// When (de)serialised, unbound keys have the string value `none` (e.g.
+/// `Escape = "none"`). A string is used instead of a null value since not all
+/// formats (notably TOML) support null. This cannot be confused with a
+/// [`Command`], whose names are all capitalised.
 #[derive(Debug)]
 pub struct Shortcuts {
-    map: LinearMap<ModifiersState, HashMap<VirtualKeyCode, Command>>,
+    map: LinearMap<ModifiersState, HashMap<VirtualKeyCode, Option<Command>>>,
 }
 
 impl Shortcuts {
@@ -189,7 +200,19 @@ impl Shortcuts {
         {
             let modifiers = ModifiersState::ALT | CMD;
             let map = self.map.entry(modifiers).or_insert_with(Default::default);
-            map.insert(VK::W, Command::Exit);
+            map.insert(VK::W, Some(Command::Exit));
+        }
+    }
+
+    /// Merge bindings from `other`
+    ///
+    /// Each binding in `other` replaces any existing binding for the same
+    /// modifiers and key; other existing bindings are retained. Keys unbound
+    /// in `other` are thus unbound in the result.
+    pub fn extend(&mut self, other: Shortcuts) {
+        for (modifiers, bindings) in other.map {
+            let map = self.map.entry(modifiers).or_insert_with(Default::default);
+            map.extend(bindings);
         }
     }
 
@@ -200,7 +223,7 @@ impl Shortcuts {
     /// status of the Shift modifier directly where this has an affect.
     pub fn get(&self, mut modifiers: ModifiersState, vkey: VirtualKeyCode) -> Option<Command> {
         if let Some(result) = self.map.get(&modifiers).and_then(|m| m.get(&vkey)) {
-            return Some(*result);
+            return *result;
         }
         modifiers.remove(ModifiersState::SHIFT);
         if modifiers.is_empty() {
@@ -261,7 +284,37 @@ impl Serialize for Shortcuts {
     {
         let mut map = s.serialize_map(Some(self.map.len()))?;
         for (k, v) in &self.map {
-            map.serialize_entry(state_to_string(*k), v)?;
+            map.serialize_entry(state_to_string(*k), &KeyMap(v))?;
+        }
+        map.end()
+    }
+}
+
+/// Serialisation helper for a map of key bindings
+///
+/// Keys are written as strings (the name of the [`VirtualKeyCode`] variant),
+/// since some formats (notably TOML) do not
+/// support enums as map keys. Entries are sorted by key for stable output.
+#[cfg(feature = "serde")]
+struct KeyMap<'a>(&'a HashMap<VirtualKeyCode, Option<Command>>);
+
+#[cfg(feature = "serde")]
+impl<'a> Serialize for KeyMap<'a> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by_key(|(k, _)| **k);
+        let mut map = s.serialize_map(Some(entries.len()))?;
+        for (k, v) in entries {
+            // All variants are unit variants, thus the Debug representation
+            // is the variant name, as expected by the Deserialize impl.
+            let name = format!("{:?}", k);
+            match v {
+                Some(cmd) => map.serialize_entry(&name, cmd)?,
+                None => map.serialize_entry(&name, "none")?,
+            }
         }
         map.end()
     }
@@ -348,15 +401,57 @@ impl<'de> Visitor<'de> for ShortcutsVisitor {
     where
         A: MapAccess<'de>,
     {
-        let mut map = LinearMap::<ModifiersState, HashMap<VirtualKeyCode, Command>>::new();
+        let mut map = LinearMap::<ModifiersState, HashMap<VirtualKeyCode, Option<Command>>>::new();
         while let Some(key) = reader.next_key::<ModifierStateVisitor>()? {
-            let value = reader.next_value()?;
-            map.insert(key.0, value);
+            let value: KeyMapVisitor = reader.next_value()?;
+            map.insert(key.0, value.0);
         }
         Ok(Shortcuts { map })
     }
 }
 
+#[cfg(feature = "serde")]
+struct KeyMapVisitor(HashMap<VirtualKeyCode, Option<Command>>);
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for KeyMapVisitor {
+    type Value = KeyMapVisitor;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("{ <key> : <command> }")
+    }
+
+    fn visit_map<A>(mut self, mut reader: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = reader.next_key::<String>()? {
+            // Keys are strings; parse as VirtualKeyCode via its own impl:
+            let key_de: de::value::StrDeserializer<'_, A::Error> = key.as_str().into_deserializer();
+            let key = VirtualKeyCode::deserialize(key_de)?;
+            let value: String = reader.next_value()?;
+            let value = match value.as_str() {
+                "none" => None,
+                s => {
+                    let de: de::value::StrDeserializer<'_, A::Error> = s.into_deserializer();
+                    Some(Command::deserialize(de)?)
+                }
+            };
+            self.0.insert(key, value);
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for KeyMapVisitor {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_map(KeyMapVisitor(Default::default()))
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Shortcuts {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
@@ -366,3 +461,27 @@ impl<'de> Deserialize<'de> for Shortcuts {
         d.deserialize_map(ShortcutsVisitor)
     }
 }
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_round_trip() {
+    use VirtualKeyCode as VK;
+    let mut shortcuts = Shortcuts::new();
+    let ctrl = ModifiersState::CTRL;
+    let map = shortcuts.map.entry(ctrl).or_insert_with(Default::default);
+    map.insert(VK::Q, Some(Command::Exit));
+    map.insert(VK::Key1, Some(Command::Home));
+    map.insert(VK::Escape, None);
+    let mut config = super::Config::default();
+    config.shortcuts = shortcuts;
+
+    let s = dep_toml::to_string(&config).unwrap();
+    assert!(s.contains("Key1 = \"Home\""));
+    let config2: super::Config = dep_toml::from_str(&s).unwrap();
+    let shortcuts2 = &config2.shortcuts;
+    assert_eq!(shortcuts2.get(ctrl, VK::Q), Some(Command::Exit));
+    assert_eq!(shortcuts2.get(ctrl, VK::Key1), Some(Command::Home));
+    assert_eq!(shortcuts2.get(ctrl, VK::Escape), None);
+    assert_eq!(config2.menu_delay_ns, config.menu_delay_ns);
+    assert_eq!(dep_toml::to_string(&config2).unwrap(), s);
+}
@@ -0,0 +1,458 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Localisation
+//!
+//! UI strings are looked up by message identifier in a [`Catalogue`]. By
+//! convention, the identifier is the untranslated (source language) text,
+//! which is used when no translation is available. Messages may contain
+//! accelerator-key markers (e.g. `&Save`) and arguments (e.g. `{name}`).
+//!
+//! The [`Message`] type describes a message with arguments and optional
+//! plural count, and resolves through a catalogue; the
+//! [`LocalisedLabel`](crate::widget::LocalisedLabel) widget displays one.
+//!
+//! The active catalogue is held by the shell; see
+//! [`Manager::set_language`](crate::event::Manager::set_language).
+//! Switching language re-configures all windows, thus all localised text is
+//! re-resolved and re-prepared.
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::path::Path;
+use thiserror::Error;
+
+/// Plural category
+///
+/// Categories follow the [Unicode CLDR] plural rules.
+///
+/// [Unicode CLDR]: https://cldr.unicode.org/index/cldr-spec/plural-rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Plural {
+    /// Zero (e.g. Arabic)
+    Zero,
+    /// Singular
+    One,
+    /// Dual (e.g. Arabic)
+    Two,
+    /// Paucal (e.g. Russian, Polish)
+    Few,
+    /// Many (e.g. Russian, Polish, Arabic)
+    Many,
+    /// General plural; the only category in some languages
+    Other,
+}
+
+impl Plural {
+    /// Get the category name, as used in catalogue keys
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Plural::Zero => "zero",
+            Plural::One => "one",
+            Plural::Two => "two",
+            Plural::Few => "few",
+            Plural::Many => "many",
+            Plural::Other => "other",
+        }
+    }
+
+    /// Get the plural category of `n` for `language`
+    ///
+    /// The `language` is a language tag such as `en` or `pt-BR`; only the
+    /// primary language subtag is used. Rules are implemented for a selection
+    /// of common languages; others use the English rule (`one` for 1,
+    /// otherwise `other`).
+    pub fn of(language: &str, n: u64) -> Plural {
+        let lang = language.split(|c| c == '-' || c == '_').next().unwrap();
+        let (n10, n100) = (n % 10, n % 100);
+        match lang {
+            "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "tr" => Plural::Other,
+            "fr" | "pt" => match n {
+                0 | 1 => Plural::One,
+                _ => Plural::Other,
+            },
+            "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+                if n10 == 1 && n100 != 11 {
+                    Plural::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    Plural::Few
+                } else {
+                    Plural::Many
+                }
+            }
+            "pl" => {
+                if n == 1 {
+                    Plural::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    Plural::Few
+                } else {
+                    Plural::Many
+                }
+            }
+            "cs" | "sk" => match n {
+                1 => Plural::One,
+                2..=4 => Plural::Few,
+                _ => Plural::Other,
+            },
+            "ar" => match (n, n100) {
+                (0, _) => Plural::Zero,
+                (1, _) => Plural::One,
+                (2, _) => Plural::Two,
+                (_, 3..=10) => Plural::Few,
+                (_, 11..=99) => Plural::Many,
+                _ => Plural::Other,
+            },
+            _ => match n {
+                1 => Plural::One,
+                _ => Plural::Other,
+            },
+        }
+    }
+}
+
+impl Display for Plural {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A message catalogue
+///
+/// A catalogue maps message identifiers to translated messages for one
+/// language. Plural forms are stored under keys of the form `id[category]`
+/// (e.g. `{n} files[one]`); see [`Plural`].
+pub trait Catalogue: Debug {
+    /// The language tag (e.g. `en` or `pt-BR`)
+    fn language(&self) -> &str;
+
+    /// Look up a message by identifier
+    fn get(&self, id: &str) -> Option<&str>;
+
+    /// Look up a plural message by identifier and count
+    ///
+    /// The default implementation tries the key for the plural category of
+    /// `n`, then the `other` category, then plain `id`.
+    fn get_plural(&self, id: &str, n: u64) -> Option<&str> {
+        let category = Plural::of(self.language(), n);
+        self.get(&format!("{}[{}]", id, category))
+            .or_else(|| self.get(&format!("{}[other]", id)))
+            .or_else(|| self.get(id))
+    }
+}
+
+/// Error loading a [`FileCatalogue`]
+#[derive(Error, Debug)]
+pub enum CatalogueError {
+    #[error("error reading catalogue file")]
+    IoError(#[from] std::io::Error),
+    #[error("catalogue parse error on line {0}: expected `id = message`")]
+    Parse(usize),
+}
+
+/// A simple file-based message catalogue
+///
+/// The file format is line-based and UTF-8 encoded:
+///
+/// ```text
+/// # Comments start with '#'
+/// &Open = &Ouvrir
+/// Hello, {name}! = Bonjour, {name} !
+/// {n} files[one] = {n} fichier
+/// {n} files[other] = {n} fichiers
+/// ```
+///
+/// Each entry is of the form `id = message`, split at the first ` = ` on the
+/// line, thus the message may contain ` = ` while an identifier containing
+/// ` = ` must escape it as ` \= `. Escape sequences `\n`, `\t`, `\=` and `\\`
+/// are recognised in both parts. Blank lines and lines starting with `#` are
+/// ignored.
+#[derive(Clone, Debug, Default)]
+pub struct FileCatalogue {
+    language: String,
+    messages: HashMap<String, String>,
+}
+
+impl FileCatalogue {
+    /// Construct an empty catalogue
+    pub fn new<S: ToString>(language: S) -> Self {
+        FileCatalogue {
+            language: language.to_string(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Parse a catalogue from `source`
+    pub fn parse<S: ToString>(language: S, source: &str) -> Result<Self, CatalogueError> {
+        let mut cat = FileCatalogue::new(language);
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match find_separator(line) {
+                Some(pos) => {
+                    let id = unescape(line[..pos].trim_end());
+                    let message = unescape(line[pos + 3..].trim_start());
+                    cat.messages.insert(id, message);
+                }
+                None => return Err(CatalogueError::Parse(i + 1)),
+            }
+        }
+        Ok(cat)
+    }
+
+    /// Load a catalogue from the file at `path`
+    pub fn load<S: ToString, P: AsRef<Path>>(language: S, path: P) -> Result<Self, CatalogueError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(language, &source)
+    }
+
+    /// Add or replace a message
+    pub fn insert<S: ToString, T: ToString>(&mut self, id: S, message: T) {
+        self.messages.insert(id.to_string(), message.to_string());
+    }
+
+    /// Get the number of messages
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// True if the catalogue is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl Catalogue for FileCatalogue {
+    fn language(&self) -> &str {
+        &self.language
+    }
+
+    fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(|s| s.as_str())
+    }
+}
+
+/// Find the first ` = ` in `line` not following an escape character
+fn find_separator(line: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if line[i..].starts_with(" = ") {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(c) => result.push(c),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// A localisable message
+///
+/// This consists of a message identifier, named arguments and an optional
+/// plural count. Use [`Message::resolve`] to translate and format.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Message {
+    id: String,
+    other: Option<String>,
+    count: Option<u64>,
+    args: Vec<(String, String)>,
+}
+
+impl Message {
+    /// Construct a message with identifier `id`
+    pub fn new<S: ToString>(id: S) -> Self {
+        Message {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Construct a plural message
+    ///
+    /// The identifier is `one`. Without a translation, `one` is used when
+    /// `n == 1`, otherwise `other`. The count is available as argument `{n}`.
+    pub fn plural<S: ToString, T: ToString>(one: S, other: T, n: u64) -> Self {
+        Message {
+            id: one.to_string(),
+            other: Some(other.to_string()),
+            ..Default::default()
+        }
+        .with_count(n)
+    }
+
+    /// Set the plural count (inline)
+    ///
+    /// The count is available as argument `{n}`.
+    pub fn with_count(mut self, n: u64) -> Self {
+        self.count = Some(n);
+        self.with_arg("n", n)
+    }
+
+    /// Set an argument (inline)
+    ///
+    /// Occurrences of `{name}` in the message are replaced with `value`.
+    pub fn with_arg<V: Display>(mut self, name: &str, value: V) -> Self {
+        self.set_arg(name, value);
+        self
+    }
+
+    /// Set an argument
+    ///
+    /// Occurrences of `{name}` in the message are replaced with `value`.
+    pub fn set_arg<V: Display>(&mut self, name: &str, value: V) {
+        let value = value.to_string();
+        match self.args.iter_mut().find(|arg| arg.0 == name) {
+            Some(arg) => arg.1 = value,
+            None => self.args.push((name.to_string(), value)),
+        }
+    }
+
+    /// Get the message identifier
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Translate and format the message
+    ///
+    /// If `catalogue` is `None` or has no translation for this message, the
+    /// untranslated message is used.
+    pub fn resolve(&self, catalogue: Option<&dyn Catalogue>) -> String {
+        let translated = catalogue.and_then(|cat| match self.count {
+            Some(n) => cat.get_plural(&self.id, n),
+            None => cat.get(&self.id),
+        });
+        let template = match (translated, &self.other, self.count) {
+            (Some(s), _, _) => s,
+            (None, Some(other), Some(n)) if n != 1 => other.as_str(),
+            (None, _, _) => self.id.as_str(),
+        };
+        format_message(template, &self.args)
+    }
+}
+
+impl From<&str> for Message {
+    fn from(id: &str) -> Self {
+        Message::new(id)
+    }
+}
+
+impl From<String> for Message {
+    fn from(id: String) -> Self {
+        Message {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+/// Substitute arguments into a message template
+///
+/// Occurrences of `{name}` are replaced by the value of the argument `name`;
+/// unknown arguments are left as is. Use `{{` and `}}` for literal braces.
+pub fn format_message<S: AsRef<str>>(template: &str, args: &[(S, S)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(|c| c == '{' || c == '}') {
+        result.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            result.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('{') {
+            if let Some(end) = tail.find('}') {
+                let name = &tail[1..end];
+                let arg = args.iter().find(|arg| arg.0.as_ref() == name);
+                if let Some(arg) = arg {
+                    result.push_str(arg.1.as_ref());
+                    rest = &tail[end + 1..];
+                    continue;
+                }
+            }
+        }
+        result.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[test]
+fn catalogue() {
+    let source = r"
+# French
+&Open = &Ouvrir
+Hello, {name}! = Bonjour, {name} !
+{n} files[one] = {n} fichier
+{n} files[other] = {n} fichiers
+Line = Ligne\nsuivante
+a = b = a = b
+x \= y = x \= y
+Path\\ = Chemin\\
+";
+    let cat = FileCatalogue::parse("fr", source).unwrap();
+    assert_eq!(cat.len(), 8);
+    let cat: Option<&dyn Catalogue> = Some(&cat);
+
+    assert_eq!(Message::new("&Open").resolve(cat), "&Ouvrir");
+    assert_eq!(Message::new("Line").resolve(cat), "Ligne\nsuivante");
+    assert_eq!(Message::new("Missing").resolve(cat), "Missing");
+    assert_eq!(Message::new("a").resolve(cat), "b = a = b");
+    assert_eq!(Message::new("x = y").resolve(cat), "x = y");
+    assert_eq!(Message::new("Path\\").resolve(cat), "Chemin\\");
+    let msg = Message::new("Hello, {name}!").with_arg("name", "Ada");
+    assert_eq!(msg.resolve(cat), "Bonjour, Ada !");
+    assert_eq!(msg.resolve(None), "Hello, Ada!");
+
+    let files = |n| Message::plural("{n} file", "{n} files", n);
+    assert_eq!(files(0).resolve(None), "0 files");
+    assert_eq!(files(1).resolve(None), "1 file");
+    let files = |n| Message::new("{n} files").with_count(n);
+    assert_eq!(files(0).resolve(cat), "0 fichier");
+    assert_eq!(files(2).resolve(cat), "2 fichiers");
+
+    assert!(FileCatalogue::parse("fr", "no separator").is_err());
+}
+
+#[test]
+fn plural_rules() {
+    assert_eq!(Plural::of("en-GB", 1), Plural::One);
+    assert_eq!(Plural::of("en", 0), Plural::Other);
+    assert_eq!(Plural::of("ru", 21), Plural::One);
+    assert_eq!(Plural::of("ru", 12), Plural::Many);
+    assert_eq!(Plural::of("pl", 22), Plural::Few);
+    assert_eq!(Plural::of("ja", 1), Plural::Other);
+}
+
+#[test]
+fn format() {
+    let args = [("a", "1"), ("b", "2")];
+    assert_eq!(format_message("{a}+{b}", &args), "1+2");
+    assert_eq!(format_message("{{a}} {c} {a", &args), "{a} {c} {a");
+}
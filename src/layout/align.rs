@@ -7,6 +7,7 @@
 
 #[allow(unused)]
 use super::Stretch; // for doc-links
+use crate::dir::LayoutDir;
 use crate::geom::{Rect, Size};
 
 pub use crate::text::Align;
@@ -29,10 +30,16 @@ pub use crate::text::Align;
 ///     .aligned_rect(pref_size, rect);
 /// // self.core.rect = rect;
 /// ```
+///
+/// Horizontal alignment is *logical*: with right-to-left layout direction
+/// (`dir`), [`Align::TL`] and [`Align::BR`] are mirrored. Parents should pass
+/// `dir` on to children. The methods [`AlignHints::unwrap_or`] and
+/// [`AlignHints::complete`] return physical alignments.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct AlignHints {
     pub horiz: Option<Align>,
     pub vert: Option<Align>,
+    pub dir: LayoutDir,
 }
 
 impl AlignHints {
@@ -40,19 +47,42 @@ impl AlignHints {
     pub const NONE: AlignHints = AlignHints::new(None, None);
 
     /// Construct with optional horiz. and vert. alignment
+    ///
+    /// Layout direction is left-to-right.
     pub const fn new(horiz: Option<Align>, vert: Option<Align>) -> Self {
-        Self { horiz, vert }
+        let dir = LayoutDir::Ltr;
+        Self { horiz, vert, dir }
+    }
+
+    /// Set the layout direction (inline)
+    #[inline]
+    pub const fn with_dir(self, dir: LayoutDir) -> Self {
+        AlignHints { dir, ..self }
+    }
+
+    /// Resolve logical horizontal alignment to physical alignment
+    fn resolve_horiz(&self, horiz: Align) -> Align {
+        match (self.dir, horiz) {
+            (LayoutDir::Rtl, Align::TL) => Align::BR,
+            (LayoutDir::Rtl, Align::BR) => Align::TL,
+            (_, align) => align,
+        }
     }
 
     /// Unwrap type's alignments or substitute parameters
+    ///
+    /// The result is physical (see type-level documentation).
     pub fn unwrap_or(self, horiz: Align, vert: Align) -> (Align, Align) {
-        (self.horiz.unwrap_or(horiz), self.vert.unwrap_or(vert))
+        let horiz = self.resolve_horiz(self.horiz.unwrap_or(horiz));
+        (horiz, self.vert.unwrap_or(vert))
     }
 
     /// Complete via default alignments
+    ///
+    /// The result is physical (see type-level documentation).
     pub fn complete(&self, horiz: Align, vert: Align) -> CompleteAlignment {
         CompleteAlignment {
-            halign: self.horiz.unwrap_or(horiz),
+            halign: self.resolve_horiz(self.horiz.unwrap_or(horiz)),
             valign: self.vert.unwrap_or(vert),
         }
     }
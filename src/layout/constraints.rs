@@ -0,0 +1,190 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Size constraints
+
+use super::{Align, AlignHints, AxisInfo, SizeRules, Stretch};
+use crate::conv::ConvFloat;
+use crate::geom::Rect;
+
+/// Size constraints
+///
+/// These may be applied to any widget to override its minimum and maximum
+/// size and/or to fix its aspect ratio. Sizes are in logical pixels (i.e. are
+/// multiplied by the scale factor).
+///
+/// Constraints are applied by adjusting the widget's [`SizeRules`] (see
+/// [`Constraints::apply_rules`]), which parent solvers honour, and by adjusting
+/// the widget's [`Rect`] within the space assigned by its parent (see
+/// [`Constraints::apply_rect`]).
+///
+/// Constraints may be specified for children of widgets using a derived
+/// layout (e.g. `#[widget(max_width = 200, aspect = 1.5)]`) or by wrapping a
+/// widget with [`kas::widget::Constrained`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Constraints {
+    /// Minimum width
+    pub min_width: Option<f32>,
+    /// Maximum width
+    pub max_width: Option<f32>,
+    /// Minimum height
+    pub min_height: Option<f32>,
+    /// Maximum height
+    pub max_height: Option<f32>,
+    /// Aspect ratio: width / height
+    pub aspect: Option<f32>,
+}
+
+impl Constraints {
+    /// No constraints
+    pub const NONE: Constraints = Constraints::new();
+
+    /// Construct with no constraints
+    pub const fn new() -> Self {
+        Constraints {
+            min_width: None,
+            max_width: None,
+            min_height: None,
+            max_height: None,
+            aspect: None,
+        }
+    }
+
+    /// Set the minimum width (inline)
+    #[inline]
+    pub fn with_min_width(mut self, width: f32) -> Self {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Set the maximum width (inline)
+    #[inline]
+    pub fn with_max_width(mut self, width: f32) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set the minimum height (inline)
+    #[inline]
+    pub fn with_min_height(mut self, height: f32) -> Self {
+        self.min_height = Some(height);
+        self
+    }
+
+    /// Set the maximum height (inline)
+    #[inline]
+    pub fn with_max_height(mut self, height: f32) -> Self {
+        self.max_height = Some(height);
+        self
+    }
+
+    /// Set the aspect ratio, width / height (inline)
+    #[inline]
+    pub fn with_aspect(mut self, aspect: f32) -> Self {
+        debug_assert!(aspect > 0.0);
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// True if no constraints are set
+    pub fn is_none(&self) -> bool {
+        *self == Constraints::NONE
+    }
+
+    /// Apply constraints to a widget's `rules`
+    ///
+    /// This should be applied to the result of [`kas::Layout::size_rules`]:
+    ///
+    /// -   The minimum size is adjusted to lie within the min/max constraints
+    ///     (the minimum constraint taking precedence over the maximum)
+    /// -   With a maximum constraint, stretching widgets become non-stretching
+    ///     with an ideal size of the maximum; solvers will thus not allocate
+    ///     more than this unless there is no other use for the space
+    /// -   With an aspect ratio, vertical rules are fixed according to the
+    ///     width, if known (via [`AxisInfo::other`])
+    pub fn apply_rules(&self, rules: SizeRules, axis: AxisInfo, scale_factor: f32) -> SizeRules {
+        let scale = |size: Option<f32>| size.map(|s| i32::conv_nearest(s * scale_factor));
+        let (min, max) = match axis.is_vertical() {
+            false => (scale(self.min_width), scale(self.max_width)),
+            true => (scale(self.min_height), scale(self.max_height)),
+        };
+
+        let mut min_size = rules.min_size();
+        let mut ideal = rules.ideal_size();
+        let mut stretch = rules.stretch();
+
+        if let (true, Some(aspect), Some(width)) = (axis.is_vertical(), self.aspect, axis.other()) {
+            let height = i32::conv_nearest(width as f32 / aspect);
+            min_size = height;
+            ideal = height;
+            stretch = Stretch::None;
+        }
+
+        if let Some(max) = max {
+            if stretch > Stretch::None {
+                ideal = max;
+                stretch = Stretch::None;
+            }
+            min_size = min_size.min(max);
+            ideal = ideal.min(max);
+        }
+        if let Some(min) = min {
+            min_size = min_size.max(min);
+            ideal = ideal.max(min);
+        }
+
+        SizeRules::new(min_size, ideal, rules.margins(), stretch)
+    }
+
+    /// Apply constraints to a widget's `rect`
+    ///
+    /// The `rect` assigned by the parent is reduced to meet maximum size and
+    /// aspect ratio constraints (if necessary), then aligned within the
+    /// original `rect` according to `align` (default: centred).
+    pub fn apply_rect(&self, rect: Rect, align: AlignHints, scale_factor: f32) -> Rect {
+        let scale = |size: Option<f32>| size.map(|s| i32::conv_nearest(s * scale_factor));
+        let mut size = rect.size;
+        if let Some(max) = scale(self.max_width) {
+            size.0 = size.0.min(max);
+        }
+        if let Some(max) = scale(self.max_height) {
+            size.1 = size.1.min(max);
+        }
+        if let Some(aspect) = self.aspect {
+            if size.0 as f32 > size.1 as f32 * aspect {
+                size.0 = i32::conv_nearest(size.1 as f32 * aspect);
+            } else {
+                size.1 = i32::conv_nearest(size.0 as f32 / aspect);
+            }
+        }
+
+        align
+            .complete(Align::Centre, Align::Centre)
+            .aligned_rect(size, rect)
+    }
+}
+
+#[test]
+fn constraints() {
+    let axis = AxisInfo::new(false, None);
+    let rules = SizeRules::new(10, 20, (1, 1), Stretch::Low);
+
+    let c = Constraints::new().with_max_width(15.0);
+    let r = c.apply_rules(rules, axis, 1.0);
+    assert_eq!(r, SizeRules::new(10, 15, (1, 1), Stretch::None));
+
+    let c = Constraints::new().with_min_width(30.0).with_max_width(15.0);
+    let r = c.apply_rules(rules, axis, 2.0);
+    assert_eq!(r, SizeRules::new(60, 60, (1, 1), Stretch::None));
+
+    let c = Constraints::new().with_aspect(2.0);
+    let r = c.apply_rules(rules, AxisInfo::new(true, Some(40)), 1.0);
+    assert_eq!(r, SizeRules::new(20, 20, (1, 1), Stretch::None));
+
+    use crate::geom::{Coord, Size};
+    let rect = Rect::new(Coord(0, 0), Size(100, 100));
+    let r = c.apply_rect(rect, AlignHints::NONE, 1.0);
+    assert_eq!(r, Rect::new(Coord(0, 25), Size(100, 50)));
+}
@@ -0,0 +1,273 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Layout debugging overlay
+
+use std::collections::HashMap;
+
+use super::{AxisInfo, SizeRules, Stretch};
+use crate::dir::LayoutDir;
+use crate::draw::{Colour, Draw, DrawText, Pass, SizeHandle, TextClass};
+use crate::event::{Manager, ManagerState};
+use crate::geom::{Offset, Quad, Rect, Vec2};
+use crate::text::{EditableTextApi, Text, TextApi};
+use crate::{WidgetConfig, WidgetId};
+
+/// Depth of the overlay's draw passes (above popups)
+const DEPTH: f32 = 0.9;
+
+const MARGIN_COL: Colour = Colour {
+    r: 1.0,
+    g: 0.0,
+    b: 1.0,
+    a: 0.25,
+};
+const HOVER_COL: Colour = Colour {
+    r: 1.0,
+    g: 1.0,
+    b: 0.0,
+    a: 0.2,
+};
+const LABEL_COL: Colour = Colour {
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
+    a: 0.8,
+};
+const TEXT_COL: Colour = Colour::grey(1.0);
+
+/// Colour used to represent a [`Stretch`] policy
+fn stretch_col(stretch: Stretch) -> Colour {
+    match stretch {
+        Stretch::None => Colour::grey(0.5),
+        Stretch::Filler => Colour::new(0.0, 0.6, 1.0),
+        Stretch::Low => Colour::new(0.0, 0.8, 0.0),
+        Stretch::High => Colour::new(1.0, 0.6, 0.0),
+        Stretch::Maximize => Colour::new(1.0, 0.0, 0.0),
+    }
+}
+
+/// A layout debugging overlay
+///
+/// This draws, over the top of a window's contents:
+///
+/// -   the [`Rect`] of each widget as an outline, coloured according to the
+///     [`Stretch`] policy (left and right edges according to the horizontal
+///     policy, top and bottom edges according to the vertical policy):
+///     grey for `None`, blue for `Filler`, green for `Low`, orange for `High`
+///     and red for `Maximize`
+/// -   the outer margins of each widget (see [`SizeRules::margins`]) as a
+///     translucent magenta frame
+/// -   the size, minimum size and ideal size of the hovered widget
+///
+/// Only widgets visible within their parent are shown; in particular, popups
+/// are not shown.
+///
+/// Drawing uses only the [`Draw`] and [`DrawText`] traits, hence this overlay
+/// may be used by any shell. Usage: call [`DebugOverlay::collect`] after each
+/// layout solve, [`DebugOverlay::update_hover`] after handling events and
+/// [`DebugOverlay::draw`] after drawing the window.
+#[derive(Debug)]
+pub struct DebugOverlay {
+    rules: HashMap<WidgetId, (SizeRules, SizeRules)>,
+    hover: Option<WidgetId>,
+    text: Text<String>,
+    text_size: Vec2,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        DebugOverlay::new()
+    }
+}
+
+impl DebugOverlay {
+    /// Construct
+    pub fn new() -> Self {
+        DebugOverlay {
+            rules: Default::default(),
+            hover: None,
+            text: Text::new(Default::default(), String::new()),
+            text_size: Vec2::ZERO,
+        }
+    }
+
+    /// Collect the size rules of each widget
+    ///
+    /// This should be called after layout is solved (see
+    /// [`super::SolveCache::apply_rect`]) since the width of each widget is
+    /// required to calculate vertical size rules.
+    ///
+    /// Rules are collected children-first, finishing with the `widget` itself
+    /// using the same axes as [`super::SolveCache::apply_rect`]. Widget layout
+    /// state thus matches that of the last solve, and the caller need only
+    /// re-apply widget rects (calling `apply_rect` again without invalidating
+    /// the rule cache does this without re-solving size rules).
+    pub fn collect(&mut self, widget: &mut dyn WidgetConfig, mgr: &mut Manager) {
+        self.rules.clear();
+        let dir = mgr.layout_dir();
+        let rules = &mut self.rules;
+        let text = &mut self.text;
+        mgr.size_handle(|size_handle| {
+            // This sets the text environment (font size and scale factor)
+            size_handle.text_bound(text, TextClass::LabelFixed, AxisInfo::new(false, None));
+            collect_rules(rules, widget, size_handle, dir);
+        });
+    }
+
+    /// Update the hovered widget
+    ///
+    /// Returns true when a redraw is required.
+    pub fn update_hover(&mut self, mgr: &ManagerState) -> bool {
+        let hover = mgr.hover();
+        let changed = hover != self.hover;
+        self.hover = hover;
+        changed
+    }
+
+    /// Draw the overlay
+    ///
+    /// The `widget` should be the window's root widget and `rect` the window's
+    /// [`Rect`].
+    pub fn draw<D: Draw + DrawText + ?Sized>(
+        &mut self,
+        draw: &mut D,
+        widget: &dyn WidgetConfig,
+        rect: Rect,
+    ) {
+        let pass = draw.add_clip_region(rect, DEPTH);
+        let mut hovered = None;
+        self.draw_widget(draw, pass, widget, Offset::ZERO, rect, &mut hovered);
+
+        if let Some((widget_rect, rules)) = hovered {
+            self.draw_hover(draw, pass, rect, widget_rect, rules);
+        }
+    }
+
+    fn draw_widget<D: Draw + ?Sized>(
+        &self,
+        draw: &mut D,
+        pass: Pass,
+        widget: &dyn WidgetConfig,
+        offset: Offset,
+        clip: Rect,
+        hovered: &mut Option<(Rect, Option<(SizeRules, SizeRules)>)>,
+    ) {
+        let rect = widget.rect() - offset;
+        let rules = self.rules.get(&widget.id()).cloned();
+        if self.hover == Some(widget.id()) {
+            *hovered = Some((rect, rules));
+        }
+
+        let quad = Quad::from(rect);
+        let (hcol, vcol) = match rules {
+            Some((w, h)) => {
+                let (left, right) = w.margins_i32();
+                let (top, bottom) = h.margins_i32();
+                if (left, right, top, bottom) != (0, 0, 0, 0) {
+                    let a = quad.a - Vec2(left as f32, top as f32);
+                    let b = quad.b + Vec2(right as f32, bottom as f32);
+                    draw.frame(pass, Quad::with_coords(a, b), quad, MARGIN_COL);
+                }
+                (stretch_col(w.stretch()), stretch_col(h.stretch()))
+            }
+            None => (Colour::grey(1.0), Colour::grey(1.0)),
+        };
+
+        let (a, b) = (quad.a, quad.b);
+        draw.rect(pass, Quad::with_coords(a, Vec2(a.0 + 1.0, b.1)), hcol);
+        draw.rect(pass, Quad::with_coords(Vec2(b.0 - 1.0, a.1), b), hcol);
+        draw.rect(pass, Quad::with_coords(a, Vec2(b.0, a.1 + 1.0)), vcol);
+        draw.rect(pass, Quad::with_coords(Vec2(a.0, b.1 - 1.0), b), vcol);
+
+        let visible = match rect.intersection(&clip) {
+            Some(rect) => rect,
+            None => return,
+        };
+        for i in 0..widget.num_children() {
+            let child = match widget.get_child(i) {
+                Some(child) => child,
+                None => continue,
+            };
+            let translation = widget.translation(i);
+            let child_offset = offset + translation;
+            let child_rect = child.rect() - child_offset;
+            if child_rect.intersection(&visible).is_none() {
+                continue;
+            }
+            // Translated (scrolled) children are clipped to the parent
+            let pass = match translation == Offset::ZERO {
+                true => pass,
+                false => draw.add_clip_region(visible, DEPTH),
+            };
+            self.draw_widget(draw, pass, child, child_offset, visible, hovered);
+        }
+    }
+
+    fn draw_hover<D: Draw + DrawText + ?Sized>(
+        &mut self,
+        draw: &mut D,
+        pass: Pass,
+        window: Rect,
+        rect: Rect,
+        rules: Option<(SizeRules, SizeRules)>,
+    ) {
+        draw.rect(pass, rect.into(), HOVER_COL);
+
+        let mut s = format!("size: {}×{}", rect.size.0, rect.size.1);
+        if let Some((w, h)) = rules {
+            s += &format!(
+                "\nmin: {}×{}\nideal: {}×{}\nstretch: {:?}, {:?}",
+                w.min_size(),
+                h.min_size(),
+                w.ideal_size(),
+                h.ideal_size(),
+                w.stretch(),
+                h.stretch()
+            );
+        }
+        self.text.set_string(s);
+        if let Some(size) = self.text.prepare() {
+            self.text_size = size.into();
+        }
+
+        // Place the label below the widget if possible, otherwise above
+        let size = self.text_size + Vec2::splat(4.0);
+        let win = Quad::from(window);
+        let mut pos = Vec2::from(rect.pos + Offset(0, rect.size.1));
+        if pos.1 + size.1 > win.b.1 {
+            pos.1 = Vec2::from(rect.pos).1 - size.1;
+        }
+        pos.0 = pos.0.min(win.b.0 - size.0).max(win.a.0);
+        pos.1 = pos.1.max(win.a.1);
+
+        let quad = Quad::with_pos_and_size(pos, size);
+        draw.rect(pass, quad, LABEL_COL);
+        let pos = pos + Vec2::splat(2.0);
+        let bounds = self.text_size;
+        draw.text(pass, pos, bounds, Vec2::ZERO, self.text.as_ref(), TEXT_COL);
+    }
+}
+
+fn collect_rules(
+    rules: &mut HashMap<WidgetId, (SizeRules, SizeRules)>,
+    widget: &mut dyn WidgetConfig,
+    size_handle: &mut dyn SizeHandle,
+    dir: LayoutDir,
+) {
+    // Children are visited first: since each parent calls size_rules on its
+    // children, the final call on each widget is made by its parent.
+    for i in 0..widget.num_children() {
+        if let Some(child) = widget.get_child_mut(i) {
+            collect_rules(rules, child, size_handle, dir);
+        }
+    }
+
+    let axis = AxisInfo::new(false, None).with_dir(dir);
+    let w = widget.size_rules(size_handle, axis);
+    let axis = AxisInfo::new(true, Some(widget.rect().size.0)).with_dir(dir);
+    let h = widget.size_rules(size_handle, axis);
+    rules.insert(widget.id(), (w, h));
+}
@@ -0,0 +1,392 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Flow (wrapping row) solver
+
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use super::{Align, AlignHints, AxisInfo, SizeRules};
+use super::{GridStorage, RowTemp, RulesSetter, RulesSolver};
+use crate::geom::{Coord, Offset, Rect, Size};
+
+/// Find the end of the line starting at `start`
+///
+/// Children are added to the line while the line's ideal width does not
+/// exceed `width`. Each line contains at least one child.
+fn line_end(rules: &[SizeRules], start: usize, width: i32) -> usize {
+    let mut sum = rules[start];
+    let mut end = start + 1;
+    while end < rules.len() {
+        let next = sum.appended(rules[end]);
+        if next.ideal_size() > width {
+            break;
+        }
+        sum = next;
+        end += 1;
+    }
+    end
+}
+
+/// Iterator over lines (as ranges of child indices)
+struct Lines<'a> {
+    rules: &'a [SizeRules],
+    width: i32,
+    start: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(rules: &'a [SizeRules], width: i32) -> Self {
+        Lines {
+            rules,
+            width,
+            start: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        if self.start >= self.rules.len() {
+            return None;
+        }
+        let start = self.start;
+        self.start = line_end(self.rules, start, self.width);
+        Some(start..self.start)
+    }
+}
+
+/// Solve widths of children within each line
+///
+/// Returns the number of lines.
+fn solve_widths<S: GridStorage>(storage: &mut S, len: usize, width: i32) -> usize {
+    let (rules, widths) = storage.rules_and_widths();
+    let mut lines = 0;
+    for range in Lines::new(&rules[0..len], width) {
+        let target = width.min(SizeRules::sum(&rules[range.clone()]).max_size());
+        SizeRules::solve_seq(&mut widths[range.clone()], &rules[range], target);
+        lines += 1;
+    }
+    lines
+}
+
+/// A [`RulesSolver`] for flows: rows which wrap onto new lines
+///
+/// Children are placed left-to-right (in logical order; see
+/// [`kas::dir::LayoutDir`]), starting a new line below whenever the available
+/// width is exhausted. The vertical size is thus dependent on the width
+/// (height-for-width): when solving the vertical axis, the width is taken from
+/// [`AxisInfo::other`] (or, if not fixed, the ideal width of a single line).
+///
+/// This re-uses [`GridStorage`]: "column" data holds each child's width rules
+/// and widths while "row" data holds each child's height rules and heights.
+/// Storage dimensions are thus `(len, len)` for `len` children.
+pub struct FlowSolver<S: GridStorage> {
+    axis: AxisInfo,
+    len: usize,
+    width: i32,
+    _s: PhantomData<S>,
+}
+
+impl<S: GridStorage> FlowSolver<S> {
+    /// Construct.
+    ///
+    /// Argument order is consistent with other [`RulesSolver`]s.
+    ///
+    /// - `axis`: `AxisInfo` instance passed into `size_rules`
+    /// - `len`: number of items
+    /// - `storage`: reference to persistent storage
+    pub fn new(axis: AxisInfo, len: usize, storage: &mut S) -> Self {
+        storage.set_dims(len, len);
+
+        let mut width = 0;
+        if axis.is_vertical() {
+            width = axis
+                .other()
+                .unwrap_or_else(|| storage.width_rules()[len].ideal_size());
+            solve_widths(storage, len, width);
+        }
+
+        FlowSolver {
+            axis,
+            len,
+            width,
+            _s: Default::default(),
+        }
+    }
+}
+
+impl<S: GridStorage> RulesSolver for FlowSolver<S> {
+    type Storage = S;
+    type ChildInfo = usize;
+
+    fn for_child<CR: FnOnce(AxisInfo) -> SizeRules>(
+        &mut self,
+        storage: &mut Self::Storage,
+        child_info: Self::ChildInfo,
+        child_rules: CR,
+    ) {
+        if self.axis.is_horizontal() {
+            self.axis.has_fixed = false;
+            storage.width_rules()[child_info] = child_rules(self.axis);
+        } else {
+            self.axis.has_fixed = true;
+            self.axis.other_axis = storage.widths()[child_info];
+            storage.height_rules()[child_info] = child_rules(self.axis);
+        }
+    }
+
+    fn finish(self, storage: &mut Self::Storage) -> SizeRules {
+        let len = self.len;
+        if len == 0 {
+            return SizeRules::EMPTY;
+        }
+
+        if self.axis.is_horizontal() {
+            // Minimum: the widest child on its own; ideal: a single line
+            let rules = &storage.width_rules()[0..len];
+            let sum = SizeRules::sum(rules);
+            let max = rules.iter().fold(SizeRules::EMPTY, |a, b| a.max(*b));
+            let rules = SizeRules::new(
+                max.min_size(),
+                sum.ideal_size(),
+                max.margins(),
+                sum.stretch(),
+            );
+            storage.width_rules()[len] = rules;
+            rules
+        } else {
+            let mut total: Option<SizeRules> = None;
+            let width_rules = storage.width_rules()[0..len].to_vec();
+            let height_rules = &storage.height_rules()[0..len];
+            for range in Lines::new(&width_rules, self.width) {
+                let line = height_rules[range]
+                    .iter()
+                    .fold(SizeRules::EMPTY, |a, b| a.max(*b));
+                total = Some(match total {
+                    Some(rules) => rules.appended(line),
+                    None => line,
+                });
+            }
+            let rules = total.unwrap_or(SizeRules::EMPTY);
+            storage.height_rules()[len] = rules;
+            rules
+        }
+    }
+}
+
+/// A [`RulesSetter`] for flows
+///
+/// See [`FlowSolver`]. With right-to-left layout direction, each line is
+/// mirrored (the first child is on the right).
+pub struct FlowSetter<T: RowTemp, S: GridStorage> {
+    x_offsets: T,
+    y_offsets: T,
+    rect: Rect,
+    // If right-to-left, the width within which to mirror children
+    mirror: Option<i32>,
+    _s: PhantomData<S>,
+}
+
+impl<T: RowTemp, S: GridStorage> FlowSetter<T, S> {
+    /// Construct
+    ///
+    /// Argument order is consistent with other [`RulesSetter`]s.
+    ///
+    /// -   `rect`: the [`Rect`] within which to position children
+    /// -   `len`: number of items
+    /// -   `align`: alignment hints; horizontal alignment applies to each line
+    /// -   `storage`: access to the solver's storage
+    pub fn new(rect: Rect, len: usize, align: AlignHints, storage: &mut S) -> Self {
+        let mut x_offsets = T::default();
+        x_offsets.set_len(len);
+        let mut y_offsets = T::default();
+        y_offsets.set_len(len);
+
+        storage.set_dims(len, len);
+
+        if len > 0 {
+            let width = rect.size.0;
+            let num_lines = solve_widths(storage, len, width);
+
+            let width_rules = storage.width_rules()[0..len].to_vec();
+            let mut line_rules = Vec::with_capacity(num_lines);
+            for range in Lines::new(&width_rules, width) {
+                let line = storage.height_rules()[range]
+                    .iter()
+                    .fold(SizeRules::EMPTY, |a, b| a.max(*b));
+                line_rules.push(line);
+            }
+
+            let mut height = rect.size.1;
+            let mut y = 0;
+            let total = storage.height_rules()[len];
+            if height > total.max_size() {
+                let extra = height - total.max_size();
+                height = total.max_size();
+                y = match align.vert.unwrap_or(Align::Default) {
+                    Align::Default | Align::TL | Align::Stretch => 0,
+                    Align::Centre => extra / 2,
+                    Align::BR => extra,
+                };
+            }
+            let mut line_heights = vec![0; num_lines];
+            SizeRules::solve_seq(&mut line_heights, &line_rules, height);
+
+            // Logical alignment: lines are mirrored later if right-to-left
+            let halign = align.horiz.unwrap_or(Align::Default);
+            let lines = Lines::new(&width_rules, width);
+            for (line, range) in lines.enumerate() {
+                let rules = SizeRules::sum(&width_rules[range.clone()]);
+                let mut x = 0;
+                if width > rules.max_size() {
+                    let extra = width - rules.max_size();
+                    x = match halign {
+                        Align::Default | Align::TL | Align::Stretch => 0,
+                        Align::Centre => extra / 2,
+                        Align::BR => extra,
+                    };
+                }
+
+                for i in range.clone() {
+                    x_offsets.as_mut()[i] = x;
+                    y_offsets.as_mut()[i] = y;
+                    storage.heights()[i] = line_heights[line];
+                    if i + 1 < range.end {
+                        let m1 = width_rules[i].margins_i32().1;
+                        let m0 = width_rules[i + 1].margins_i32().0;
+                        x += storage.widths()[i] + m1.max(m0);
+                    }
+                }
+
+                if line + 1 < num_lines {
+                    let m1 = line_rules[line].margins_i32().1;
+                    let m0 = line_rules[line + 1].margins_i32().0;
+                    y += line_heights[line] + m1.max(m0);
+                }
+            }
+        }
+
+        let mirror = match align.dir.is_rtl() {
+            false => None,
+            true => Some(rect.size.0),
+        };
+
+        FlowSetter {
+            x_offsets,
+            y_offsets,
+            rect,
+            mirror,
+            _s: Default::default(),
+        }
+    }
+}
+
+impl<T: RowTemp, S: GridStorage> RulesSetter for FlowSetter<T, S> {
+    type Storage = S;
+    type ChildInfo = usize;
+
+    fn child_rect(&mut self, storage: &mut Self::Storage, index: Self::ChildInfo) -> Rect {
+        let mut x = self.x_offsets.as_mut()[index];
+        let y = self.y_offsets.as_mut()[index];
+        let size = Size(storage.widths()[index], storage.heights()[index]);
+
+        if let Some(width) = self.mirror {
+            x = width - (x + size.0);
+        }
+        let pos = self.rect.pos + Offset(x, y);
+
+        Rect { pos, size }
+    }
+
+    fn maximal_rect_of(&mut self, storage: &mut Self::Storage, index: Self::ChildInfo) -> Rect {
+        // Bounded by the child's line: exclude the minimum size of other
+        // children on the same line
+        let len = storage.widths().len();
+        let width_rules = storage.width_rules()[0..len].to_vec();
+        let range = Lines::new(&width_rules, self.rect.size.0)
+            .find(|range| range.contains(&index))
+            .unwrap();
+
+        let pre_rules = SizeRules::min_sum(&width_rules[range.start..index]);
+        let m = width_rules[index].margins();
+        let post_rules = SizeRules::min_sum(&width_rules[(index + 1)..range.end]);
+
+        let size1 = pre_rules.min_size() + i32::from(pre_rules.margins().1.max(m.0));
+        let size2 = size1 + post_rules.min_size() + i32::from(post_rules.margins().0.max(m.1));
+
+        // When mirrored, children after index are positioned first
+        let offset = match self.mirror {
+            Some(_) => size2 - size1,
+            None => size1,
+        };
+
+        let y = self.y_offsets.as_mut()[index];
+        let pos = Coord(self.rect.pos.0 + offset, self.rect.pos.1 + y);
+        let size = Size((self.rect.size.0 - size2).max(0), storage.heights()[index]);
+        Rect { pos, size }
+    }
+}
+
+#[test]
+fn lines() {
+    use super::Stretch;
+    let rules = [SizeRules::new(20, 30, (2, 2), Stretch::None); 5];
+    // Ideal widths with margins: 30, 62, 94, ...
+    let lines: Vec<_> = Lines::new(&rules, 70).collect();
+    assert_eq!(lines, vec![0..2, 2..4, 4..5]);
+    let lines: Vec<_> = Lines::new(&rules, 10).collect();
+    assert_eq!(lines.len(), 5);
+    let lines: Vec<_> = Lines::new(&rules, 1000).collect();
+    assert_eq!(lines, vec![0..5]);
+}
+
+#[test]
+fn flow_rtl() {
+    use super::{DynGridStorage, Stretch};
+    use crate::dir::LayoutDir;
+
+    let mut storage = DynGridStorage::default();
+    let width = SizeRules::new(20, 30, (2, 2), Stretch::None);
+    let height = SizeRules::fixed(10, (2, 2));
+
+    let axis = AxisInfo::new(false, None).with_dir(LayoutDir::Rtl);
+    let mut solver = FlowSolver::new(axis, 5, &mut storage);
+    for i in 0..5 {
+        solver.for_child(&mut storage, i, |_| width);
+    }
+    let rules = solver.finish(&mut storage);
+    assert_eq!(rules, SizeRules::new(20, 158, (2, 2), Stretch::None));
+
+    // Height depends on width: two children fit on each line of width 70
+    let axis = AxisInfo::new(true, Some(70)).with_dir(LayoutDir::Rtl);
+    let mut solver = FlowSolver::new(axis, 5, &mut storage);
+    for i in 0..5 {
+        solver.for_child(&mut storage, i, |axis| {
+            assert_eq!(axis.other(), Some(30));
+            height
+        });
+    }
+    let rules = solver.finish(&mut storage);
+    assert_eq!(rules, SizeRules::fixed(34, (2, 2)));
+
+    // Each line is mirrored: the first child is on the right
+    let rect = Rect::new(Coord(5, 0), Size(70, 34));
+    let align = AlignHints::NONE.with_dir(LayoutDir::Rtl);
+    let mut setter = FlowSetter::<Vec<i32>, _>::new(rect, 5, align, &mut storage);
+    let r = |x, y, w| Rect::new(Coord(x, y), Size(w, 10));
+    assert_eq!(setter.child_rect(&mut storage, 0), r(45, 0, 30));
+    assert_eq!(setter.child_rect(&mut storage, 1), r(13, 0, 30));
+    assert_eq!(setter.child_rect(&mut storage, 2), r(45, 12, 30));
+    assert_eq!(setter.child_rect(&mut storage, 3), r(13, 12, 30));
+    assert_eq!(setter.child_rect(&mut storage, 4), r(45, 24, 30));
+
+    // The maximal rect excludes the minimum size of others on the same line
+    assert_eq!(setter.maximal_rect_of(&mut storage, 0), r(27, 0, 46));
+    assert_eq!(setter.maximal_rect_of(&mut storage, 1), r(7, 0, 46));
+    assert_eq!(setter.maximal_rect_of(&mut storage, 4), r(7, 24, 66));
+}
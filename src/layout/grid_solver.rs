@@ -13,6 +13,7 @@ use crate::conv::Conv;
 use crate::geom::{Coord, Offset, Rect, Size};
 
 /// Per-child information
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct GridChildInfo {
     /// Column index (first column when in a span)
     pub col: u32,
@@ -24,6 +25,47 @@ pub struct GridChildInfo {
     pub row_end: u32,
 }
 
+impl GridChildInfo {
+    /// Construct for a single cell at `(col, row)`
+    #[inline]
+    pub fn new(col: u32, row: u32) -> Self {
+        GridChildInfo {
+            col,
+            col_end: col + 1,
+            row,
+            row_end: row + 1,
+        }
+    }
+
+    /// Set the number of columns and rows spanned (inline)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cspan` or `rspan` is zero.
+    #[inline]
+    pub fn with_span(mut self, cspan: u32, rspan: u32) -> Self {
+        assert!(
+            cspan >= 1 && rspan >= 1,
+            "GridChildInfo: span must be at least 1"
+        );
+        self.col_end = self.col + cspan;
+        self.row_end = self.row + rspan;
+        self
+    }
+
+    /// Number of columns spanned
+    #[inline]
+    pub fn cspan(&self) -> u32 {
+        self.col_end - self.col
+    }
+
+    /// Number of rows spanned
+    #[inline]
+    pub fn rspan(&self) -> u32 {
+        self.row_end - self.row
+    }
+}
+
 /// A [`RulesSolver`] for grids supporting cell-spans
 ///
 /// This implementation relies on the caller to provide storage for solver data.
@@ -44,10 +86,25 @@ impl<CSR: Default, RSR: Default, S: GridStorage> GridSolver<CSR, RSR, S> {
     /// - `axis`: `AxisInfo` instance passed into `size_rules`
     /// - `(cols, rows)`: number of columns and rows
     /// - `storage`: reference to persistent storage
-    pub fn new(axis: AxisInfo, (cols, rows): (usize, usize), storage: &mut S) -> Self {
-        let col_spans = CSR::default();
-        let row_spans = RSR::default();
+    pub fn new(axis: AxisInfo, dim: (usize, usize), storage: &mut S) -> Self {
+        let spans = (CSR::default(), RSR::default());
+        Self::new_with_spans(axis, dim, spans, storage)
+    }
+}
 
+impl<CSR, RSR, S: GridStorage> GridSolver<CSR, RSR, S> {
+    /// Construct with explicit span storage
+    ///
+    /// This is the same as [`GridSolver::new`], except that span storage is
+    /// passed explicitly, allowing use of dynamically-sized types (e.g.
+    /// `Vec<(SizeRules, u32, u32)>`). The length of `col_spans` must equal the
+    /// number of children spanning multiple columns; likewise for `row_spans`.
+    pub fn new_with_spans(
+        axis: AxisInfo,
+        (cols, rows): (usize, usize),
+        (col_spans, row_spans): (CSR, RSR),
+        storage: &mut S,
+    ) -> Self {
         storage.set_dims(cols, rows);
 
         let mut solver = GridSolver {
@@ -229,6 +286,8 @@ pub struct GridSetter<RT: RowTemp, CT: RowTemp, S: GridStorage> {
     w_offsets: RT,
     h_offsets: CT,
     pos: Coord,
+    // If right-to-left, the width within which to mirror columns
+    mirror: Option<i32>,
     _s: PhantomData<S>,
 }
 
@@ -239,7 +298,8 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
     ///
     /// -   `rect`: the [`Rect`] within which to position children
     /// -   `(cols, rows)`: number of columns and rows
-    /// -   `align`: alignment hints
+    /// -   `align`: alignment hints; with right-to-left layout direction,
+    ///     columns are mirrored (the first column is on the right)
     /// -   `storage`: access to the solver's storage
     pub fn new(
         rect: Rect,
@@ -255,6 +315,7 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
         storage.set_dims(cols, rows);
 
         if cols > 0 {
+            // Logical alignment: columns are mirrored later if right-to-left
             let align = align.horiz.unwrap_or(Align::Default);
             let (rules, widths) = storage.rules_and_widths();
             let max_size = rules[cols].max_size();
@@ -306,10 +367,16 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
             }
         }
 
+        let mirror = match align.dir.is_rtl() {
+            false => None,
+            true => Some(rect.size.0),
+        };
+
         GridSetter {
             w_offsets,
             h_offsets,
             pos: rect.pos,
+            mirror,
             _s: Default::default(),
         }
     }
@@ -320,9 +387,8 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> RulesSetter for GridSetter<RT, CT
     type ChildInfo = GridChildInfo;
 
     fn child_rect(&mut self, storage: &mut Self::Storage, info: Self::ChildInfo) -> Rect {
-        let x = self.w_offsets.as_mut()[usize::conv(info.col)];
+        let mut x = self.w_offsets.as_mut()[usize::conv(info.col)];
         let y = self.h_offsets.as_mut()[usize::conv(info.row)];
-        let pos = self.pos + Offset(x, y);
 
         let i1 = usize::conv(info.col_end) - 1;
         let w = storage.widths()[i1] + self.w_offsets.as_mut()[i1]
@@ -332,6 +398,11 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> RulesSetter for GridSetter<RT, CT
             - self.h_offsets.as_mut()[usize::conv(info.row)];
         let size = Size(w, h);
 
+        if let Some(width) = self.mirror {
+            x = width - (x + w);
+        }
+        let pos = self.pos + Offset(x, y);
+
         Rect { pos, size }
     }
 
@@ -339,3 +410,34 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> RulesSetter for GridSetter<RT, CT
         unimplemented!()
     }
 }
+
+#[test]
+fn grid_setter_rtl() {
+    use super::DynGridStorage;
+    use crate::dir::LayoutDir;
+
+    let mut storage = DynGridStorage::default();
+    storage.set_dims(2, 1);
+    storage.width_rules()[0] = SizeRules::fixed(10, (0, 0));
+    storage.width_rules()[1] = SizeRules::fixed(20, (0, 0));
+    storage.width_rules()[2] = SizeRules::fixed(30, (0, 0));
+    storage.height_rules()[0] = SizeRules::fixed(10, (0, 0));
+    storage.height_rules()[1] = SizeRules::fixed(10, (0, 0));
+
+    // Columns are mirrored: the first is on the right
+    let rect = Rect::new(Coord(5, 5), Size(30, 10));
+    let align = AlignHints::NONE.with_dir(LayoutDir::Rtl);
+    let mut setter = GridSetter::<Vec<i32>, Vec<i32>, _>::new(rect, (2, 1), align, &mut storage);
+    let r = |x, w| Rect::new(Coord(x, 5), Size(w, 10));
+    let info = GridChildInfo::new(0, 0);
+    assert_eq!(setter.child_rect(&mut storage, info), r(25, 10));
+    let info = GridChildInfo::new(1, 0);
+    assert_eq!(setter.child_rect(&mut storage, info), r(5, 20));
+    let info = GridChildInfo::new(0, 0).with_span(2, 1);
+    assert_eq!(setter.child_rect(&mut storage, info), r(5, 30));
+
+    let align = AlignHints::NONE.with_dir(LayoutDir::Ltr);
+    let mut setter = GridSetter::<Vec<i32>, Vec<i32>, _>::new(rect, (2, 1), align, &mut storage);
+    let info = GridChildInfo::new(0, 0);
+    assert_eq!(setter.child_rect(&mut storage, info), r(5, 10));
+}
@@ -0,0 +1,376 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Headless layout solving
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+use super::{AxisInfo, FrameRules, Margins, SizeRules, SolveCache, Stretch};
+use crate::clipboard::{Clipboard, MemoryClipboard};
+use crate::conv::ConvFloat;
+use crate::data::SharedData;
+use crate::dir::LayoutDir;
+use crate::draw::{SizeHandle, TextClass, ThemeAction, ThemeApi};
+use crate::event::{self, Config, ManagerState, UpdateHandle};
+use crate::geom::{Coord, Rect, Size};
+use crate::text::TextApi;
+use crate::{ShellWindow, Widget, WidgetConfig, WidgetId, WindowId};
+
+/// Widget rects, as solved by [`solve_headless`]
+///
+/// Each widget is identified both by its [`WidgetId`] and by its path: the
+/// sequence of child indices leading to the widget from the root (thus the
+/// root widget has an empty path). Rects are as assigned by
+/// [`crate::Layout::set_rect`], thus are in the coordinate space of the
+/// widget's parent (which differs from window coordinates only within scroll
+/// regions).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutRects {
+    min: Size,
+    ideal: Size,
+    rects: Vec<(WidgetId, Vec<usize>, Rect)>,
+    by_id: HashMap<WidgetId, usize>,
+    by_path: HashMap<Vec<usize>, usize>,
+}
+
+impl LayoutRects {
+    /// The minimum size of the root widget, including margins
+    pub fn min(&self) -> Size {
+        self.min
+    }
+
+    /// The ideal size of the root widget, including margins
+    pub fn ideal(&self) -> Size {
+        self.ideal
+    }
+
+    /// Number of widgets
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    /// True if there are no widgets
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Get the rect of the widget with identifier `id`
+    pub fn get(&self, id: WidgetId) -> Option<Rect> {
+        self.by_id.get(&id).map(|i| self.rects[*i].2)
+    }
+
+    /// Get the rect of the widget at `path`
+    pub fn get_path(&self, path: &[usize]) -> Option<Rect> {
+        self.by_path.get(path).map(|i| self.rects[*i].2)
+    }
+
+    /// Iterate over `(id, path, rect)` for all widgets, parents first
+    pub fn iter(&self) -> impl Iterator<Item = (WidgetId, &[usize], Rect)> {
+        self.rects
+            .iter()
+            .map(|entry| (entry.0, entry.1.as_slice(), entry.2))
+    }
+}
+
+/// Solve layout of a widget tree without a shell
+///
+/// This configures `widget` (assigning [`WidgetId`]s), solves its size rules
+/// and applies layout for a window of the given `size` (with margins internal
+/// to the window, as a shell does), returning the resulting rect of each
+/// widget. Neither a graphics device nor a theme is required, only a
+/// [`SizeHandle`] implementation. This is intended for testing layouts.
+///
+/// The `widget` may be any widget, though is usually a [`crate::Window`]
+/// (e.g. a [`crate::widget::Window`]); pop-ups are not solved. Window resizing
+/// may be tested by calling this method multiple times with different sizes.
+/// Where no theme is available, [`HeadlessSizeHandle`] may be used:
+/// ```
+/// use kas::dir::Right;
+/// use kas::geom::Size;
+/// use kas::layout::{solve_headless, HeadlessSizeHandle};
+/// use kas::widget::{Filler, List};
+///
+/// let mut row = List::<Right, _>::new(vec![Filler::new(), Filler::new()]);
+/// let mut size_handle = HeadlessSizeHandle::new(1.0);
+/// let rects = solve_headless(&mut row, &mut size_handle, Size(200, 100));
+/// assert_eq!(rects.get_path(&[1]).unwrap().size, Size(100, 100));
+/// ```
+///
+/// Event handling is not supported: pop-ups and windows added during
+/// configuration or layout are ignored, the clipboard is an in-memory
+/// clipboard and the layout direction is left-to-right.
+pub fn solve_headless<W: Widget + ?Sized>(
+    widget: &mut W,
+    size_handle: &mut dyn SizeHandle,
+    size: Size,
+) -> LayoutRects {
+    let config = Rc::new(RefCell::new(Config::default()));
+    let mut mgr = ManagerState::new(config);
+    let mut shell = HeadlessShell {
+        size_handle,
+        clipboard: MemoryClipboard::new(),
+        window_id: 0,
+    };
+    mgr.configure(&mut shell, widget);
+
+    let dir = LayoutDir::Ltr;
+    let mut solve_cache =
+        SolveCache::find_constraints(widget.as_widget_mut(), shell.size_handle, dir);
+    let rect = Rect::new(Coord::ZERO, size);
+    mgr.with(&mut shell, |mgr| {
+        solve_cache.apply_rect(widget.as_widget_mut(), mgr, rect, true);
+    });
+
+    let mut rects = vec![];
+    collect_rects(&mut rects, widget.as_widget(), &mut vec![]);
+    let by_id = rects.iter().enumerate().map(|(i, e)| (e.0, i)).collect();
+    let by_path = rects
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.1.clone(), i))
+        .collect();
+    LayoutRects {
+        min: solve_cache.min(true),
+        ideal: solve_cache.ideal(true),
+        rects,
+        by_id,
+        by_path,
+    }
+}
+
+fn collect_rects(
+    rects: &mut Vec<(WidgetId, Vec<usize>, Rect)>,
+    widget: &dyn WidgetConfig,
+    path: &mut Vec<usize>,
+) {
+    rects.push((widget.id(), path.clone(), widget.rect()));
+    for i in 0..widget.num_children() {
+        if let Some(child) = widget.get_child(i) {
+            path.push(i);
+            collect_rects(rects, child, path);
+            path.pop();
+        }
+    }
+}
+
+/// A [`SizeHandle`] with fixed dimensions, for use without a theme
+///
+/// All dimensions are multiples of the scale factor: frames are 4 pixels
+/// wide, margins are 2 (inner) and 4 (outer) pixels and lines are 16 pixels
+/// high. Text is not prepared; instead its size is estimated using an advance
+/// of 8 pixels per `char` (without line-wrapping). Since many text widgets
+/// prepare text in [`crate::Layout::set_rect`], these still require fonts to
+/// be loaded.
+#[derive(Clone, Debug)]
+pub struct HeadlessSizeHandle {
+    scale_factor: f32,
+}
+
+impl HeadlessSizeHandle {
+    /// Construct, with the given scale factor
+    pub fn new(scale_factor: f32) -> Self {
+        HeadlessSizeHandle { scale_factor }
+    }
+
+    fn scale(&self, n: i32) -> i32 {
+        i32::conv_nearest(n as f32 * self.scale_factor)
+    }
+
+    fn scale_u16(&self, n: u16) -> u16 {
+        u16::conv_nearest(n as f32 * self.scale_factor)
+    }
+}
+
+impl SizeHandle for HeadlessSizeHandle {
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn frame(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(self.scale(4), 0, (0, 0))
+    }
+
+    fn menu_frame(&self, vert: bool) -> FrameRules {
+        let size = if vert { 2 } else { 4 };
+        FrameRules::new_sym(self.scale(size), 0, (0, 0))
+    }
+
+    fn separator(&self) -> Size {
+        Size::splat(self.scale(4))
+    }
+
+    fn inner_margin(&self) -> Size {
+        Size::splat(self.scale(2))
+    }
+
+    fn outer_margins(&self) -> Margins {
+        Margins::splat(self.scale_u16(4))
+    }
+
+    fn line_height(&self, _: TextClass) -> i32 {
+        self.scale(16)
+    }
+
+    fn text_bound(
+        &mut self,
+        text: &mut dyn TextApi,
+        class: TextClass,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        let string = text.as_str();
+        let margin = self.scale_u16(2);
+        let margins = (margin, margin);
+        if axis.is_horizontal() {
+            let chars = string.lines().map(|line| line.chars().count()).max();
+            let width = self.scale(8 * chars.unwrap_or(0) as i32);
+            let stretch = match class {
+                TextClass::LabelFixed => Stretch::None,
+                TextClass::Button => Stretch::Filler,
+                _ => Stretch::Low,
+            };
+            SizeRules::new(width, width, margins, stretch)
+        } else {
+            let lines = string.lines().count().max(1) as i32;
+            let min = match class {
+                TextClass::Label => lines,
+                TextClass::EditMulti => 3,
+                _ => 1,
+            };
+            let line_height = self.line_height(class);
+            let (min, ideal) = (min * line_height, lines.max(min) * line_height);
+            SizeRules::new(min, ideal, margins, Stretch::None)
+        }
+    }
+
+    fn edit_marker_width(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn button_surround(&self, _vert: bool) -> FrameRules {
+        let outer = self.scale_u16(4);
+        FrameRules::new_sym(self.scale(4), self.scale(2), (outer, outer))
+    }
+
+    fn edit_surround(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(self.scale(4), self.scale(2), (0, 0))
+    }
+
+    fn checkbox(&self) -> Size {
+        Size::splat(self.scale(16))
+    }
+
+    fn radiobox(&self) -> Size {
+        self.checkbox()
+    }
+
+    fn scrollbar(&self) -> (Size, i32) {
+        let size = Size(self.scale(16), self.scale(8));
+        (size, 3 * size.0)
+    }
+
+    fn slider(&self) -> (Size, i32) {
+        let size = Size(self.scale(12), self.scale(24));
+        (size, 5 * size.0)
+    }
+
+    fn progress_bar(&self) -> Size {
+        Size(self.scale(128), self.scale(8))
+    }
+}
+
+/// A minimal [`ShellWindow`] providing only a [`SizeHandle`]
+struct HeadlessShell<'a> {
+    size_handle: &'a mut dyn SizeHandle,
+    clipboard: MemoryClipboard,
+    window_id: u32,
+}
+
+impl<'a> HeadlessShell<'a> {
+    fn next_window_id(&mut self) -> WindowId {
+        self.window_id += 1;
+        WindowId::new(NonZeroU32::new(self.window_id).unwrap())
+    }
+}
+
+impl<'a> ShellWindow for HeadlessShell<'a> {
+    fn add_popup(&mut self, _: crate::Popup) -> WindowId {
+        self.next_window_id()
+    }
+
+    fn add_window(&mut self, _: Box<dyn crate::Window>) -> WindowId {
+        self.next_window_id()
+    }
+
+    fn close_window(&mut self, _: WindowId) {}
+
+    fn update_shared_data(&mut self, _: UpdateHandle, _: Rc<dyn SharedData>) {}
+
+    fn trigger_update(&mut self, _: UpdateHandle, _: u64) {}
+
+    fn clipboard(&mut self) -> &mut dyn Clipboard {
+        &mut self.clipboard
+    }
+
+    fn get_primary(&mut self) -> Option<String> {
+        None
+    }
+
+    fn set_primary(&mut self, _: String) {}
+
+    fn adjust_theme(&mut self, _: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {}
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        f(self.size_handle);
+    }
+
+    fn set_cursor_icon(&mut self, _: event::CursorIcon) {}
+}
+
+#[test]
+fn solve_row() {
+    use crate::dir::Right;
+    use crate::event::VoidMsg;
+    use crate::widget::{BoxList, Filler, ReserveP};
+    use crate::WidgetCore;
+
+    fn fixed(_: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        SizeRules::fixed(if axis.is_horizontal() { 50 } else { 20 }, (0, 0))
+    }
+    let fixed: ReserveP<Filler> = ReserveP::new(Filler::with(Stretch::None), fixed);
+
+    let mut row = BoxList::<Right, VoidMsg>::new(vec![Box::new(fixed), Box::new(Filler::new())]);
+    let mut size_handle = HeadlessSizeHandle::new(1.0);
+
+    let rects = solve_headless(&mut row, &mut size_handle, Size(200, 40));
+    assert_eq!(rects.len(), 4);
+    assert_eq!(rects.min(), Size(50, 20));
+    assert_eq!(
+        rects.get_path(&[]),
+        Some(Rect::new(Coord(0, 0), Size(200, 40)))
+    );
+    assert_eq!(
+        rects.get_path(&[0]),
+        Some(Rect::new(Coord(0, 0), Size(50, 40)))
+    );
+    assert_eq!(
+        rects.get_path(&[1]),
+        Some(Rect::new(Coord(50, 0), Size(150, 40)))
+    );
+    let id = row.id();
+    assert_eq!(rects.get(id), rects.get_path(&[]));
+
+    let rects = solve_headless(&mut row, &mut size_handle, Size(400, 40));
+    assert_eq!(
+        rects.get_path(&[0]),
+        Some(Rect::new(Coord(0, 0), Size(50, 40)))
+    );
+    assert_eq!(
+        rects.get_path(&[1]),
+        Some(Rect::new(Coord(50, 0), Size(350, 40)))
+    );
+}
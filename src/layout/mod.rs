@@ -14,6 +14,7 @@
 //! and [`SizeRules::solve_seq`], the "muscle" of the layout engine.
 //!
 //! [`AxisInfo`], [`Margins`] and [`Stretch`] are auxilliary data types.
+//! [`Constraints`] may be used to override a widget's size.
 //!
 //! ## Layout engines
 //!
@@ -29,12 +30,27 @@
 //! -   [`GridSolver`] and [`GridSetter`] set out children assigned to grid
 //!     cells with optional cell-spans. This is the most powerful and flexible
 //!     layout engine.
+//! -   [`FlowSolver`] and [`FlowSetter`] set out a row of children which
+//!     wraps onto new lines as required (using [`GridStorage`]).
 //!
 //! [`RowPositionSolver`] may be used with widgets set out by [`RowSetter`]
 //! to quickly locate children from a `coord` or `rect`.
+//!
+//! ## Debugging
+//!
+//! [`DebugOverlay`] may be used by shells to draw widget rects, margins and
+//! stretch policies over a window.
+//!
+//! [`solve_headless`] solves layout for a widget tree without a shell (given a
+//! [`crate::draw::SizeHandle`] implementation such as [`HeadlessSizeHandle`]),
+//! allowing layouts to be tested.
 
 mod align;
+mod constraints;
+mod debug;
+mod flow_solver;
 mod grid_solver;
+mod headless;
 mod row_solver;
 mod single_solver;
 mod size_rules;
@@ -42,10 +58,14 @@ mod size_types;
 mod sizer;
 mod storage;
 
-use kas::dir::{Direction, Directional};
+use kas::dir::{Direction, Directional, LayoutDir};
 
 pub use align::{Align, AlignHints, CompleteAlignment};
+pub use constraints::Constraints;
+pub use debug::DebugOverlay;
+pub use flow_solver::{FlowSetter, FlowSolver};
 pub use grid_solver::{GridChildInfo, GridSetter, GridSolver};
+pub use headless::{solve_headless, HeadlessSizeHandle, LayoutRects};
 pub use row_solver::{RowPositionSolver, RowSetter, RowSolver};
 pub use single_solver::{SingleSetter, SingleSolver};
 pub use size_rules::SizeRules;
@@ -58,12 +78,14 @@ pub use storage::{
 
 /// Information on which axis is being resized
 ///
-/// Also conveys the size of the other axis, if fixed.
+/// Also conveys the size of the other axis, if fixed, and the horizontal
+/// layout direction.
 #[derive(Copy, Clone, Debug)]
 pub struct AxisInfo {
     vertical: bool,
     has_fixed: bool,
     other_axis: i32,
+    dir: LayoutDir,
 }
 
 impl AxisInfo {
@@ -76,9 +98,25 @@ impl AxisInfo {
             vertical,
             has_fixed: fixed.is_some(),
             other_axis: fixed.unwrap_or(0),
+            dir: LayoutDir::Ltr,
         }
     }
 
+    /// Set the layout direction (inline)
+    ///
+    /// This method is *usually* not required by user code.
+    #[inline]
+    pub fn with_dir(mut self, dir: LayoutDir) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    /// Get the layout direction
+    #[inline]
+    pub fn dir(&self) -> LayoutDir {
+        self.dir
+    }
+
     /// True if the current axis is vertical
     #[inline]
     pub fn is_vertical(&self) -> bool {
@@ -10,7 +10,7 @@ use std::ops::Range;
 
 use super::{Align, AlignHints, AxisInfo, SizeRules};
 use super::{RowStorage, RowTemp, RulesSetter, RulesSolver};
-use crate::dir::{Direction, Directional};
+use crate::dir::{Direction, Directional, LayoutDir};
 use crate::geom::{Coord, Rect};
 use crate::Widget;
 
@@ -49,7 +49,7 @@ impl<S: RowStorage> RowSolver<S> {
         RowSolver {
             axis,
             axis_is_vertical,
-            axis_is_reversed: dir.is_reversed(),
+            axis_is_reversed: axis.dir().is_reversed(dir),
             rules: None,
             _s: Default::default(),
         }
@@ -112,6 +112,7 @@ pub struct RowSetter<D, T: RowTemp, S: RowStorage> {
     rect: Rect,
     offsets: T,
     direction: D,
+    is_reversed: bool,
     _s: PhantomData<S>,
 }
 
@@ -122,7 +123,7 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
     ///
     /// -   `rect`: the [`Rect`] within which to position children
     /// - `(direction, len)`: direction and number of items
-    /// -   `align`: alignment hints
+    /// -   `align`: alignment hints; these also determine the layout direction
     /// -   `storage`: access to the solver's storage
     pub fn new(
         mut rect: Rect,
@@ -139,9 +140,9 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
             let mut width = if is_horiz { rect.size.0 } else { rect.size.1 };
             let (rules, widths) = storage.rules_and_widths();
             let max_size = rules[len].max_size();
-            let align = if is_horiz { align.horiz } else { align.vert };
-            let align = align.unwrap_or(Align::Default);
-            if rect.size.0 > max_size {
+            let (halign, valign) = align.unwrap_or(Align::Default, Align::Default);
+            let align = if is_horiz { halign } else { valign };
+            if width > max_size {
                 let extra = width - max_size;
                 width = max_size;
                 let offset = match align {
@@ -158,11 +159,13 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
             SizeRules::solve_seq_total(widths, rules, width);
         }
 
+        let is_reversed = align.dir.is_reversed(direction);
         let _s = Default::default();
         let mut row = RowSetter {
             rect,
             offsets,
             direction,
+            is_reversed,
             _s,
         };
         row.update_offsets(storage);
@@ -177,16 +180,23 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
     /// using this `RowSetter` to calculate child positions.
     ///
     /// It is also assumed that alignment is [`Align::Stretch`].
-    pub fn new_unsolved(rect: Rect, (direction, len): (D, usize), storage: &mut S) -> Self {
+    pub fn new_unsolved(
+        rect: Rect,
+        (direction, len): (D, usize),
+        dir: LayoutDir,
+        storage: &mut S,
+    ) -> Self {
         let mut offsets = T::default();
         offsets.set_len(len);
         storage.set_dim(len);
 
+        let is_reversed = dir.is_reversed(direction);
         let _s = Default::default();
         RowSetter {
             rect,
             offsets,
             direction,
+            is_reversed,
             _s,
         }
     }
@@ -204,7 +214,7 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
             self.rect.pos.1
         };
 
-        if self.direction.is_reversed() {
+        if self.is_reversed {
             offsets[len - 1] = pos;
             for i in (0..(len - 1)).rev() {
                 let i1 = i + 1;
@@ -256,12 +266,19 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RulesSetter for RowSetter<D, T,
         let size1 = pre_rules.min_size() + i32::from(pre_rules.margins().1.max(m.0));
         let size2 = size1 + post_rules.min_size() + i32::from(post_rules.margins().0.max(m.1));
 
+        // When reversed, children after index are positioned first
+        let offset = if self.is_reversed {
+            size2 - size1
+        } else {
+            size1
+        };
+
         let mut rect = self.rect;
         if self.direction.is_horizontal() {
-            rect.pos.0 = self.rect.pos.0 + size1;
+            rect.pos.0 = self.rect.pos.0 + offset;
             rect.size.0 = (self.rect.size.0 - size2).max(0);
         } else {
-            rect.pos.1 = self.rect.pos.1 + size1;
+            rect.pos.1 = self.rect.pos.1 + offset;
             rect.size.1 = (self.rect.size.1 - size2).max(0);
         }
         rect
@@ -274,6 +291,9 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RulesSetter for RowSetter<D, T,
 /// This is only applicable where child widgets are contained in a slice of type
 /// `W: Widget` (which may be `Box<dyn Widget>`). In other cases, the naive
 /// implementation (test all items) must be used.
+///
+/// The direction used must be the *physical* direction in which children were
+/// set out; see [`LayoutDir::resolve`].
 #[derive(Clone, Copy, Debug)]
 pub struct RowPositionSolver<D: Directional> {
     direction: D,
@@ -359,3 +379,34 @@ impl<D: Directional> RowPositionSolver<D> {
         }
     }
 }
+
+#[test]
+fn row_setter_rtl() {
+    use super::{DynRowStorage, Stretch};
+    use crate::dir::Right;
+    use crate::geom::Size;
+
+    let mut storage = DynRowStorage::default();
+    let axis = AxisInfo::new(false, None).with_dir(LayoutDir::Rtl);
+    let mut solver = RowSolver::new(axis, (Right, 3), &mut storage);
+    solver.for_child(&mut storage, 0, |_| SizeRules::fixed(10, (0, 0)));
+    solver.for_child(&mut storage, 1, |_| {
+        SizeRules::new(20, 20, (0, 0), Stretch::Low)
+    });
+    solver.for_child(&mut storage, 2, |_| SizeRules::fixed(30, (0, 0)));
+    solver.finish(&mut storage);
+
+    // Children are mirrored: the first is on the right
+    let rect = Rect::new(Coord(5, 0), Size(100, 10));
+    let align = AlignHints::NONE.with_dir(LayoutDir::Rtl);
+    let mut setter = RowSetter::<_, Vec<i32>, _>::new(rect, (Right, 3), align, &mut storage);
+    let r = |x, w| Rect::new(Coord(x, 0), Size(w, 10));
+    assert_eq!(setter.child_rect(&mut storage, 0), r(95, 10));
+    assert_eq!(setter.child_rect(&mut storage, 1), r(35, 60));
+    assert_eq!(setter.child_rect(&mut storage, 2), r(5, 30));
+
+    // The maximal rect excludes the minimum size of children on either side
+    assert_eq!(setter.maximal_rect_of(&mut storage, 0), r(55, 50));
+    assert_eq!(setter.maximal_rect_of(&mut storage, 1), r(35, 60));
+    assert_eq!(setter.maximal_rect_of(&mut storage, 2), r(5, 70));
+}
@@ -9,6 +9,7 @@ use log::trace;
 use std::fmt;
 
 use super::{AlignHints, AxisInfo, Margins, SizeRules};
+use crate::dir::LayoutDir;
 use crate::draw::SizeHandle;
 use crate::event::Manager;
 use crate::geom::{Rect, Size};
@@ -74,15 +75,17 @@ pub trait RulesSetter {
 /// to do this if `set_rect` is also never called and the widget never drawn.)
 ///
 /// Parameters `x_size` and `y_size` should be passed where this dimension is
-/// fixed and are used e.g. for text wrapping.
+/// fixed and are used e.g. for text wrapping. The layout direction `dir` should
+/// match that later passed to `set_rect` (via [`AlignHints::dir`]).
 pub fn solve_size_rules<W: Widget>(
     widget: &mut W,
     size_handle: &mut dyn SizeHandle,
     x_size: Option<i32>,
     y_size: Option<i32>,
+    dir: LayoutDir,
 ) {
-    widget.size_rules(size_handle, AxisInfo::new(false, y_size));
-    widget.size_rules(size_handle, AxisInfo::new(true, x_size));
+    widget.size_rules(size_handle, AxisInfo::new(false, y_size).with_dir(dir));
+    widget.size_rules(size_handle, AxisInfo::new(true, x_size).with_dir(dir));
 }
 
 /// Size solver
@@ -103,6 +106,7 @@ pub struct SolveCache {
     margins: Margins,
     refresh_rules: bool,
     last_width: i32,
+    dir: LayoutDir,
 }
 
 impl SolveCache {
@@ -134,12 +138,17 @@ impl SolveCache {
     }
 
     /// Calculate required size of widget
+    ///
+    /// The layout direction `dir` is usually [`Manager::layout_dir`].
     pub fn find_constraints(
         widget: &mut dyn WidgetConfig,
         size_handle: &mut dyn SizeHandle,
+        dir: LayoutDir,
     ) -> Self {
-        let w = widget.size_rules(size_handle, AxisInfo::new(false, None));
-        let h = widget.size_rules(size_handle, AxisInfo::new(true, Some(w.ideal_size())));
+        let axis = AxisInfo::new(false, None).with_dir(dir);
+        let w = widget.size_rules(size_handle, axis);
+        let axis = AxisInfo::new(true, Some(w.ideal_size())).with_dir(dir);
+        let h = widget.size_rules(size_handle, axis);
 
         let min = Size(w.min_size(), h.min_size());
         let ideal = Size(w.ideal_size(), h.ideal_size());
@@ -158,6 +167,7 @@ impl SolveCache {
             margins,
             refresh_rules,
             last_width,
+            dir,
         }
     }
 
@@ -172,14 +182,15 @@ impl SolveCache {
 
     /// Apply layout solution to a widget
     ///
-    /// The widget's layout is solved for the given `rect` and assigned.
+    /// The widget's layout is solved for the given `rect` and assigned, using
+    /// the layout direction [`Manager::layout_dir`].
     /// If `inner_margin` is true, margins are internal to this `rect`; if not,
     /// the caller is responsible for handling margins.
     ///
     /// If [`SolveCache::invalidate_rule_cache`] was called since rules were
-    /// last calculated then this method will recalculate all rules; otherwise
-    /// it will only do so if necessary (when dimensions do not match those
-    /// last used).
+    /// last calculated or the layout direction has changed then this method
+    /// will recalculate all rules; otherwise it will only do so if necessary
+    /// (when dimensions do not match those last used).
     pub fn apply_rect(
         &mut self,
         widget: &mut dyn WidgetConfig,
@@ -194,16 +205,23 @@ impl SolveCache {
 
         // We call size_rules not because we want the result, but because our
         // spec requires that we do so before calling set_rect.
+        let dir = mgr.layout_dir();
+        if dir != self.dir {
+            self.dir = dir;
+            self.refresh_rules = true;
+        }
         if self.refresh_rules || width != self.last_width {
             mgr.size_handle(|size_handle| {
                 if self.refresh_rules {
-                    let w = widget.size_rules(size_handle, AxisInfo::new(false, None));
+                    let axis = AxisInfo::new(false, None).with_dir(dir);
+                    let w = widget.size_rules(size_handle, axis);
                     self.min.0 = w.min_size();
                     self.ideal.0 = w.ideal_size();
                     self.margins.horiz = w.margins();
                 }
 
-                let h = widget.size_rules(size_handle, AxisInfo::new(true, Some(width)));
+                let axis = AxisInfo::new(true, Some(width)).with_dir(dir);
+                let h = widget.size_rules(size_handle, axis);
                 self.min.1 = h.min_size();
                 self.ideal.1 = h.ideal_size();
                 self.margins.vert = h.margins();
@@ -216,7 +234,7 @@ impl SolveCache {
             rect.size.0 = width;
             rect.size.1 -= self.margins.sum_vert();
         }
-        widget.set_rect(mgr, rect, AlignHints::NONE);
+        widget.set_rect(mgr, rect, AlignHints::NONE.with_dir(dir));
 
         trace!(
             "layout::solve_and_set for size={:?} has hierarchy:{}",
@@ -36,13 +36,16 @@ mod future;
 mod toolkit;
 
 // public implementations:
+pub mod anim;
 pub mod class;
+pub mod clipboard;
 pub mod conv;
 pub mod data;
 pub mod dir;
 pub mod draw;
 pub mod event;
 pub mod geom;
+pub mod i18n;
 pub mod layout;
 pub mod prelude;
 pub mod text;
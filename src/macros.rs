@@ -105,6 +105,8 @@
 //! -   `left` — reversed row
 //! -   `grid` — child widgets are arranged in a grid; position is specified
 //!     via parameters to the `#[widget]` attribute on child fields
+//! -   `flow` — child widgets are arranged in a row, left-to-right, wrapping
+//!     onto new lines as required
 //!
 //! Optionally, a second parameter of form `area=FIELD` is allowed (e.g.
 //! `#[layout(row, area=checkbox)]`). `FIELD` must identify a child widget.
@@ -133,6 +135,13 @@
 //! -   `halign = ...` — one of `default`, `left`, `centre`, `center`, `right`, `stretch`
 //! -   `valign = ...` — one of `default`, `top`, `centre`, `center`, `bottom`, `stretch`
 //!
+//! Size constraints may be applied to children (see [`Constraints`]). Sizes are
+//! in logical pixels; the aspect ratio is width / height:
+//!
+//! -   `min_width = ...`, `max_width = ...` — constrain the child's width
+//! -   `min_height = ...`, `max_height = ...` — constrain the child's height
+//! -   `aspect = ...` — fix the child's aspect ratio
+//!
 //! **Layout data storage**
 //!
 //! When deriving [`Layout`], data storage is required (exception: layout
@@ -415,7 +424,7 @@
 #[allow(unused)]
 use crate::{
     event::{Handler, SendEvent},
-    layout::AlignHints,
+    layout::{AlignHints, Constraints},
     CoreData, Layout, LayoutData, Widget, WidgetChildren, WidgetConfig, WidgetCore, WidgetId,
 };
 
@@ -13,6 +13,9 @@
 
 pub use kas_text::*;
 
+mod elide;
+pub use elide::{Elide, ElidedText};
+
 mod selection;
 pub use selection::SelectionHelper;
 
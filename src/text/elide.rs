@@ -0,0 +1,270 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Text elision
+
+use super::{format::FormattableText, Environment, Text, TextApi, TextApiExt, Vec2};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+const ELLIPSIS: &str = "…";
+
+/// Text elision mode
+///
+/// Elision shortens text which does not fit the available width by replacing
+/// some part with an ellipsis ("…"). This is intended for single-line text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Elide {
+    /// No elision: text may be clipped or overflow
+    None,
+    /// Remove text from the start: "…of text"
+    Start,
+    /// Remove text from the middle: "A li…text"
+    Middle,
+    /// Remove text from the end: "A line o…"
+    End,
+}
+
+impl Default for Elide {
+    fn default() -> Self {
+        Elide::None
+    }
+}
+
+/// Elided text
+///
+/// This is a helper for widgets supporting [`Elide`]. It stores a shortened
+/// copy of some text, used for display when the original does not fit.
+/// The elided copy is unformatted.
+#[derive(Clone, Debug, Default)]
+pub struct ElidedText {
+    mode: Elide,
+    text: Option<Text<String>>,
+    // Range of the original text replaced by the ellipsis
+    hidden: Range<usize>,
+}
+
+impl ElidedText {
+    /// Construct with the given `mode`
+    #[inline]
+    pub fn new(mode: Elide) -> Self {
+        ElidedText {
+            mode,
+            text: None,
+            hidden: 0..0,
+        }
+    }
+
+    /// Get the elision mode
+    #[inline]
+    pub fn mode(&self) -> Elide {
+        self.mode
+    }
+
+    /// Set the elision mode
+    ///
+    /// This clears any elided text; call [`ElidedText::update`] afterwards.
+    #[inline]
+    pub fn set_mode(&mut self, mode: Elide) {
+        self.mode = mode;
+        self.text = None;
+        self.hidden = 0..0;
+    }
+
+    /// True if the text is currently elided
+    #[inline]
+    pub fn is_elided(&self) -> bool {
+        self.text.is_some()
+    }
+
+    /// Get the elided text, if elided
+    ///
+    /// When this is `Some`, it should be drawn instead of the original text.
+    #[inline]
+    pub fn text(&self) -> Option<&Text<String>> {
+        self.text.as_ref()
+    }
+
+    /// Clamp a `range` of the original text to the visible part
+    ///
+    /// When elided, `range` may include text hidden by the ellipsis. The
+    /// result is restricted to the visible text before the ellipsis or, if
+    /// `range` starts within or after the hidden text, that after it.
+    pub fn clamp_range(&self, range: Range<usize>) -> Range<usize> {
+        let hidden = &self.hidden;
+        if range.start < hidden.start {
+            range.start..range.end.min(hidden.start)
+        } else {
+            let start = range.start.max(hidden.end);
+            start..range.end.max(start)
+        }
+    }
+
+    /// Map an index of the elided text to the original text
+    ///
+    /// An index within the ellipsis maps to the start of the hidden text.
+    /// If not elided, `index` is returned unchanged.
+    pub fn to_original(&self, index: usize) -> usize {
+        let hidden = &self.hidden;
+        if self.text.is_none() || index <= hidden.start {
+            index
+        } else {
+            let after = hidden.start + ELLIPSIS.len();
+            index.max(after) - after + hidden.end
+        }
+    }
+
+    /// Map a `range` of the original text to the elided text
+    ///
+    /// The `range` is first clamped to the visible part (see
+    /// [`ElidedText::clamp_range`]). If not elided, `range` is returned
+    /// unchanged.
+    pub fn to_elided_range(&self, range: Range<usize>) -> Range<usize> {
+        if self.text.is_none() {
+            return range;
+        }
+        let hidden = &self.hidden;
+        let map = |index: usize| match index <= hidden.start {
+            true => index,
+            false => index - hidden.end + hidden.start + ELLIPSIS.len(),
+        };
+        let range = self.clamp_range(range);
+        map(range.start)..map(range.end)
+    }
+
+    /// Minimum width required in elision mode
+    ///
+    /// This is the width of the ellipsis, using the environment of `text`.
+    /// If the mode is [`Elide::None`], this returns the width of `text`.
+    pub fn min_width<T: FormattableText>(&self, text: &Text<T>) -> f32 {
+        match self.mode {
+            Elide::None => measure(text.env(), text.as_str()),
+            _ => measure(text.env(), ELLIPSIS),
+        }
+    }
+
+    /// Update elision for `text`
+    ///
+    /// This must be called after the environment of `text` is updated (e.g.
+    /// from `Layout::set_rect`) and after the text itself is changed. The
+    /// elided copy uses the same environment as `text`; it is shortened on
+    /// grapheme boundaries until it fits within `width`.
+    pub fn update<T: FormattableText>(&mut self, text: &Text<T>, width: f32) {
+        self.text = None;
+        self.hidden = 0..0;
+        if self.mode == Elide::None {
+            return;
+        }
+
+        let env = text.env();
+        let full = text.as_str();
+        let mut line = Text::new(single_line(env), full.to_string());
+        if line.prepare().map(|size| size.0).unwrap_or(0.0) <= width {
+            return;
+        }
+
+        // The full line is prepared once; the width of each candidate is
+        // estimated from glyph positions within it.
+        let x = |index: usize| {
+            line.text_glyph_pos(index)
+                .next_back()
+                .map(|marker| marker.pos.0)
+                .unwrap_or(0.0)
+        };
+        let span = |range: Range<usize>| (x(range.end) - x(range.start)).abs();
+        let ellipsis = measure(env, ELLIPSIS);
+
+        let mut bounds: Vec<usize> = full.grapheme_indices(true).map(|(i, _)| i).collect();
+        bounds.push(full.len());
+        let count = bounds.len() - 1;
+        let mode = self.mode;
+        // Range of `full` hidden when keeping `n` graphemes
+        let hidden = |n: usize| -> Range<usize> {
+            let (head, tail) = match mode {
+                Elide::None => unreachable!(),
+                Elide::Start => (0, n),
+                Elide::Middle => ((n + 1) / 2, n / 2),
+                Elide::End => (n, 0),
+            };
+            let head = full[..bounds[head]].trim_end().len();
+            let tail = full.len() - full[bounds[count - tail]..].trim_start().len();
+            head..tail
+        };
+        let fits = |n: usize| {
+            let hidden = hidden(n);
+            span(0..hidden.start) + ellipsis + span(hidden.end..full.len()) <= width
+        };
+
+        // Find the largest number of graphemes which fits
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if fits(mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        let hidden = hidden(lo);
+        let string = format!(
+            "{}{}{}",
+            &full[..hidden.start],
+            ELLIPSIS,
+            &full[hidden.end..]
+        );
+        let mut elided = Text::new(env.clone(), string);
+        let _ = elided.prepare();
+        self.text = Some(elided);
+        self.hidden = hidden;
+    }
+}
+
+/// Copy `env`, configured for a single unbounded line
+fn single_line(env: &Environment) -> Environment {
+    let mut env = env.clone();
+    env.set_wrap(false);
+    env.set_bounds(Vec2::INFINITY);
+    env
+}
+
+/// Measure the width of `text` on a single line
+fn measure(env: &Environment, text: &str) -> f32 {
+    let mut text = Text::new(single_line(env), text.to_string());
+    text.prepare().map(|size| size.0).unwrap_or(0.0)
+}
+
+#[test]
+fn clamp_range() {
+    let mut elided = ElidedText::new(Elide::Middle);
+    assert_eq!(elided.clamp_range(2..10), 2..10);
+
+    elided.hidden = 4..8;
+    assert_eq!(elided.clamp_range(0..3), 0..3);
+    assert_eq!(elided.clamp_range(2..10), 2..4);
+    assert_eq!(elided.clamp_range(5..7), 8..8);
+    assert_eq!(elided.clamp_range(5..10), 8..10);
+    assert_eq!(elided.clamp_range(9..10), 9..10);
+}
+
+#[test]
+fn map_indices() {
+    let mut elided = ElidedText::new(Elide::Middle);
+    assert_eq!(elided.to_original(5), 5);
+    assert_eq!(elided.to_elided_range(2..10), 2..10);
+
+    // "abcdefghij" elided as "abcd…ij"
+    elided.text = Some(Text::new(Default::default(), "abcd…ij".to_string()));
+    elided.hidden = 4..8;
+    assert_eq!(elided.to_original(3), 3);
+    assert_eq!(elided.to_original(4), 4);
+    assert_eq!(elided.to_original(5), 8);
+    assert_eq!(elided.to_original(7), 8);
+    assert_eq!(elided.to_original(8), 9);
+    assert_eq!(elided.to_elided_range(0..3), 0..3);
+    assert_eq!(elided.to_elided_range(2..10), 2..4);
+    assert_eq!(elided.to_elided_range(5..10), 7..9);
+    assert_eq!(elided.to_elided_range(9..10), 8..9);
+}
@@ -15,10 +15,13 @@
 use std::num::NonZeroU32;
 use std::rc::Rc;
 
+use crate::clipboard::Clipboard;
 use crate::data::SharedData;
+use crate::dir::LayoutDir;
 use crate::draw::{SizeHandle, ThemeAction, ThemeApi};
 use crate::event;
 use crate::event::UpdateHandle;
+use crate::i18n::Catalogue;
 
 /// Identifier for a window or pop-up
 ///
@@ -122,14 +125,50 @@ pub trait ShellWindow {
     /// windows, will receive an update.
     fn trigger_update(&mut self, handle: UpdateHandle, payload: u64);
 
-    /// Attempt to get clipboard contents
+    /// Access the clipboard
     ///
-    /// In case of failure, paste actions will simply fail. The implementation
-    /// may wish to log an appropriate warning message.
-    fn get_clipboard(&mut self) -> Option<String>;
+    /// Shells without system clipboard support may return a
+    /// [`MemoryClipboard`](crate::clipboard::MemoryClipboard).
+    fn clipboard(&mut self) -> &mut dyn Clipboard;
 
-    /// Attempt to set clipboard contents
-    fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>);
+    /// Attempt to get the primary selection
+    ///
+    /// The primary selection is an X11 and Wayland feature: selected text is
+    /// made available for pasting, usually via middle-click. Where this is
+    /// not supported, the implementation may emulate it within the
+    /// application or simply return `None`.
+    fn get_primary(&mut self) -> Option<String>;
+
+    /// Attempt to set the primary selection
+    fn set_primary(&mut self, content: String);
+
+    /// Get the active message catalogue, if any
+    ///
+    /// The default implementation returns `None`.
+    fn catalogue(&self) -> Option<Rc<dyn Catalogue>> {
+        None
+    }
+
+    /// Set the active message catalogue
+    ///
+    /// The shell must re-configure (and thus resize) all windows, allowing
+    /// localised text to be updated.
+    ///
+    /// The default implementation does nothing; shells not supporting
+    /// localisation need not implement this or [`ShellWindow::catalogue`].
+    fn set_catalogue(&mut self, catalogue: Option<Rc<dyn Catalogue>>) {
+        let _ = catalogue;
+    }
+
+    /// Get the application's layout direction
+    ///
+    /// The default implementation infers this from the language of the
+    /// active catalogue (left-to-right if there is none).
+    fn layout_dir(&self) -> LayoutDir {
+        self.catalogue()
+            .map(|cat| LayoutDir::from_language(cat.language()))
+            .unwrap_or_default()
+    }
 
     /// Adjust the theme
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction);
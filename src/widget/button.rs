@@ -10,9 +10,13 @@ use std::rc::Rc;
 
 use kas::draw::TextClass;
 use kas::event::{self, VirtualKeyCode, VirtualKeyCodes};
+use kas::geom::Vec2;
 use kas::prelude::*;
+use kas::text::{Elide, ElidedText};
 
 /// A push-button with a text label
+///
+/// The label optionally supports elision; see [`TextButton::with_elide`].
 #[derive(Clone, Widget)]
 #[handler(handle=noauto)]
 #[widget(config=noauto)]
@@ -23,6 +27,7 @@ pub struct TextButton<M: 'static> {
     frame_size: Size,
     // label_rect: Rect,
     label: Text<AccelString>,
+    elide: ElidedText,
     on_push: Option<Rc<dyn Fn(&mut Manager) -> Option<M>>>,
 }
 
@@ -53,7 +58,17 @@ impl<M: 'static> WidgetConfig for TextButton<M> {
 impl<M: 'static> Layout for TextButton<M> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let frame_rules = size_handle.button_surround(axis.is_vertical());
-        let content_rules = size_handle.text_bound(&mut self.label, TextClass::Button, axis);
+        let mut content_rules = size_handle.text_bound(&mut self.label, TextClass::Button, axis);
+        if axis.is_horizontal() && self.elide.mode() != Elide::None {
+            // Allow shrinking down to the ellipsis
+            let min = i32::conv_ceil(self.elide.min_width(&self.label));
+            content_rules = SizeRules::new(
+                min.min(content_rules.min_size()),
+                content_rules.ideal_size(),
+                content_rules.margins(),
+                content_rules.stretch(),
+            );
+        }
 
         let (rules, _offset, size) = frame_rules.surround(content_rules);
         self.frame_size.set_component(axis, size);
@@ -71,12 +86,17 @@ impl<M: 'static> Layout for TextButton<M> {
             env.set_bounds(rect.size.into());
             env.set_align(align.unwrap_or(Align::Centre, Align::Centre));
         });
+        self.update_elide();
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         draw_handle.button(self.core.rect, self.input_state(mgr, disabled));
-        let state = mgr.show_accel_labels();
-        draw_handle.text_accel(self.core.rect.pos, &self.label, state, TextClass::Button);
+        if let Some(text) = self.elide.text() {
+            draw_handle.text(self.core.rect.pos, text, TextClass::Button);
+        } else {
+            let state = mgr.show_accel_labels();
+            draw_handle.text_accel(self.core.rect.pos, &self.label, state, TextClass::Button);
+        }
     }
 }
 
@@ -92,6 +112,7 @@ impl TextButton<VoidMsg> {
             frame_size: Default::default(),
             // label_rect: Default::default(),
             label: text,
+            elide: Default::default(),
             on_push: None,
         }
     }
@@ -111,6 +132,7 @@ impl TextButton<VoidMsg> {
             keys1: self.keys1,
             frame_size: self.frame_size,
             label: self.label,
+            elide: self.elide,
             on_push: Some(Rc::new(f)),
         }
     }
@@ -151,6 +173,31 @@ impl<M: 'static> TextButton<M> {
         self.keys1.extend_from_slice(keys);
         self
     }
+
+    /// Set the elision mode (inline)
+    ///
+    /// If the button is assigned less width than its label requires, the
+    /// label is shortened on grapheme boundaries and an ellipsis ("…")
+    /// inserted. Accelerator keys are not underlined in elided labels.
+    ///
+    /// The full label remains available via [`HasStr::get_str`].
+    /// Default: [`Elide::None`].
+    #[inline]
+    pub fn with_elide(mut self, mode: Elide) -> Self {
+        self.elide.set_mode(mode);
+        self
+    }
+
+    /// Get whether the label is currently elided
+    #[inline]
+    pub fn is_elided(&self) -> bool {
+        self.elide.is_elided()
+    }
+
+    fn update_elide(&mut self) {
+        let avail = self.core.rect.size.clamped_sub(self.frame_size);
+        self.elide.update(&self.label, Vec2::from(avail).0);
+    }
 }
 
 impl<M: 'static> HasStr for TextButton<M> {
@@ -166,7 +213,9 @@ impl<M: 'static> SetAccel for TextButton<M> {
             action |= TkAction::RECONFIGURE;
         }
         let avail = self.core.rect.size.clamped_sub(self.frame_size);
-        action | kas::text::util::set_text_and_prepare(&mut self.label, string, avail)
+        action |= kas::text::util::set_text_and_prepare(&mut self.label, string, avail);
+        self.update_elide();
+        action
     }
 }
 
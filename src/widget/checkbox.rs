@@ -241,3 +241,10 @@ impl<M: 'static> HasBool for CheckBox<M> {
         self.checkbox.set_bool(state)
     }
 }
+
+impl<M: 'static> SetAccel for CheckBox<M> {
+    #[inline]
+    fn set_accel_string(&mut self, string: AccelString) -> TkAction {
+        self.label.set_accel_string(string)
+    }
+}
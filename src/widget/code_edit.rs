@@ -0,0 +1,392 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Code editor
+
+use std::fmt::{Debug, Write};
+use std::ops::Range;
+
+use super::{EditField, EditGuard};
+use kas::draw::{Colour, TextClass};
+use kas::event;
+use kas::geom::Vec2;
+use kas::prelude::*;
+use kas::text::Effect;
+
+/// A syntax highlighter, as used by [`CodeEdit`]
+pub trait Highlighter: Debug + 'static {
+    /// Compute colours and effects for `text`
+    ///
+    /// The result must be ordered by `start`; each entry applies until the
+    /// start of the next. Text before the first entry uses the default colour.
+    ///
+    /// This is called after each edit.
+    fn highlight(&mut self, text: &str) -> Vec<Effect<Colour>>;
+}
+
+/// No highlighting
+impl Highlighter for () {
+    fn highlight(&mut self, _: &str) -> Vec<Effect<Colour>> {
+        vec![]
+    }
+}
+
+/// Indentation inserted by the Tab key in code mode
+pub(super) const INDENT: &str = "    ";
+
+/// Expand `range` to cover all lines it touches, excluding line breaks
+///
+/// A trailing line break at the end of a non-empty `range` does not pull in
+/// the following line.
+pub(super) fn line_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let start = text[0..range.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let mut end = range.end;
+    if end > range.start && text[0..end].ends_with('\n') {
+        // Do not include the line following the selection
+        end -= 1;
+    }
+    let end = text[end..]
+        .find('\n')
+        .map(|i| end + i)
+        .unwrap_or(text.len());
+    start..end
+}
+
+/// Indent or outdent each line of `lines`
+///
+/// Indentation adds [`INDENT`] to each non-empty line. Outdentation removes a
+/// single tab or up to `INDENT.len()` spaces from each line.
+pub(super) fn indent_lines(lines: &str, outdent: bool) -> String {
+    let mut string = String::with_capacity(lines.len() + INDENT.len());
+    for (i, line) in lines.split('\n').enumerate() {
+        if i > 0 {
+            string.push('\n');
+        }
+        if outdent {
+            let n = if line.starts_with('\t') {
+                1
+            } else {
+                let spaces = line.bytes().take_while(|b| *b == b' ').count();
+                spaces.min(INDENT.len())
+            };
+            string.push_str(&line[n..]);
+        } else {
+            if !line.is_empty() {
+                string.push_str(INDENT);
+            }
+            string.push_str(line);
+        }
+    }
+    string
+}
+
+/// Leading whitespace of the line containing `pos`, up to `pos`
+///
+/// This is the indentation copied to a new line by auto-indent.
+pub(super) fn line_indent(text: &str, pos: usize) -> &str {
+    let start = text[0..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = &text[start..pos];
+    &line[0..line.len() - line.trim_start().len()]
+}
+
+/// A code-editing box
+///
+/// This is a multi-line [`EditField`] within a frame, with:
+///
+/// -   a line-number gutter
+/// -   syntax highlighting via a [`Highlighter`]
+/// -   highlighting of the current line
+/// -   indentation of selected lines via Tab and Shift+Tab
+/// -   auto-indent on Return
+#[derive(Clone, Debug, Widget)]
+#[handler(send=noauto, msg = G::Msg)]
+pub struct CodeEdit<H: Highlighter = (), G: EditGuard = ()> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: EditField<G>,
+    highlighter: H,
+    gutter: Text<String>,
+    gutter_rect: Rect,
+    gutter_width: i32,
+    gutter_margin: i32,
+    offset: Offset,
+    frame_size: Size,
+}
+
+impl CodeEdit<(), ()> {
+    /// Construct a `CodeEdit` with the given inital `text`
+    #[inline]
+    pub fn new<S: ToString>(text: S) -> Self {
+        let mut inner = EditField::new(text).multi_line(true);
+        inner.set_code_mode(true);
+        CodeEdit {
+            core: Default::default(),
+            inner,
+            highlighter: (),
+            gutter: Text::new(Default::default(), String::new()),
+            gutter_rect: Rect::default(),
+            gutter_width: 0,
+            gutter_margin: 0,
+            offset: Default::default(),
+            frame_size: Default::default(),
+        }
+    }
+}
+
+impl<G: EditGuard> CodeEdit<(), G> {
+    /// Set a [`Highlighter`]
+    #[inline]
+    pub fn with_highlighter<H: Highlighter>(self, highlighter: H) -> CodeEdit<H, G> {
+        let mut edit = CodeEdit {
+            core: self.core,
+            inner: self.inner,
+            highlighter,
+            gutter: self.gutter,
+            gutter_rect: self.gutter_rect,
+            gutter_width: self.gutter_width,
+            gutter_margin: self.gutter_margin,
+            offset: self.offset,
+            frame_size: self.frame_size,
+        };
+        edit.update_effects();
+        edit
+    }
+}
+
+impl<H: Highlighter> CodeEdit<H, ()> {
+    /// Set an [`EditGuard`]
+    ///
+    /// See [`EditField::with_guard`].
+    #[inline]
+    pub fn with_guard<G: EditGuard>(self, guard: G) -> CodeEdit<H, G> {
+        CodeEdit {
+            core: self.core,
+            inner: self.inner.with_guard(guard),
+            highlighter: self.highlighter,
+            gutter: self.gutter,
+            gutter_rect: self.gutter_rect,
+            gutter_width: self.gutter_width,
+            gutter_margin: self.gutter_margin,
+            offset: self.offset,
+            frame_size: self.frame_size,
+        }
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> CodeEdit<H, G> {
+    /// Access the highlighter
+    pub fn highlighter(&self) -> &H {
+        &self.highlighter
+    }
+
+    /// Access the highlighter mutably
+    ///
+    /// After changing the highlighter's configuration, call
+    /// [`CodeEdit::rehighlight`].
+    pub fn highlighter_mut(&mut self) -> &mut H {
+        &mut self.highlighter
+    }
+
+    /// Recompute highlighting
+    pub fn rehighlight(&mut self) -> TkAction {
+        self.update_effects();
+        TkAction::REDRAW
+    }
+
+    fn update_effects(&mut self) {
+        let effects = self.highlighter.highlight(self.inner.get_str());
+        self.inner.set_effects(effects);
+    }
+
+    /// Update the gutter text after the text is prepared
+    ///
+    /// Each line number is placed on the first visual line of its line.
+    fn update_gutter(&mut self) -> TkAction {
+        let text = self.inner.text_object();
+        let string = text.text();
+        let starts = std::iter::once(0).chain(string.match_indices('\n').map(|(i, _)| i + 1));
+        let mut gutter = String::new();
+        let mut last_line = None;
+        for (n, start) in starts.enumerate() {
+            let line = text.find_line(start).map(|r| r.0);
+            if n > 0 {
+                let breaks = match (last_line, line) {
+                    (Some(a), Some(b)) if b > a => b - a,
+                    _ => 1,
+                };
+                gutter.extend(std::iter::repeat('\n').take(breaks));
+            }
+            last_line = line;
+            write!(gutter, "{}", n + 1).unwrap();
+        }
+
+        // The last number is the widest
+        let digits = |s: &str| s.rsplit('\n').next().map(|s| s.len());
+        let resize = digits(&gutter) != digits(self.gutter.text());
+        self.gutter.set_string(gutter);
+        let _ = self.gutter.prepare();
+        if resize {
+            TkAction::RESIZE
+        } else {
+            TkAction::REDRAW
+        }
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> Layout for CodeEdit<H, G> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let frame_rules = size_handle.edit_surround(axis.is_vertical());
+        let mut child_rules = self.inner.size_rules(size_handle, axis);
+        if axis.is_horizontal() {
+            if self.gutter.text().is_empty() {
+                let _ = self.update_gutter();
+            }
+            let gutter_rules =
+                size_handle.text_bound(&mut self.gutter, TextClass::LabelFixed, axis);
+            self.gutter_margin = size_handle.inner_margin().0;
+            self.gutter_width = gutter_rules.min_size() + 2 * self.gutter_margin;
+            child_rules = SizeRules::new(
+                child_rules.min_size() + self.gutter_width,
+                child_rules.ideal_size() + self.gutter_width,
+                child_rules.margins(),
+                child_rules.stretch(),
+            );
+        }
+
+        let (rules, offset, size) = frame_rules.surround(child_rules);
+        self.offset.set_component(axis, offset);
+        self.frame_size.set_component(axis, size);
+        rules
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, mut rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        rect.pos += self.offset;
+        rect.size -= self.frame_size;
+        let gutter_width = self.gutter_width.min(rect.size.0);
+        let text_width = (gutter_width - 2 * self.gutter_margin).max(0);
+        let pos = Coord(rect.pos.0 + self.gutter_margin, rect.pos.1);
+        self.gutter_rect = Rect::new(pos, Size(text_width, rect.size.1));
+        rect.pos.0 += gutter_width;
+        rect.size.0 -= gutter_width;
+        self.inner.set_rect(mgr, rect, align);
+
+        // Vertical position must match that of the text
+        self.gutter_rect.pos.1 = self.inner.rect().pos.1;
+        let bounds = self.gutter_rect.size;
+        self.gutter.update_env(|env| {
+            env.set_align((Align::BR, Align::TL));
+            env.set_bounds(bounds.into());
+            env.set_wrap(false);
+        });
+        *mgr |= self.update_gutter();
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.inner.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let mut input_state = self.inner.input_state(mgr, disabled);
+        input_state.error = self.inner.has_error();
+        draw_handle.edit_box(self.core.rect, input_state);
+
+        // The gutter scrolls vertically with the text
+        let bounds = Vec2::from(self.gutter_rect.size);
+        let offset = Offset(0, self.inner.view_offset().1);
+        draw_handle.text_offset(
+            self.gutter_rect.pos,
+            bounds,
+            offset,
+            self.gutter.as_ref(),
+            TextClass::LabelFixed,
+        );
+
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> event::SendEvent for CodeEdit<H, G> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled;
+        }
+
+        if id <= self.inner.id() {
+            let r = self.inner.send(mgr, id, event);
+            match r {
+                Response::Update | Response::Msg(_) => {
+                    self.update_effects();
+                    *mgr |= self.update_gutter();
+                }
+                _ => (),
+            }
+            r
+        } else {
+            Manager::handle_generic(self, mgr, event)
+        }
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> HasStr for CodeEdit<H, G> {
+    #[inline]
+    fn get_str(&self) -> &str {
+        self.inner.get_str()
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> HasString for CodeEdit<H, G> {
+    fn set_string(&mut self, text: String) -> TkAction {
+        let action = self.inner.set_string(text);
+        self.update_effects();
+        action | self.update_gutter()
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> std::ops::Deref for CodeEdit<H, G> {
+    type Target = EditField<G>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<H: Highlighter, G: EditGuard> std::ops::DerefMut for CodeEdit<H, G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+#[test]
+fn line_range_selection() {
+    let text = "a\n  bc\nd\n";
+    assert_eq!(line_range(text, 0..0), 0..1);
+    assert_eq!(line_range(text, 4..4), 2..6);
+    assert_eq!(line_range(text, 1..4), 0..6);
+    // A selection ending just after a line break excludes the next line
+    assert_eq!(line_range(text, 2..7), 2..6);
+    assert_eq!(line_range(text, 9..9), 9..9);
+}
+
+#[test]
+fn indent_outdent() {
+    assert_eq!(indent_lines("a\n\n  b", false), "    a\n\n      b");
+    assert_eq!(indent_lines("      a\n\tb\n  c\nd", true), "  a\nb\nc\nd");
+    assert_eq!(indent_lines("a", true), "a");
+}
+
+#[test]
+fn auto_indent() {
+    let text = "fn f() {\n    \tx\n";
+    assert_eq!(line_indent(text, 3), "");
+    assert_eq!(line_indent(text, 9), "");
+    assert_eq!(line_indent(text, 14), "    \t");
+    // Only whitespace before the cursor is copied
+    assert_eq!(line_indent(text, 11), "  ");
+}
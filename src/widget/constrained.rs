@@ -0,0 +1,98 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Size constraints
+
+use kas::layout::Constraints;
+use kas::{event, prelude::*};
+
+/// A wrapper applying size constraints
+///
+/// This widget overrides the minimum and maximum size and/or fixes the aspect
+/// ratio of its inner widget; see [`Constraints`]. Constraints may be adjusted
+/// at run-time.
+///
+/// For children of widgets using a derived layout, constraints may also be
+/// specified via the `#[widget]` attribute, e.g.
+/// `#[widget(min_width = 100, aspect = 1.5)]`.
+#[derive(Clone, Default, Debug, Widget)]
+#[handler(msg = <W as Handler>::Msg)]
+pub struct Constrained<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    pub inner: W,
+    constraints: Constraints,
+}
+
+impl<W: Widget> Constrained<W> {
+    /// Construct
+    #[inline]
+    pub fn new(inner: W, constraints: Constraints) -> Self {
+        Constrained {
+            core: Default::default(),
+            inner,
+            constraints,
+        }
+    }
+
+    /// Get the constraints
+    #[inline]
+    pub fn constraints(&self) -> Constraints {
+        self.constraints
+    }
+
+    /// Set the constraints
+    ///
+    /// Triggers a [resize action](Manager::send_action) if changed.
+    pub fn set_constraints(&mut self, constraints: Constraints) -> TkAction {
+        if constraints == self.constraints {
+            return TkAction::empty();
+        }
+        self.constraints = constraints;
+        TkAction::RESIZE
+    }
+}
+
+impl<W: Widget> Layout for Constrained<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules = self.inner.size_rules(size_handle, axis);
+        let scale_factor = size_handle.scale_factor();
+        self.constraints.apply_rules(rules, axis, scale_factor)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        let scale_factor = mgr.size_handle(|sh| sh.scale_factor());
+        let rect = self.constraints.apply_rect(rect, align, scale_factor);
+        self.core.rect = rect;
+        self.inner.set_rect(mgr, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.inner.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<W: Widget> std::ops::Deref for Constrained<W> {
+    type Target = W;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<W: Widget> std::ops::DerefMut for Constrained<W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -5,17 +5,25 @@
 
 //! Text widgets
 
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::fmt::{self, Debug};
 use std::ops::Range;
+use std::rc::Rc;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
-use kas::draw::TextClass;
-use kas::event::components::{TextInput, TextInputAction};
-use kas::event::{self, Command, ScrollDelta};
+use super::code_edit::{indent_lines, line_indent, line_range, INDENT};
+use super::spelling::{find_misspelled, SpellMenu};
+use super::{CheckBox, MenuFrame, SpellChecker, TextButton};
+use kas::draw::{Colour, TextClass};
+use kas::event::components::{PrimaryTracker, TextInput, TextInputAction};
+use kas::event::{self, Command, MouseButton, PressSource, ScrollDelta};
 use kas::geom::Vec2;
+use kas::i18n::Message;
 use kas::macros::*;
 use kas::prelude::*;
-use kas::text::SelectionHelper;
+use kas::text::{Effect, SelectionHelper};
+use kas::WindowId;
 
 #[derive(Clone, Debug, PartialEq)]
 enum LastEdit {
@@ -31,6 +39,155 @@ impl Default for LastEdit {
     }
 }
 
+/// Default maximum number of entries in the undo history
+const UNDO_DEPTH: usize = 100;
+
+/// Glyph displayed in place of each grapheme in password mode
+const PASSWORD_MASK: char = '\u{2022}';
+
+/// A text state saved in the undo history
+#[derive(Clone, Debug)]
+struct UndoState {
+    text: String,
+    edit_pos: usize,
+    sel_pos: usize,
+}
+
+/// Undo and redo history
+///
+/// Each entry is a complete copy of the text plus cursor and selection
+/// positions. This is simple but has `O(n)` memory cost per entry, which is
+/// acceptable for the short texts [`EditField`] is intended for.
+#[derive(Clone, Debug)]
+struct UndoStack {
+    undo: VecDeque<UndoState>,
+    redo: Vec<UndoState>,
+    depth: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        UndoStack {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth: UNDO_DEPTH,
+        }
+    }
+}
+
+impl UndoStack {
+    fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+        self.redo.truncate(depth);
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Push a new state (before an edit), clearing redo history
+    fn push(&mut self, state: UndoState) {
+        self.redo.clear();
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() >= self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(state);
+    }
+
+    /// Step back, given the `current` state
+    fn undo(&mut self, current: UndoState) -> Option<UndoState> {
+        let state = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(state)
+    }
+
+    /// Step forward, given the `current` state
+    fn redo(&mut self, current: UndoState) -> Option<UndoState> {
+        let state = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(state)
+    }
+}
+
+/// Search state
+#[derive(Clone, Debug, Default)]
+struct FindState {
+    query: String,
+    match_case: bool,
+    matches: Vec<Range<usize>>,
+    current: Option<usize>,
+}
+
+impl FindState {
+    /// Update matches after a change to the query or text
+    fn update(&mut self, text: &str) {
+        self.matches.clear();
+        self.current = None;
+        if self.query.is_empty() {
+            return;
+        }
+
+        if self.match_case {
+            let len = self.query.len();
+            let iter = text.match_indices(self.query.as_str());
+            self.matches.extend(iter.map(|(i, _)| i..i + len));
+        } else {
+            let mut start = 0;
+            while let Some(c) = text[start..].chars().next() {
+                if let Some(len) = match_len_ignore_case(&text[start..], &self.query) {
+                    self.matches.push(start..start + len);
+                    start += len;
+                } else {
+                    start += c.len_utf8();
+                }
+            }
+        }
+    }
+}
+
+/// If `text` starts with `query` (ignoring case), return the match length
+fn match_len_ignore_case(text: &str, query: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+        if c != q && !c.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map(|(i, _)| i).unwrap_or(text.len()))
+}
+
+/// Byte index of each grapheme in `text`
+fn grapheme_starts(text: &str) -> Vec<usize> {
+    text.grapheme_indices(true).map(|(i, _)| i).collect()
+}
+
+/// Map a byte index in the text to a byte index in the password mask
+///
+/// The grapheme byte indices `starts` are from [`grapheme_starts`].
+fn mask_index(starts: &[usize], index: usize) -> usize {
+    let n = match starts.binary_search(&index) {
+        Ok(n) | Err(n) => n,
+    };
+    n * PASSWORD_MASK.len_utf8()
+}
+
+/// Map a byte index in the password mask to a byte index in the text
+///
+/// The grapheme byte indices `starts` are from [`grapheme_starts`]; `len` is
+/// the length of the text.
+fn unmask_index(starts: &[usize], index: usize, len: usize) -> usize {
+    let n = index / PASSWORD_MASK.len_utf8();
+    starts.get(n).cloned().unwrap_or(len)
+}
+
 enum EditAction {
     None,
     Unhandled,
@@ -177,16 +334,32 @@ impl<F: FnMut(&str)> Debug for EditUpdate<F> {
 
 /// A text-edit box
 ///
-/// This is just a wrapper around [`EditField`] adding a frame.
+/// This is a wrapper around [`EditField`] adding a frame.
+///
+/// In [multi-line](EditBox::multi_line) mode the box supports find and
+/// replace: [`Command::Find`] and [`Command::FindReplace`] open a find bar
+/// below the box, while [`Command::FindNext`] and [`Command::FindPrev`] step
+/// through matches. (The find bar is only constructed in this mode.)
+///
+/// With a [spell checker](EditBox::with_spell_checker), suggestions for the
+/// misspelled word under the cursor are shown in a pop-up menu on
+/// [`Command::Spelling`] or on right-click.
 #[derive(Clone, Debug, Widget)]
-#[handler(msg = G::Msg)]
+#[handler(noauto)]
+#[widget(children=noauto)]
 pub struct EditBox<G: EditGuard = ()> {
     #[widget_core]
     core: CoreData,
-    #[widget]
     inner: EditField<G>,
     offset: Offset,
     frame_size: Size,
+    // Present only in multi-line mode
+    find_bar: Option<Box<MenuFrame<FindBar>>>,
+    find_popup: Option<WindowId>,
+    // Present only once a spell checker is set
+    spell_menu: Option<Box<MenuFrame<SpellMenu>>>,
+    spell_popup: Option<WindowId>,
+    spell_word: Range<usize>,
 }
 
 impl EditBox<()> {
@@ -198,6 +371,11 @@ impl EditBox<()> {
             inner: EditField::new(text),
             offset: Offset::ZERO,
             frame_size: Size::ZERO,
+            find_bar: None,
+            find_popup: None,
+            spell_menu: None,
+            spell_popup: None,
+            spell_word: 0..0,
         }
     }
 
@@ -215,6 +393,11 @@ impl EditBox<()> {
             inner: self.inner.with_guard(guard),
             offset: self.offset,
             frame_size: self.frame_size,
+            find_bar: self.find_bar,
+            find_popup: self.find_popup,
+            spell_menu: self.spell_menu,
+            spell_popup: self.spell_popup,
+            spell_word: self.spell_word,
         }
     }
 
@@ -298,6 +481,87 @@ impl<G: EditGuard> EditBox<G> {
     #[inline]
     pub fn multi_line(mut self, multi_line: bool) -> Self {
         self.inner = self.inner.multi_line(multi_line);
+        self.find_bar = match multi_line {
+            false => None,
+            true => Some(Box::new(MenuFrame::new(FindBar::new()))),
+        };
+        self
+    }
+
+    /// Set the maximum depth of the undo history (inline)
+    ///
+    /// See [`EditField::with_undo_depth`].
+    #[inline]
+    pub fn with_undo_depth(mut self, depth: usize) -> Self {
+        self.inner = self.inner.with_undo_depth(depth);
+        self
+    }
+
+    /// Set the maximum depth of the undo history
+    ///
+    /// See [`EditField::set_undo_depth`].
+    #[inline]
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.inner.set_undo_depth(depth);
+    }
+
+    /// Set whether the undo history is kept on programmatic updates (inline)
+    ///
+    /// See [`EditField::with_keep_history`].
+    #[inline]
+    pub fn with_keep_history(mut self, keep: bool) -> Self {
+        self.inner = self.inner.with_keep_history(keep);
+        self
+    }
+
+    /// Set whether the undo history is kept on programmatic updates
+    ///
+    /// See [`EditField::set_keep_history`].
+    #[inline]
+    pub fn set_keep_history(&mut self, keep: bool) {
+        self.inner.set_keep_history(keep);
+    }
+
+    /// Set a spell checker (inline)
+    ///
+    /// See [`EditField::with_spell_checker`].
+    pub fn with_spell_checker(mut self, checker: Rc<RefCell<dyn SpellChecker>>) -> Self {
+        self.inner = self.inner.with_spell_checker(checker);
+        self.spell_menu = Some(Box::new(MenuFrame::new(SpellMenu::default())));
+        self
+    }
+
+    /// Set or remove the spell checker
+    ///
+    /// See [`EditField::set_spell_checker`]. The suggestion menu is only
+    /// constructed once a spell checker is set, which requires reconfiguring.
+    pub fn set_spell_checker(
+        &mut self,
+        checker: Option<Rc<RefCell<dyn SpellChecker>>>,
+    ) -> TkAction {
+        let mut action = TkAction::empty();
+        if checker.is_some() && self.spell_menu.is_none() {
+            self.spell_menu = Some(Box::new(MenuFrame::new(SpellMenu::default())));
+            action = TkAction::RECONFIGURE;
+        }
+        action | self.inner.set_spell_checker(checker)
+    }
+
+    /// Set whether this `EditBox` is a password field (inline)
+    ///
+    /// See [`EditField::set_password`].
+    #[inline]
+    pub fn password(mut self, password: bool) -> Self {
+        self.inner = self.inner.password(password);
+        self
+    }
+
+    /// Set placeholder text (inline)
+    ///
+    /// See [`EditField::set_placeholder`].
+    #[inline]
+    pub fn with_placeholder<S: ToString>(mut self, placeholder: S) -> Self {
+        self.inner = self.inner.with_placeholder(placeholder);
         self
     }
 
@@ -315,6 +579,210 @@ impl<G: EditGuard> EditBox<G> {
     pub fn set_error_state(&mut self, error_state: bool) {
         self.inner.set_error_state(error_state);
     }
+
+    fn open_find(&mut self, mgr: &mut Manager, replace: bool) {
+        let parent = self.id();
+        let find_bar = match self.find_bar.as_deref_mut() {
+            Some(find_bar) => find_bar,
+            None => return,
+        };
+        let bar = &mut find_bar.inner;
+        let selection = self.inner.selection.range();
+        if selection.start < selection.end {
+            let text = &self.inner.text.text()[selection];
+            if !text.contains('\n') {
+                *mgr |= bar.query.inner.set_string(text.to_string());
+            }
+        }
+        let query = &mut bar.query.inner;
+        query.selection.set_sel_pos(0);
+        query.selection.set_edit_pos(query.text.str_len());
+
+        let disabled = !self.inner.editable;
+        *mgr |= bar.replacement.set_disabled(disabled)
+            | bar.replace.set_disabled(disabled)
+            | bar.replace_all.set_disabled(disabled);
+        *mgr |= self
+            .inner
+            .set_find(bar.query.get_str(), bar.match_case.get_bool());
+
+        if self.find_popup.is_none() {
+            let id = mgr.add_popup(kas::Popup {
+                id: find_bar.id(),
+                parent,
+                direction: Direction::Down,
+            });
+            self.find_popup = Some(id);
+        }
+
+        let bar = &find_bar.inner;
+        if replace && !disabled && !bar.query.get_str().is_empty() {
+            mgr.request_char_focus(bar.replacement.inner.id());
+        } else {
+            mgr.request_char_focus(bar.query.inner.id());
+        }
+    }
+
+    fn close_find(&mut self, mgr: &mut Manager) {
+        if let Some(id) = self.find_popup {
+            mgr.close_window(id);
+            mgr.request_char_focus(self.inner.id());
+            self.inner.select_current_match();
+        }
+    }
+
+    fn find_command(&mut self, mgr: &mut Manager, cmd: Command) -> Response<G::Msg> {
+        let bar = match self.find_bar.as_deref() {
+            Some(find_bar) => &find_bar.inner,
+            None => return Response::Unhandled,
+        };
+
+        match cmd {
+            Command::Find => self.open_find(mgr, false),
+            Command::FindReplace => self.open_find(mgr, true),
+            Command::FindNext | Command::FindPrev => {
+                let reverse = cmd == Command::FindPrev;
+                if !self.inner.find.query.is_empty() {
+                    self.inner.find_next(mgr, reverse);
+                } else if !bar.query.get_str().is_empty() {
+                    // Repeat the last search
+                    *mgr |= self
+                        .inner
+                        .set_find(bar.query.get_str(), bar.match_case.get_bool());
+                    if reverse {
+                        self.inner.find_next(mgr, true);
+                    }
+                } else {
+                    self.open_find(mgr, false);
+                }
+            }
+            Command::Escape if self.find_popup.is_some() => self.close_find(mgr),
+            Command::Escape if !self.inner.find.query.is_empty() => {
+                *mgr |= self.inner.clear_find();
+            }
+            _ => return Response::Unhandled,
+        }
+        Response::None
+    }
+
+    fn command(&mut self, mgr: &mut Manager, cmd: Command) -> Response<G::Msg> {
+        match cmd {
+            Command::Spelling => self.open_spelling(mgr),
+            Command::Escape if self.spell_popup.is_some() => {
+                self.close_spelling(mgr);
+                Response::None
+            }
+            cmd => self.find_command(mgr, cmd),
+        }
+    }
+
+    /// Open the spelling menu for the misspelled word at the cursor, if any
+    fn open_spelling(&mut self, mgr: &mut Manager) -> Response<G::Msg> {
+        let parent = self.id();
+        let menu = match self.spell_menu.as_deref_mut() {
+            Some(menu) => menu,
+            None => return Response::Unhandled,
+        };
+        let range = match self.inner.misspelled_at_cursor() {
+            Some(range) if self.inner.editable => range,
+            _ => return Response::Unhandled,
+        };
+        let word = &self.inner.text.text()[range.clone()];
+        let suggestions = match self.inner.spell_checker {
+            Some(ref checker) => checker.borrow().suggest(word),
+            None => vec![],
+        };
+        let action = menu.inner.set_suggestions(mgr, suggestions);
+        *mgr |= action;
+        self.spell_word = range;
+
+        if self.spell_popup.is_none() {
+            let id = mgr.add_popup(kas::Popup {
+                id: menu.id(),
+                parent,
+                direction: Direction::Down,
+            });
+            self.spell_popup = Some(id);
+        }
+        Response::None
+    }
+
+    fn close_spelling(&mut self, mgr: &mut Manager) {
+        if let Some(id) = self.spell_popup {
+            mgr.close_window(id);
+        }
+    }
+
+    fn spell_msg(&mut self, mgr: &mut Manager, index: usize) -> Response<G::Msg> {
+        self.close_spelling(mgr);
+        let range = self.spell_word.clone();
+        let menu = self.spell_menu.as_deref().map(|menu| &menu.inner);
+        match menu.and_then(|menu| menu.suggestion(index)) {
+            Some(word) => {
+                let word = word.to_string();
+                self.inner.replace_word(mgr, range, &word);
+                Response::update_or_msg(G::edit(&mut self.inner, mgr))
+            }
+            None => {
+                let word = self.inner.text.text()[range].to_string();
+                self.inner.add_to_dictionary(&word);
+                mgr.redraw(self.inner.id());
+                Response::None
+            }
+        }
+    }
+
+    fn find_msg(&mut self, mgr: &mut Manager, msg: FindMsg) -> Response<G::Msg> {
+        let bar = match self.find_bar.as_deref() {
+            Some(find_bar) => &find_bar.inner,
+            None => return Response::None,
+        };
+        let edited = match msg {
+            FindMsg::Update => {
+                *mgr |= self
+                    .inner
+                    .set_find(bar.query.get_str(), bar.match_case.get_bool());
+                false
+            }
+            FindMsg::Next | FindMsg::Prev => {
+                self.inner.find_next(mgr, msg == FindMsg::Prev);
+                false
+            }
+            FindMsg::Replace => self.inner.replace_match(mgr, bar.replacement.get_str()),
+            FindMsg::ReplaceAll => self.inner.replace_all(mgr, bar.replacement.get_str()) > 0,
+        };
+        if edited {
+            Response::update_or_msg(G::edit(&mut self.inner, mgr))
+        } else {
+            Response::None
+        }
+    }
+}
+
+impl<G: EditGuard> WidgetChildren for EditBox<G> {
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.inner.first_id()
+    }
+    fn num_children(&self) -> usize {
+        1 + usize::from(self.find_bar.is_some()) + usize::from(self.spell_menu.is_some())
+    }
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        let find_bar = self.find_bar.as_deref().map(|w| w.as_widget());
+        let spell_menu = self.spell_menu.as_deref().map(|w| w.as_widget());
+        std::iter::once(self.inner.as_widget())
+            .chain(find_bar)
+            .chain(spell_menu)
+            .nth(index)
+    }
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        let find_bar = self.find_bar.as_deref_mut().map(|w| w.as_widget_mut());
+        let spell_menu = self.spell_menu.as_deref_mut().map(|w| w.as_widget_mut());
+        std::iter::once(self.inner.as_widget_mut())
+            .chain(find_bar)
+            .chain(spell_menu)
+            .nth(index)
+    }
 }
 
 impl<G: EditGuard> Layout for EditBox<G> {
@@ -335,6 +803,11 @@ impl<G: EditGuard> Layout for EditBox<G> {
         self.inner.set_rect(mgr, rect, align);
     }
 
+    fn spatial_range(&self) -> (usize, usize) {
+        // The find bar and spelling menu are pop-ups, hence excluded
+        (0, 0)
+    }
+
     #[inline]
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
         if !self.rect().contains(coord) {
@@ -353,6 +826,110 @@ impl<G: EditGuard> Layout for EditBox<G> {
     }
 }
 
+impl<G: EditGuard> event::Handler for EditBox<G> {
+    type Msg = G::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::PopupRemoved(id) if Some(id) == self.spell_popup => {
+                self.spell_popup = None;
+                Response::None
+            }
+            Event::PopupRemoved(id) => {
+                debug_assert_eq!(Some(id), self.find_popup);
+                self.find_popup = None;
+                *mgr |= self.inner.clear_find();
+                Response::None
+            }
+            Event::PressMove { coord, .. } if self.spell_popup.is_some() => {
+                // Sent without a grab while a pop-up is open
+                if let Some(menu) = self.spell_menu.as_deref_mut() {
+                    menu.inner.highlight_at(mgr, coord);
+                }
+                Response::None
+            }
+            Event::Command(cmd, _) => self.command(mgr, cmd),
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+impl<G: EditGuard> event::SendEvent for EditBox<G> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled;
+        }
+
+        let r = if id <= self.inner.id() {
+            // While the spelling menu is open, navigation keys apply to it
+            let menu = match self.spell_popup {
+                Some(_) => self.spell_menu.as_deref_mut(),
+                None => None,
+            };
+            if let (Some(menu), Event::Command(cmd, _)) = (menu, &event) {
+                let menu = &mut menu.inner;
+                match cmd {
+                    Command::Up | Command::Down => {
+                        menu.move_highlight(mgr, *cmd == Command::Up);
+                        return Response::None;
+                    }
+                    Command::Return => match menu.highlighted() {
+                        Some(index) => return self.spell_msg(mgr, index),
+                        None => (),
+                    },
+                    _ => (),
+                }
+            }
+            let r = self.inner.send(mgr, id, event.clone());
+            if let Response::Update | Response::Msg(_) = r {
+                // The word may have moved
+                self.close_spelling(mgr);
+            }
+            r
+        } else if let Some(bar) = self.find_bar.as_deref_mut().filter(|bar| id <= bar.id()) {
+            match bar.send(mgr, id, event.clone()) {
+                Response::None | Response::Update => Response::None,
+                Response::Unhandled => Response::Unhandled,
+                Response::Focus(rect) => Response::Focus(rect),
+                Response::Msg(msg) => return self.find_msg(mgr, msg),
+            }
+        } else if let Some(menu) = self
+            .spell_menu
+            .as_deref_mut()
+            .filter(|menu| id <= menu.id())
+        {
+            match menu.send(mgr, id, event.clone()) {
+                Response::None | Response::Update => Response::None,
+                Response::Unhandled => Response::Unhandled,
+                Response::Focus(rect) => Response::Focus(rect),
+                Response::Msg(index) => return self.spell_msg(mgr, index),
+            }
+        } else {
+            return Manager::handle_generic(self, mgr, event);
+        };
+
+        // Find and spelling commands are handled here if not used by the
+        // focussed child, as is a right-click
+        match (r, event) {
+            (Response::Unhandled, Event::Command(cmd, _)) => self.command(mgr, cmd),
+            (
+                Response::Unhandled,
+                Event::PressStart {
+                    source: PressSource::Mouse(MouseButton::Right, _),
+                    coord,
+                    ..
+                },
+            ) if id == self.inner.id() => {
+                self.inner.set_edit_pos_from_coord(mgr, coord);
+                self.inner.selection.set_empty();
+                mgr.request_char_focus(self.inner.id());
+                self.open_spelling(mgr)
+            }
+            (r, _) => r,
+        }
+    }
+}
+
 impl<G: EditGuard> HasStr for EditBox<G> {
     #[inline]
     fn get_str(&self) -> &str {
@@ -380,6 +957,155 @@ impl<G: EditGuard> std::ops::DerefMut for EditBox<G> {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, VoidMsg)]
+enum FindMsg {
+    Update,
+    Next,
+    Prev,
+    Replace,
+    ReplaceAll,
+}
+
+#[derive(Clone, Debug)]
+struct FindGuard;
+impl EditGuard for FindGuard {
+    type Msg = FindMsg;
+    fn activate(_: &mut EditField<Self>, _: &mut Manager) -> Option<Self::Msg> {
+        Some(FindMsg::Next)
+    }
+    fn edit(_: &mut EditField<Self>, _: &mut Manager) -> Option<Self::Msg> {
+        Some(FindMsg::Update)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ReplaceGuard;
+impl EditGuard for ReplaceGuard {
+    type Msg = FindMsg;
+    fn activate(_: &mut EditField<Self>, _: &mut Manager) -> Option<Self::Msg> {
+        Some(FindMsg::Replace)
+    }
+}
+
+/// An [`EditField`] with a frame, used by the find bar
+///
+/// (Using [`EditBox`] here would make for a recursive type.)
+#[derive(Clone, Debug, Widget)]
+#[handler(msg = G::Msg)]
+struct FindEdit<G: EditGuard> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    inner: EditField<G>,
+    offset: Offset,
+    frame_size: Size,
+}
+
+impl<G: EditGuard> FindEdit<G> {
+    fn new(guard: G) -> Self {
+        FindEdit {
+            core: Default::default(),
+            inner: EditField::new("").with_guard(guard),
+            offset: Offset::ZERO,
+            frame_size: Size::ZERO,
+        }
+    }
+
+    fn get_str(&self) -> &str {
+        self.inner.get_str()
+    }
+}
+
+impl<G: EditGuard> Layout for FindEdit<G> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let frame_rules = size_handle.edit_surround(axis.is_vertical());
+        let child_rules = self.inner.size_rules(size_handle, axis);
+
+        let (rules, offset, size) = frame_rules.surround(child_rules);
+        self.offset.set_component(axis, offset);
+        self.frame_size.set_component(axis, size);
+        rules
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, mut rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        rect.pos += self.offset;
+        rect.size -= self.frame_size;
+        self.inner.set_rect(mgr, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.inner.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let input_state = self.inner.input_state(mgr, disabled);
+        draw_handle.edit_box(self.core.rect, input_state);
+        let disabled = disabled || self.is_disabled();
+        self.inner.draw(draw_handle, mgr, disabled);
+    }
+}
+
+/// The find bar of an [`EditBox`]
+///
+/// Labels are translated (see [`kas::i18n`]) when configured.
+#[derive(Clone, Debug, Widget)]
+#[layout(grid)]
+#[handler(msg = FindMsg)]
+#[widget(config=noauto)]
+struct FindBar {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(row = 0, col = 0)]
+    query: FindEdit<FindGuard>,
+    #[widget(row = 0, col = 1)]
+    prev: TextButton<FindMsg>,
+    #[widget(row = 0, col = 2)]
+    next: TextButton<FindMsg>,
+    #[widget(row = 0, col = 3)]
+    match_case: CheckBox<FindMsg>,
+    #[widget(row = 1, col = 0)]
+    replacement: FindEdit<ReplaceGuard>,
+    #[widget(row = 1, col = 1)]
+    replace: TextButton<FindMsg>,
+    #[widget(row = 1, col = 2)]
+    replace_all: TextButton<FindMsg>,
+}
+
+impl FindBar {
+    fn new() -> Self {
+        FindBar {
+            core: Default::default(),
+            layout_data: Default::default(),
+            query: FindEdit::new(FindGuard),
+            prev: TextButton::new_msg("Previous", FindMsg::Prev),
+            next: TextButton::new_msg("Next", FindMsg::Next),
+            match_case: CheckBox::new("Match case").on_toggle(|_, _| Some(FindMsg::Update)),
+            replacement: FindEdit::new(ReplaceGuard),
+            replace: TextButton::new_msg("Replace", FindMsg::Replace),
+            replace_all: TextButton::new_msg("Replace all", FindMsg::ReplaceAll),
+        }
+    }
+}
+
+impl WidgetConfig for FindBar {
+    fn configure(&mut self, mgr: &mut Manager) {
+        let tr = |id: &str| AccelString::from(mgr.translate(&Message::new(id)));
+        // This is called before resizing, so the results may be ignored
+        let _ = self.prev.set_accel_string(tr("Previous"));
+        let _ = self.next.set_accel_string(tr("Next"));
+        let _ = self.match_case.set_accel_string(tr("Match case"));
+        let _ = self.replace.set_accel_string(tr("Replace"));
+        let _ = self.replace_all.set_accel_string(tr("Replace all"));
+    }
+}
+
 /// A text-edit field (single- or multi-line)
 ///
 /// Usually one uses a derived type like [`EditBox`] instead. This field does
@@ -393,6 +1119,10 @@ impl<G: EditGuard> std::ops::DerefMut for EditBox<G> {
 /// Optionally, [`EditField::multi_line`] mode can be activated (enabling
 /// line-wrapping and a larger vertical height). This mode is only recommended
 /// for short texts for performance reasons.
+///
+/// Selected text is set as the primary selection once selection completes
+/// (except in password mode); a middle-click pastes the primary selection at
+/// the pointer position.
 #[derive(Clone, Default, Debug, Widget)]
 #[widget(config(key_nav = true, cursor_icon = event::CursorIcon::Text))]
 #[handler(handle=noauto, generics = <> where G: EditGuard)]
@@ -406,9 +1136,23 @@ pub struct EditField<G: EditGuard = ()> {
     text: Text<String>,
     required: Vec2,
     selection: SelectionHelper,
+    primary: PrimaryTracker,
     edit_x_coord: Option<f32>,
-    old_state: Option<(String, usize, usize)>,
+    history: UndoStack,
+    keep_history: bool,
     last_edit: LastEdit,
+    find: FindState,
+    password: bool,
+    reveal: bool,
+    mask: Text<String>,
+    /// Byte index of each grapheme of the text (in password mode)
+    graphemes: Vec<usize>,
+    mask_required: Vec2,
+    placeholder: Text<String>,
+    code: bool,
+    effects: Vec<Effect<Colour>>,
+    spell_checker: Option<Rc<RefCell<dyn SpellChecker>>>,
+    misspelled: Vec<Range<usize>>,
     error_state: bool,
     input_handler: TextInput,
     /// The associated [`EditGuard`] implementation
@@ -422,7 +1166,23 @@ impl<G: EditGuard> Layout for EditField<G> {
         } else {
             TextClass::Edit
         };
-        let rules = size_handle.text_bound(&mut self.text, class, axis);
+        let mut rules = size_handle.text_bound(&mut self.text, class, axis);
+        if self.password {
+            rules = size_handle.text_bound(&mut self.mask, class, axis);
+        }
+        if !self.placeholder.text().is_empty() {
+            if axis.is_horizontal() {
+                // The theme gives edit fields a fixed ideal width; we measure
+                // the placeholder as a label so that the ideal width fits it.
+                let p_rules =
+                    size_handle.text_bound(&mut self.placeholder, TextClass::LabelFixed, axis);
+                let ideal = rules.ideal_size().max(p_rules.ideal_size());
+                rules = SizeRules::new(rules.min_size(), ideal, rules.margins(), rules.stretch());
+            } else {
+                let p_rules = size_handle.text_bound(&mut self.placeholder, class, axis);
+                rules = rules.max(p_rules);
+            }
+        }
         if axis.is_vertical() {
             self.ideal_height = rules.ideal_size();
         }
@@ -450,8 +1210,25 @@ impl<G: EditGuard> Layout for EditField<G> {
                 env.set_align(align.unwrap_or(Align::Default, Align::Default));
                 env.set_bounds(size.into());
                 env.set_wrap(multi_line);
-            })
-            .into();
+            })
+            .into();
+        if self.password {
+            self.mask_required = self
+                .mask
+                .update_env(|env| {
+                    env.set_align(align.unwrap_or(Align::Default, Align::Default));
+                    env.set_bounds(size.into());
+                    env.set_wrap(multi_line);
+                })
+                .into();
+        }
+        if !self.placeholder.text().is_empty() {
+            self.placeholder.update_env(|env| {
+                env.set_align(align.unwrap_or(Align::Default, Align::Default));
+                env.set_bounds(size.into());
+                env.set_wrap(multi_line);
+            });
+        }
         self.set_view_offset_from_edit_pos();
     }
 
@@ -461,26 +1238,84 @@ impl<G: EditGuard> Layout for EditField<G> {
         } else {
             TextClass::Edit
         };
-        let bounds = self.text.env().bounds.into();
-        if self.selection.is_empty() {
-            draw_handle.text_offset(
+        let text = self.display_text();
+        let bounds = text.env().bounds.into();
+        if self.code {
+            self.draw_current_line(draw_handle);
+        }
+        if self.text.str_len() == 0 && !self.placeholder.text().is_empty() {
+            draw_handle.text_placeholder(
                 self.rect().pos,
                 bounds,
-                self.view_offset,
-                self.text.as_ref(),
+                Offset::ZERO,
+                self.placeholder.as_ref(),
                 class,
             );
+        }
+        if !self.find.matches.is_empty() && !self.is_masked() {
+            draw_handle.text_highlights(
+                self.rect().pos,
+                bounds,
+                self.view_offset,
+                text.as_ref(),
+                &self.find.matches,
+            );
+        }
+        let range = if !self.selection.is_empty() {
+            let range = self.selection.range();
+            Some(self.to_display(range.start)..self.to_display(range.end))
         } else {
+            // The current match is shown as selected even without focus
+            self.find.current.map(|i| {
+                let range = &self.find.matches[i];
+                self.to_display(range.start)..self.to_display(range.end)
+            })
+        };
+        let highlight = self.code && !self.effects.is_empty();
+        match range {
+            Some(range) if highlight => draw_handle.text_selected_col_effects(
+                self.rect().pos,
+                bounds,
+                self.view_offset,
+                text.as_ref(),
+                range,
+                &self.effects,
+                class,
+            ),
             // TODO(opt): we could cache the selection rectangles here to make
             // drawing more efficient (self.text.highlight_lines(range) output).
             // The same applies to the edit marker below.
-            draw_handle.text_selected(
+            Some(range) => draw_handle.text_selected(
+                self.rect().pos,
+                bounds,
+                self.view_offset,
+                text,
+                range,
+                class,
+            ),
+            None if highlight => draw_handle.text_col_effects(
+                self.rect().pos,
+                bounds,
+                self.view_offset,
+                text.as_ref(),
+                &self.effects,
+                class,
+            ),
+            None => draw_handle.text_offset(
                 self.rect().pos,
                 bounds,
                 self.view_offset,
-                &self.text,
-                self.selection.range(),
+                text.as_ref(),
                 class,
+            ),
+        }
+        if !self.misspelled.is_empty() && !self.password {
+            draw_handle.text_squiggles(
+                self.rect().pos,
+                bounds,
+                self.view_offset,
+                text.as_ref(),
+                &self.misspelled,
             );
         }
         if self.input_state(mgr, disabled).char_focus {
@@ -488,9 +1323,9 @@ impl<G: EditGuard> Layout for EditField<G> {
                 self.rect().pos,
                 bounds,
                 self.view_offset,
-                self.text.as_ref(),
+                text.as_ref(),
                 class,
-                self.selection.edit_pos(),
+                self.to_display(self.selection.edit_pos()),
             );
         }
     }
@@ -511,9 +1346,22 @@ impl EditField<()> {
             text: Text::new(Default::default(), text.into()),
             required: Vec2::ZERO,
             selection: SelectionHelper::new(len, len),
+            primary: Default::default(),
             edit_x_coord: None,
-            old_state: None,
+            history: Default::default(),
+            keep_history: false,
             last_edit: LastEdit::None,
+            find: Default::default(),
+            password: false,
+            reveal: false,
+            mask: Text::new(Default::default(), String::new()),
+            graphemes: vec![],
+            mask_required: Vec2::ZERO,
+            placeholder: Text::new(Default::default(), String::new()),
+            code: false,
+            effects: vec![],
+            spell_checker: None,
+            misspelled: vec![],
             error_state: false,
             input_handler: Default::default(),
             guard: (),
@@ -538,9 +1386,22 @@ impl EditField<()> {
             text: self.text,
             required: self.required,
             selection: self.selection,
+            primary: self.primary,
             edit_x_coord: self.edit_x_coord,
-            old_state: self.old_state,
+            history: self.history,
+            keep_history: self.keep_history,
             last_edit: self.last_edit,
+            find: self.find,
+            password: self.password,
+            reveal: self.reveal,
+            mask: self.mask,
+            graphemes: self.graphemes,
+            mask_required: self.mask_required,
+            placeholder: self.placeholder,
+            code: self.code,
+            effects: self.effects,
+            spell_checker: self.spell_checker,
+            misspelled: self.misspelled,
             error_state: self.error_state,
             input_handler: self.input_handler,
             guard,
@@ -630,6 +1491,31 @@ impl<G: EditGuard> EditField<G> {
         self
     }
 
+    /// Set placeholder text (inline)
+    ///
+    /// See [`EditField::set_placeholder`].
+    #[inline]
+    pub fn with_placeholder<S: ToString>(mut self, placeholder: S) -> Self {
+        let _ = self.set_placeholder(placeholder);
+        self
+    }
+
+    /// Get the placeholder text
+    pub fn placeholder(&self) -> &str {
+        self.placeholder.text()
+    }
+
+    /// Set placeholder text
+    ///
+    /// This text is displayed in a dimmed colour while the field is empty,
+    /// and is hidden as soon as any text is entered. The field's ideal width
+    /// is large enough to show the placeholder, subject to the theme's limit
+    /// on line length (as for labels).
+    pub fn set_placeholder<S: ToString>(&mut self, placeholder: S) -> TkAction {
+        self.placeholder.set_string(placeholder.to_string());
+        TkAction::RESIZE
+    }
+
     /// Get whether the input state is erroneous
     pub fn has_error(&self) -> bool {
         self.error_state
@@ -643,6 +1529,471 @@ impl<G: EditGuard> EditField<G> {
         self.error_state = error_state;
     }
 
+    /// Set the maximum depth of the undo history (inline)
+    ///
+    /// Default value: 100. A depth of zero disables undo.
+    #[inline]
+    pub fn with_undo_depth(mut self, depth: usize) -> Self {
+        self.history.set_depth(depth);
+        self
+    }
+
+    /// Set the maximum depth of the undo history
+    ///
+    /// If the history is longer than `depth`, the oldest entries are removed.
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.history.set_depth(depth);
+    }
+
+    /// Set whether the undo history is kept on programmatic updates (inline)
+    ///
+    /// By default (`false`), the undo history is cleared when the text is set
+    /// via [`HasString::set_string`]. If `true`, the history is kept and the
+    /// update may itself be undone.
+    #[inline]
+    pub fn with_keep_history(mut self, keep: bool) -> Self {
+        self.keep_history = keep;
+        self
+    }
+
+    /// Set whether the undo history is kept on programmatic updates
+    ///
+    /// See [`EditField::with_keep_history`].
+    #[inline]
+    pub fn set_keep_history(&mut self, keep: bool) {
+        self.keep_history = keep;
+    }
+
+    /// Clear the undo history
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.last_edit = LastEdit::None;
+    }
+
+    /// Set whether this `EditField` is a password field (inline)
+    ///
+    /// See [`EditField::set_password`].
+    #[inline]
+    pub fn password(mut self, password: bool) -> Self {
+        let _ = self.set_password(password);
+        self
+    }
+
+    /// Get whether this `EditField` is a password field
+    pub fn is_password(&self) -> bool {
+        self.password
+    }
+
+    /// Set whether this `EditField` is a password field
+    ///
+    /// In password mode, each grapheme is displayed as a mask glyph (`•`),
+    /// unless revealed via [`EditField::set_reveal`] (no control to do so is
+    /// provided by this widget; see that method). Cut and copy are
+    /// disabled, word-wise navigation and deletion move to the start or end of
+    /// the text, and no undo history is kept.
+    ///
+    /// Password mode is intended for single-line fields.
+    pub fn set_password(&mut self, password: bool) -> TkAction {
+        self.password = password;
+        self.reveal = false;
+        self.clear_history();
+        self.update_mask();
+        TkAction::RESIZE
+    }
+
+    /// Get whether the content of a password field is revealed
+    pub fn is_revealed(&self) -> bool {
+        self.reveal
+    }
+
+    /// Set whether the content of a password field is revealed
+    ///
+    /// This is intended to be set while some toggle is held, for example
+    /// between press-start and press-end events on a button. Neither
+    /// `EditField` nor [`EditBox`] provides such a toggle: the app must add
+    /// one (e.g. a button beside the field) and call this method. It has no
+    /// effect unless in [password mode](EditField::set_password).
+    pub fn set_reveal(&mut self, reveal: bool) -> TkAction {
+        if self.reveal == reveal {
+            return TkAction::empty();
+        }
+        self.reveal = reveal;
+        self.set_view_offset_from_edit_pos();
+        TkAction::REDRAW
+    }
+
+    fn is_masked(&self) -> bool {
+        self.password && !self.reveal
+    }
+
+    /// The text as displayed (masked in password mode)
+    fn display_text(&self) -> &Text<String> {
+        if self.is_masked() {
+            &self.mask
+        } else {
+            &self.text
+        }
+    }
+
+    /// Map a byte index in the text to a byte index in the displayed text
+    fn to_display(&self, index: usize) -> usize {
+        if self.is_masked() {
+            mask_index(&self.graphemes, index)
+        } else {
+            index
+        }
+    }
+
+    /// Map a byte index in the displayed text to a byte index in the text
+    fn from_display(&self, index: usize) -> usize {
+        if self.is_masked() {
+            unmask_index(&self.graphemes, index, self.text.str_len())
+        } else {
+            index
+        }
+    }
+
+    fn update_mask(&mut self) {
+        if self.password {
+            self.graphemes = grapheme_starts(self.text.text());
+            let len = self.graphemes.len();
+            let mask = std::iter::repeat(PASSWORD_MASK).take(len).collect();
+            self.mask.set_string(mask);
+            self.mask.prepare();
+        } else {
+            self.graphemes.clear();
+        }
+    }
+
+    fn required(&self) -> Vec2 {
+        if self.is_masked() {
+            self.mask_required
+        } else {
+            self.required
+        }
+    }
+
+    fn prepare_text(&mut self) {
+        self.text.prepare();
+        self.update_mask();
+    }
+
+    /// Set the search query
+    ///
+    /// All occurrences of `query` are highlighted and the first occurrence at
+    /// or after the current match (or edit position) is selected.
+    /// If `match_case` is false, matching ignores case.
+    /// An empty `query` clears the search.
+    pub fn set_find(&mut self, query: &str, match_case: bool) -> TkAction {
+        let from = match self.find.current {
+            Some(i) => self.find.matches[i].start,
+            None => self.selection.range().start,
+        };
+        self.find.query = query.to_string();
+        self.find.match_case = match_case;
+        self.find.update(self.text.text());
+        if !self.find.matches.is_empty() {
+            let index = self.find.matches.iter().position(|m| m.start >= from);
+            self.find.current = Some(index.unwrap_or(0));
+            self.select_current_match();
+        }
+        TkAction::REDRAW
+    }
+
+    /// Clear the search query and highlighting
+    pub fn clear_find(&mut self) -> TkAction {
+        self.find = Default::default();
+        TkAction::REDRAW
+    }
+
+    /// Get the byte ranges of all search matches
+    pub fn find_matches(&self) -> &[Range<usize>] {
+        &self.find.matches
+    }
+
+    /// Select the next search match, or previous if `reverse`
+    ///
+    /// The search wraps around at the end (start) of the text, and the match
+    /// is scrolled into view. Returns false when there are no matches.
+    pub fn find_next(&mut self, mgr: &mut Manager, reverse: bool) -> bool {
+        let len = self.find.matches.len();
+        if len == 0 {
+            return false;
+        }
+
+        let range = self.selection.range();
+        let matches = &self.find.matches;
+        let index = match self.find.current {
+            Some(i) if reverse => (i + len - 1) % len,
+            Some(i) => (i + 1) % len,
+            None if reverse => (matches.iter())
+                .rposition(|m| m.end <= range.start)
+                .unwrap_or(len - 1),
+            None => (matches.iter())
+                .position(|m| m.start >= range.end)
+                .unwrap_or(0),
+        };
+        self.find.current = Some(index);
+        self.select_current_match();
+        mgr.redraw(self.id());
+        true
+    }
+
+    /// Replace the current search match with `replacement`
+    ///
+    /// The following match is then selected. If there is no current match,
+    /// this only selects the next match. Replacement is a single undo step.
+    ///
+    /// Returns true if the text was edited. This does not call
+    /// [`EditGuard::edit`].
+    pub fn replace_match(&mut self, mgr: &mut Manager, replacement: &str) -> bool {
+        let range = match self.find.current {
+            Some(i) if self.editable => self.find.matches[i].clone(),
+            _ => {
+                self.find_next(mgr, false);
+                return false;
+            }
+        };
+
+        self.save_state();
+        self.text.replace_range(range.clone(), replacement);
+        let pos = range.start + replacement.len();
+        self.selection.set_pos(pos);
+        self.after_replace(mgr);
+        if !self.find.matches.is_empty() {
+            let index = self.find.matches.iter().position(|m| m.start >= pos);
+            self.find.current = Some(index.unwrap_or(0));
+            self.select_current_match();
+        }
+        true
+    }
+
+    /// Replace all search matches with `replacement`
+    ///
+    /// Replacement is a single undo step.
+    ///
+    /// Returns the number of replacements made. This does not call
+    /// [`EditGuard::edit`].
+    pub fn replace_all(&mut self, mgr: &mut Manager, replacement: &str) -> usize {
+        let count = self.find.matches.len();
+        if !self.editable || count == 0 {
+            return 0;
+        }
+
+        self.save_state();
+        let text = self.text.text();
+        let mut string = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in &self.find.matches {
+            string.push_str(&text[last..m.start]);
+            string.push_str(replacement);
+            last = m.end;
+        }
+        let pos = string.len();
+        string.push_str(&text[last..]);
+
+        self.text.set_string(string);
+        self.selection.set_pos(pos);
+        self.after_replace(mgr);
+        count
+    }
+
+    fn after_replace(&mut self, mgr: &mut Manager) {
+        self.edit_x_coord = None;
+        self.last_edit = LastEdit::None;
+        self.prepare_text();
+        self.find.update(self.text.text());
+        self.update_spelling();
+        self.set_view_offset_from_edit_pos();
+        mgr.redraw(self.id());
+    }
+
+    /// Set a spell checker (inline)
+    ///
+    /// Misspelled words are underlined with a wavy line. Within an
+    /// [`EditBox`], suggestions for the word under the cursor are offered on
+    /// [`Command::Spelling`] or right-click.
+    ///
+    /// The checker may be shared between multiple fields. Spell checking is
+    /// not used in password mode.
+    #[inline]
+    pub fn with_spell_checker(mut self, checker: Rc<RefCell<dyn SpellChecker>>) -> Self {
+        self.spell_checker = Some(checker);
+        self.update_spelling();
+        self
+    }
+
+    /// Set or remove the spell checker
+    pub fn set_spell_checker(
+        &mut self,
+        checker: Option<Rc<RefCell<dyn SpellChecker>>>,
+    ) -> TkAction {
+        self.spell_checker = checker;
+        self.update_spelling();
+        TkAction::REDRAW
+    }
+
+    /// Re-check spelling
+    ///
+    /// This happens automatically on edits, but must be called manually if
+    /// the spell checker's dictionary is changed by other means.
+    pub fn check_spelling(&mut self) -> TkAction {
+        self.update_spelling();
+        TkAction::REDRAW
+    }
+
+    /// Get the byte ranges of misspelled words
+    pub fn misspelled(&self) -> &[Range<usize>] {
+        &self.misspelled
+    }
+
+    fn update_spelling(&mut self) {
+        self.misspelled.clear();
+        if let Some(ref checker) = self.spell_checker {
+            if !self.password {
+                self.misspelled = find_misspelled(&*checker.borrow(), self.text.text());
+            }
+        }
+    }
+
+    /// Get the misspelled word at the edit position, if any
+    fn misspelled_at_cursor(&self) -> Option<Range<usize>> {
+        let pos = self.selection.edit_pos();
+        let mut iter = self.misspelled.iter();
+        iter.find(|range| range.start <= pos && pos <= range.end)
+            .cloned()
+    }
+
+    /// Replace the word at `range` as a single undo step
+    fn replace_word(&mut self, mgr: &mut Manager, range: Range<usize>, word: &str) {
+        self.save_state();
+        self.text.replace_range(range.clone(), word);
+        self.selection.set_pos(range.start + word.len());
+        self.after_replace(mgr);
+    }
+
+    fn add_to_dictionary(&mut self, word: &str) {
+        if let Some(ref checker) = self.spell_checker {
+            checker.borrow_mut().add_word(word);
+        }
+        self.update_spelling();
+    }
+
+    fn select_current_match(&mut self) {
+        if let Some(i) = self.find.current {
+            let range = self.find.matches[i].clone();
+            self.selection.set_sel_pos(range.start);
+            self.selection.set_edit_pos(range.end);
+            self.edit_x_coord = None;
+            self.last_edit = LastEdit::None;
+            self.set_view_offset_from_edit_pos();
+        }
+    }
+
+    /// Enable code mode
+    ///
+    /// This enables indentation via Tab and Shift+Tab, auto-indent on Return,
+    /// highlighting of the current line and drawing with
+    /// [`EditField::set_effects`].
+    pub(crate) fn set_code_mode(&mut self, code: bool) {
+        self.code = code;
+    }
+
+    /// Set colours and effects used to draw the text (code mode only)
+    ///
+    /// These are ignored while a selection is drawn.
+    pub(crate) fn set_effects(&mut self, effects: Vec<Effect<Colour>>) {
+        self.effects = effects;
+    }
+
+    /// Access the text object
+    pub(crate) fn text_object(&self) -> &Text<String> {
+        &self.text
+    }
+
+    /// Get the view offset (scroll position)
+    pub(crate) fn view_offset(&self) -> Offset {
+        self.view_offset
+    }
+
+    fn draw_current_line(&self, draw_handle: &mut dyn DrawHandle) {
+        let pos = self.selection.edit_pos();
+        let start = match self.text.find_line(pos) {
+            Some((_, range)) => range.start,
+            None => return,
+        };
+        if let Some(marker) = self.text.text_glyph_pos(start).next_back() {
+            let y1: i32 = (marker.pos.1 - marker.ascent).cast_floor();
+            let y2: i32 = (marker.pos.1 - marker.descent).cast_ceil();
+            let rect = self.rect();
+            let line = Rect::new(
+                Coord(rect.pos.0, rect.pos.1 + y1 - self.view_offset.1),
+                Size(rect.size.0, y2 - y1),
+            );
+            if let Some(line) = rect.intersection(&line) {
+                draw_handle.edit_line_highlight(line);
+            }
+        }
+    }
+
+    /// Indent or outdent all lines touched by the selection
+    ///
+    /// The affected lines are selected afterwards. Returns true if the text
+    /// was changed.
+    fn indent_lines(&mut self, outdent: bool) -> bool {
+        let text = self.text.text();
+        let Range { start, end } = line_range(text, self.selection.range());
+        let string = indent_lines(&text[start..end], outdent);
+        if string == text[start..end] {
+            return false;
+        }
+
+        self.save_state();
+        self.last_edit = LastEdit::None;
+        self.text.replace_range(start..end, &string);
+        self.selection.set_sel_pos(start);
+        self.selection.set_edit_pos(start + string.len());
+        self.edit_x_coord = None;
+        true
+    }
+
+    fn current_state(&self) -> UndoState {
+        UndoState {
+            text: self.text.clone_string(),
+            edit_pos: self.selection.edit_pos(),
+            sel_pos: self.selection.sel_pos(),
+        }
+    }
+
+    /// Save the current state to the undo history (before an edit)
+    fn save_state(&mut self) {
+        if self.password {
+            // The secret must not be retained in history
+            return;
+        }
+        let state = self.current_state();
+        self.history.push(state);
+    }
+
+    /// Save or clear history (according to `keep_history`) before replacing
+    /// the text programmatically
+    fn save_or_clear_history(&mut self) {
+        if self.keep_history {
+            self.save_state();
+        } else {
+            self.history.clear();
+        }
+        self.last_edit = LastEdit::None;
+    }
+
+    fn restore_state(&mut self, state: UndoState) {
+        self.text.set_string(state.text);
+        self.selection.set_edit_pos(state.edit_pos);
+        self.selection.set_sel_pos(state.sel_pos);
+        self.edit_x_coord = None;
+        self.last_edit = LastEdit::None;
+    }
+
     // returns true on success, false on unhandled event
     fn received_char(&mut self, mgr: &mut Manager, c: char) -> bool {
         if !self.editable {
@@ -653,7 +2004,7 @@ impl<G: EditGuard> EditField<G> {
         let selection = self.selection.range();
         let have_sel = selection.start < selection.end;
         if self.last_edit != LastEdit::Insert || have_sel {
-            self.old_state = Some((self.text.clone_string(), pos, self.selection.sel_pos()));
+            self.save_state();
             self.last_edit = LastEdit::Insert;
         }
         if have_sel {
@@ -666,7 +2017,7 @@ impl<G: EditGuard> EditField<G> {
             self.selection.set_pos(pos + c.len_utf8());
         }
         self.edit_x_coord = None;
-        self.text.prepare();
+        self.prepare_text();
         self.set_view_offset_from_edit_pos();
         mgr.redraw(self.id());
         true
@@ -704,9 +2055,19 @@ impl<G: EditGuard> EditField<G> {
                 }
             }
             Command::Return if shift || !self.multi_line => Action::Activate,
+            Command::Return if self.code => {
+                // Auto-indent: copy the leading whitespace of the current line
+                string = format!("\n{}", line_indent(self.text.text(), pos));
+                Action::Insert(&string, LastEdit::Insert)
+            }
             Command::Return if self.multi_line => {
                 Action::Insert('\n'.encode_utf8(&mut buf), LastEdit::Insert)
             }
+            Command::Tab if self.code && (shift || have_sel) => match self.indent_lines(shift) {
+                true => Action::Edit,
+                false => Action::None,
+            },
+            Command::Tab if self.code => Action::Insert(INDENT, LastEdit::Insert),
             Command::Tab => Action::Insert('\t'.encode_utf8(&mut buf), LastEdit::Insert),
             Command::Left => {
                 let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
@@ -724,6 +2085,9 @@ impl<G: EditGuard> EditField<G> {
                     .map(|pos| Action::Move(pos, None))
                     .unwrap_or(Action::None)
             }
+            // Word-wise navigation would reveal the structure of a password:
+            Command::WordLeft if self.password => Action::Move(0, None),
+            Command::WordRight if self.password => Action::Move(self.text.str_len(), None),
             Command::WordLeft => {
                 let mut iter = self.text.text()[0..pos].split_word_bound_indices();
                 let mut p = iter.next_back().map(|(index, _)| index).unwrap_or(0);
@@ -839,6 +2203,8 @@ impl<G: EditGuard> EditField<G> {
                     .unwrap_or(0);
                 Action::Delete(prev..pos)
             }
+            Command::DelWord if self.password => Action::Delete(pos..self.text.str_len()),
+            Command::DelWordBack if self.password => Action::Delete(0..pos),
             Command::DelWord => {
                 let next = self.text.text()[pos..]
                     .split_word_bound_indices()
@@ -866,6 +2232,7 @@ impl<G: EditGuard> EditField<G> {
                 shift = true; // hack
                 Action::Move(self.text.str_len(), None)
             }
+            Command::Cut | Command::Copy if self.password => Action::Unhandled,
             Command::Cut if have_sel => {
                 mgr.set_clipboard((self.text.text()[selection.clone()]).into());
                 Action::Delete(selection.clone())
@@ -876,38 +2243,24 @@ impl<G: EditGuard> EditField<G> {
             }
             Command::Paste => {
                 if let Some(content) = mgr.get_clipboard() {
-                    let mut end = content.len();
-                    if !self.multi_line {
-                        // We cut the content short on control characters and
-                        // ignore them (preventing line-breaks and ignoring any
-                        // actions such as recursive-paste).
-                        for (i, c) in content.char_indices() {
-                            if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
-                                end = i;
-                                break;
-                            }
-                        }
-                    }
-
                     string = content;
-                    Action::Insert(&string[0..end], LastEdit::Paste)
+                    Action::Insert(self.paste_filter(&string), LastEdit::Paste)
                 } else {
                     Action::None
                 }
             }
             Command::Undo | Command::Redo => {
-                // TODO: maintain full edit history (externally?)
-                if let Some((state, pos2, sel_pos)) = self.old_state.as_mut() {
-                    self.text.swap_string(state);
-                    self.selection.set_edit_pos(*pos2);
-                    *pos2 = pos;
-                    let pos = *sel_pos;
-                    *sel_pos = self.selection.sel_pos();
-                    self.selection.set_sel_pos(pos);
-                    self.edit_x_coord = None;
-                    self.last_edit = LastEdit::None;
+                let current = self.current_state();
+                let state = match key {
+                    Command::Undo => self.history.undo(current),
+                    _ => self.history.redo(current),
+                };
+                if let Some(state) = state {
+                    self.restore_state(state);
+                    Action::Edit
+                } else {
+                    Action::None
                 }
-                Action::Edit
             }
             _ => Action::Unhandled,
         };
@@ -920,16 +2273,14 @@ impl<G: EditGuard> EditField<G> {
             Action::Insert(s, edit) => {
                 let mut pos = pos;
                 if have_sel {
-                    self.old_state =
-                        Some((self.text.clone_string(), pos, self.selection.sel_pos()));
+                    self.save_state();
                     self.last_edit = edit;
 
                     self.text.replace_range(selection.clone(), s);
                     pos = selection.start;
                 } else {
                     if self.last_edit != edit {
-                        self.old_state =
-                            Some((self.text.clone_string(), pos, self.selection.sel_pos()));
+                        self.save_state();
                         self.last_edit = edit;
                     }
 
@@ -941,8 +2292,7 @@ impl<G: EditGuard> EditField<G> {
             }
             Action::Delete(sel) => {
                 if self.last_edit != LastEdit::Delete {
-                    self.old_state =
-                        Some((self.text.clone_string(), pos, self.selection.sel_pos()));
+                    self.save_state();
                     self.last_edit = LastEdit::Delete;
                 }
 
@@ -957,6 +2307,8 @@ impl<G: EditGuard> EditField<G> {
                     self.selection.set_empty();
                 }
                 self.edit_x_coord = x_coord;
+                // Moving the cursor ends grouping of edits for undo:
+                self.last_edit = LastEdit::None;
                 mgr.redraw(self.id());
                 EditAction::None
             }
@@ -964,7 +2316,7 @@ impl<G: EditGuard> EditField<G> {
 
         let mut set_offset = self.selection.edit_pos() != pos;
         if !self.text.required_action().is_ready() {
-            self.text.prepare();
+            self.prepare_text();
             set_offset = true;
             mgr.redraw(self.id());
         }
@@ -975,18 +2327,68 @@ impl<G: EditGuard> EditField<G> {
         result
     }
 
+    /// Set the primary selection from a non-empty selection
+    ///
+    /// This is not used in password mode.
+    fn update_primary(&mut self, mgr: &mut Manager) {
+        if !self.selection.is_empty() && !self.password {
+            let range = self.selection.range();
+            mgr.set_primary(self.text.text()[range].into());
+        }
+    }
+
+    /// Paste the primary selection at `coord`
+    fn paste_primary(&mut self, mgr: &mut Manager, coord: Coord) -> Response<G::Msg> {
+        let content = match mgr.get_primary() {
+            Some(content) => content,
+            None => return Response::None,
+        };
+        mgr.request_char_focus(self.id());
+        self.set_edit_pos_from_coord(mgr, coord);
+        self.selection.set_empty();
+        let content = self.paste_filter(&content);
+        if content.is_empty() {
+            return Response::None;
+        }
+
+        let pos = self.selection.edit_pos();
+        self.save_state();
+        self.last_edit = LastEdit::Paste;
+        self.text.replace_range(pos..pos, content);
+        self.selection.set_pos(pos + content.len());
+        self.after_replace(mgr);
+        Response::update_or_msg(G::edit(self, mgr))
+    }
+
+    /// Filter pasted content
+    ///
+    /// For single-line fields, we cut the content short on control characters
+    /// and ignore them (preventing line-breaks and ignoring any actions such
+    /// as recursive-paste).
+    fn paste_filter<'s>(&self, content: &'s str) -> &'s str {
+        if !self.multi_line {
+            for (i, c) in content.char_indices() {
+                if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
+                    return &content[0..i];
+                }
+            }
+        }
+        content
+    }
+
     fn set_edit_pos_from_coord(&mut self, mgr: &mut Manager, coord: Coord) {
         let rel_pos = (coord - self.rect().pos + self.view_offset).into();
-        self.selection
-            .set_edit_pos(self.text.text_index_nearest(rel_pos));
+        let index = self.display_text().text_index_nearest(rel_pos);
+        self.selection.set_edit_pos(self.from_display(index));
         self.set_view_offset_from_edit_pos();
         self.edit_x_coord = None;
+        self.last_edit = LastEdit::None;
         mgr.redraw(self.id());
     }
 
     fn pan_delta(&mut self, mgr: &mut Manager, delta: Offset) -> bool {
         let bounds = Vec2::from(self.text.env().bounds);
-        let max_offset = (self.required - bounds).ceil();
+        let max_offset = (self.required() - bounds).ceil();
         let max_offset = Offset::from(max_offset).max(Offset::ZERO);
         let new_offset = (self.view_offset - delta).min(max_offset).max(Offset::ZERO);
         if new_offset != self.view_offset {
@@ -1002,9 +2404,10 @@ impl<G: EditGuard> EditField<G> {
     ///
     /// A redraw is assumed since edit_pos moved.
     fn set_view_offset_from_edit_pos(&mut self) {
-        let edit_pos = self.selection.edit_pos();
-        if let Some(marker) = self.text.text_glyph_pos(edit_pos).next_back() {
-            let bounds = Vec2::from(self.text.env().bounds);
+        let edit_pos = self.to_display(self.selection.edit_pos());
+        let text = self.display_text();
+        if let Some(marker) = text.text_glyph_pos(edit_pos).next_back() {
+            let bounds = Vec2::from(text.env().bounds);
             let min_x = marker.pos.0 - bounds.0;
             let min_y = marker.pos.1 - marker.descent - bounds.1;
             let max_x = marker.pos.0;
@@ -1012,7 +2415,7 @@ impl<G: EditGuard> EditField<G> {
             let min = Offset(min_x.cast_ceil(), min_y.cast_ceil());
             let max = Offset(max_x.cast_floor(), max_y.cast_floor());
 
-            let max_offset = (self.required - bounds).ceil();
+            let max_offset = (self.required() - bounds).ceil();
             let max_offset = Offset::from(max_offset).max(Offset::ZERO);
             let max = max.min(max_offset);
 
@@ -1029,8 +2432,12 @@ impl<G: EditGuard> HasStr for EditField<G> {
 
 impl<G: EditGuard> HasString for EditField<G> {
     fn set_string(&mut self, string: String) -> TkAction {
+        self.save_or_clear_history();
         let avail = self.core.rect.size;
         let action = kas::text::util::set_string_and_prepare(&mut self.text, string, avail);
+        self.update_spelling();
+        self.update_mask();
+        self.find.update(self.text.text());
         let _ = G::update(self);
         action
     }
@@ -1040,6 +2447,20 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
     type Msg = G::Msg;
 
     fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        if self.primary.pre_event(&event) {
+            self.update_primary(mgr);
+        }
+        let selection = self.selection.range();
+        let r = self.handle_event(mgr, event);
+        if self.primary.post_event(self.selection.range() != selection) {
+            self.update_primary(mgr);
+        }
+        r
+    }
+}
+
+impl<G: EditGuard + 'static> EditField<G> {
+    fn handle_event(&mut self, mgr: &mut Manager, event: Event) -> Response<G::Msg> {
         match event {
             Event::Activate => {
                 mgr.request_char_focus(self.id());
@@ -1057,11 +2478,19 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
                 EditAction::None => Response::None,
                 EditAction::Unhandled => Response::Unhandled,
                 EditAction::Activate => Response::none_or_msg(G::activate(self, mgr)),
-                EditAction::Edit => Response::update_or_msg(G::edit(self, mgr)),
+                EditAction::Edit => {
+                    self.find.update(self.text.text());
+                    self.update_spelling();
+                    Response::update_or_msg(G::edit(self, mgr))
+                }
             },
             Event::ReceivedCharacter(c) => match self.received_char(mgr, c) {
                 false => Response::Unhandled,
-                true => Response::update_or_msg(G::edit(self, mgr)),
+                true => {
+                    self.find.update(self.text.text());
+                    self.update_spelling();
+                    Response::update_or_msg(G::edit(self, mgr))
+                }
             },
             Event::Scroll(delta) => {
                 let delta2 = match delta {
@@ -1078,6 +2507,11 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
                     Response::Unhandled
                 }
             }
+            Event::PressStart {
+                source: PressSource::Mouse(MouseButton::Middle, _),
+                coord,
+                ..
+            } if self.editable => self.paste_primary(mgr, coord),
             event => match self.input_handler.handle(mgr, self.id(), event) {
                 TextInputAction::None => Response::None,
                 TextInputAction::Unhandled => Response::Unhandled,
@@ -1093,7 +2527,10 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
                     if clear {
                         self.selection.set_empty();
                     }
-                    if repeats > 1 {
+                    if repeats > 1 && self.password {
+                        self.selection.set_sel_pos(0);
+                        self.selection.set_edit_pos(self.text.str_len());
+                    } else if repeats > 1 {
                         self.selection.expand(&self.text, repeats);
                     }
                     Response::None
@@ -1102,3 +2539,149 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
         }
     }
 }
+
+#[test]
+fn find_state() {
+    assert_eq!(match_len_ignore_case("Straße", "STRASSE"), None);
+    assert_eq!(match_len_ignore_case("ÄBC def", "äb"), Some(3));
+    assert_eq!(match_len_ignore_case("ab", "abc"), None);
+    assert_eq!(match_len_ignore_case("abc", ""), Some(0));
+
+    let text = "Ab ab aB äb";
+    let mut find = FindState::default();
+    find.update(text);
+    assert!(find.matches.is_empty());
+
+    find.query = "ab".to_string();
+    find.update(text);
+    assert_eq!(find.matches, vec![0..2, 3..5, 6..8]);
+    assert_eq!(find.current, None);
+
+    find.match_case = true;
+    find.update(text);
+    assert_eq!(find.matches, vec![3..5]);
+
+    find.query = "Äb".to_string();
+    find.match_case = false;
+    find.update(text);
+    assert_eq!(find.matches, vec![9..12]);
+}
+
+#[test]
+fn paste_filter() {
+    // Used when pasting the clipboard or primary selection (middle-click)
+    let edit = EditField::new("");
+    assert_eq!(edit.paste_filter("ab\ncd"), "ab");
+    assert_eq!(edit.paste_filter("a\u{9b}b"), "a");
+    assert_eq!(edit.paste_filter("äb"), "äb");
+    let edit = EditField::new("").multi_line(true);
+    assert_eq!(edit.paste_filter("ab\ncd"), "ab\ncd");
+}
+
+#[test]
+fn undo_stack() {
+    let state = |text: &str| UndoState {
+        text: text.to_string(),
+        edit_pos: text.len(),
+        sel_pos: text.len(),
+    };
+    let texts =
+        |stack: &UndoStack| -> Vec<String> { stack.undo.iter().map(|s| s.text.clone()).collect() };
+
+    // The oldest entries are evicted when the depth is reached
+    let mut stack = UndoStack::default();
+    stack.set_depth(2);
+    stack.push(state("a"));
+    stack.push(state("ab"));
+    stack.push(state("abc"));
+    assert_eq!(texts(&stack), vec!["ab", "abc"]);
+    stack.set_depth(1);
+    assert_eq!(texts(&stack), vec!["abc"]);
+
+    // Undo then redo returns to the current state
+    let r = stack.undo(state("abcd")).unwrap();
+    assert_eq!(r.text, "abc");
+    assert!(stack.undo(state("abc")).is_none());
+    let r = stack.redo(state("abc")).unwrap();
+    assert_eq!(r.text, "abcd");
+    assert!(stack.redo(state("abcd")).is_none());
+
+    // A new edit invalidates redo history
+    let _ = stack.undo(state("abcd")).unwrap();
+    stack.push(state("abc"));
+    assert!(stack.redo(state("abcx")).is_none());
+
+    // Depth zero disables undo
+    stack.set_depth(0);
+    assert!(stack.undo.is_empty());
+    stack.push(state("a"));
+    assert!(stack.undo(state("ab")).is_none());
+}
+
+#[test]
+fn keep_history() {
+    // This is the history step of HasString::set_string (the remainder of
+    // which requires fonts for text preparation)
+    let mut edit = EditField::new("a");
+    edit.history.push(edit.current_state());
+    edit.save_or_clear_history();
+    assert!(edit.history.undo.is_empty());
+
+    edit.set_keep_history(true);
+    edit.save_or_clear_history();
+    let state = edit.history.undo(edit.current_state()).unwrap();
+    assert_eq!(state.text, "a");
+}
+
+#[test]
+fn password_mask() {
+    // "e" + combining acute accent and an emoji are single graphemes
+    let text = "aä\u{1F600}e\u{301}";
+    let starts = grapheme_starts(text);
+    assert_eq!(starts, vec![0, 1, 3, 7]);
+
+    let m = PASSWORD_MASK.len_utf8();
+    assert_eq!(mask_index(&starts, 0), 0);
+    assert_eq!(mask_index(&starts, 3), 2 * m);
+    assert_eq!(mask_index(&starts, 7), 3 * m);
+    assert_eq!(mask_index(&starts, text.len()), 4 * m);
+
+    assert_eq!(unmask_index(&starts, 0, text.len()), 0);
+    assert_eq!(unmask_index(&starts, 2 * m, text.len()), 3);
+    assert_eq!(unmask_index(&starts, 3 * m, text.len()), 7);
+    assert_eq!(unmask_index(&starts, 4 * m, text.len()), text.len());
+
+    // Each boundary maps to the mask and back
+    for i in starts.iter().cloned().chain(std::iter::once(text.len())) {
+        assert_eq!(unmask_index(&starts, mask_index(&starts, i), text.len()), i);
+    }
+
+    assert!(grapheme_starts("").is_empty());
+    assert_eq!(mask_index(&[], 0), 0);
+    assert_eq!(unmask_index(&[], 0, 0), 0);
+}
+
+#[test]
+fn placeholder_size() {
+    use kas::layout::HeadlessSizeHandle;
+
+    let mut size_handle = HeadlessSizeHandle::new(1.0);
+    let horiz = AxisInfo::new(false, None);
+    let mut edit = EditField::new("abc");
+    let rules = edit.size_rules(&mut size_handle, horiz);
+
+    // The ideal width fits the placeholder; the minimum width is unaffected
+    let mut edit = EditField::new("abc").with_placeholder("user@example.com");
+    let p_rules = edit.size_rules(&mut size_handle, horiz);
+    assert_eq!(p_rules.min_size(), rules.min_size());
+    assert!(p_rules.ideal_size() > rules.ideal_size());
+
+    let mut label = super::Label::new("user@example.com");
+    let label_rules = label.size_rules(&mut size_handle, horiz);
+    assert_eq!(p_rules.ideal_size(), label_rules.ideal_size());
+
+    // A short placeholder does not reduce the ideal width
+    let mut edit = EditField::new("abc").with_placeholder("a");
+    let p_rules = edit.size_rules(&mut size_handle, horiz);
+    assert_eq!(p_rules, rules);
+}
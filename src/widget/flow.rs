@@ -0,0 +1,280 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A wrapping row with run-time adjustable contents
+
+use std::ops::{Index, IndexMut};
+
+use kas::layout::{self, RulesSetter, RulesSolver};
+use kas::{event, prelude::*};
+
+/// A flow of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Flow`] type.
+pub type BoxFlow<M> = Flow<Box<dyn Widget<Msg = M>>>;
+
+/// A generic flow widget
+///
+/// Child widgets are set out in a row, left-to-right (or right-to-left; see
+/// [`kas::dir::LayoutDir`]), wrapping onto new lines when the available width
+/// is exhausted. This is useful for toolbars and tag clouds. The height of a
+/// flow depends on its width.
+///
+/// As with [`List`], this is essentially a [`Vec`] which also implements the
+/// [`Widget`] trait; [`BoxFlow`] parameterises `W = Box<dyn Widget>`.
+///
+/// For fixed configurations of child widgets, the `flow` layout of
+/// [`make_widget`] can be used instead.
+///
+/// Configuring, resizing, drawing and event handling are all O(n) in the
+/// number of children.
+///
+/// [`List`]: super::List
+/// [`make_widget`]: ../macros/index.html#the-make_widget-macro
+#[derive(Clone, Default, Debug, Widget)]
+#[handler(send=noauto, msg=(usize, <W as event::Handler>::Msg))]
+#[widget(children=noauto)]
+pub struct Flow<W: Widget> {
+    first_id: WidgetId,
+    #[widget_core]
+    core: CoreData,
+    widgets: Vec<W>,
+    data: layout::DynGridStorage,
+}
+
+impl<W: Widget> WidgetChildren for Flow<W> {
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.first_id
+    }
+    fn record_first_id(&mut self, id: WidgetId) {
+        self.first_id = id;
+    }
+    #[inline]
+    fn num_children(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+}
+
+impl<W: Widget> Layout for Flow<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let len = self.widgets.len();
+        let mut solver = layout::FlowSolver::new(axis, len, &mut self.data);
+        for (n, child) in self.widgets.iter_mut().enumerate() {
+            solver.for_child(&mut self.data, n, |axis| {
+                child.size_rules(size_handle, axis)
+            });
+        }
+        solver.finish(&mut self.data)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let len = self.widgets.len();
+        let mut setter = layout::FlowSetter::<Vec<i32>, _>::new(rect, len, align, &mut self.data);
+
+        for (n, child) in self.widgets.iter_mut().enumerate() {
+            let align = AlignHints::NONE.with_dir(align.dir);
+            child.set_rect(mgr, setter.child_rect(&mut self.data, n), align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        for child in &self.widgets {
+            if let Some(id) = child.find_id(coord) {
+                return Some(id);
+            }
+        }
+
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        let rect = draw_handle.target_rect();
+        for child in &self.widgets {
+            if child.rect().intersection(&rect).is_some() {
+                child.draw(draw_handle, mgr, disabled);
+            }
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for Flow<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for (i, child) in self.widgets.iter_mut().enumerate() {
+                if id <= child.id() {
+                    let r = child.send(mgr, id, event);
+                    return match Response::try_from(r) {
+                        Ok(r) => r,
+                        Err(msg) => Response::Msg((i, msg)),
+                    };
+                }
+            }
+        }
+
+        Response::Unhandled
+    }
+}
+
+impl<W: Widget> Flow<W> {
+    /// Construct a new instance
+    pub fn new(widgets: Vec<W>) -> Self {
+        Flow {
+            first_id: Default::default(),
+            core: Default::default(),
+            widgets,
+            data: Default::default(),
+        }
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.widgets.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements to be inserted
+    /// into the flow. See documentation of [`Vec::reserve`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.widgets.reserve(additional);
+    }
+
+    /// Remove all child widgets
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn clear(&mut self) -> TkAction {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        self.widgets.clear();
+        action
+    }
+
+    /// Append a child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, widget: W) -> TkAction {
+        self.widgets.push(widget);
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Returns `None` if there are no children.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self) -> (Option<W>, TkAction) {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        (self.widgets.pop(), action)
+    }
+
+    /// Inserts a child widget position `index`
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
+        self.widgets.insert(index, widget);
+        TkAction::RECONFIGURE
+    }
+
+    /// Removes the child widget at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        let r = self.widgets.remove(index);
+        (r, TkAction::RECONFIGURE)
+    }
+
+    /// Replace the child at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
+        std::mem::swap(&mut widget, &mut self.widgets[index]);
+        (widget, TkAction::RECONFIGURE)
+    }
+
+    /// Append child widgets from an iterator
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widgets
+    /// are added.
+    pub fn extend<T: IntoIterator<Item = W>>(&mut self, iter: T) -> TkAction {
+        let len = self.widgets.len();
+        self.widgets.extend(iter);
+        match len == self.widgets.len() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    /// Retain only widgets satisfying predicate `f`
+    ///
+    /// See documentation of [`Vec::retain`].
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widgets
+    /// are removed.
+    pub fn retain<F: FnMut(&W) -> bool>(&mut self, f: F) -> TkAction {
+        let len = self.widgets.len();
+        self.widgets.retain(f);
+        match len == self.widgets.len() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    /// Iterate over childern
+    pub fn iter(&self) -> impl Iterator<Item = &W> {
+        self.widgets.iter()
+    }
+}
+
+impl<W: Widget> Index<usize> for Flow<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for Flow<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index]
+    }
+}
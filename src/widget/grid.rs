@@ -0,0 +1,428 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A grid with run-time adjustable contents
+
+use std::ops::{Index, IndexMut};
+
+use kas::layout::{self, GridChildInfo, RulesSetter, RulesSolver};
+use kas::{event, prelude::*};
+
+/// A grid of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Grid`] type.
+pub type BoxGrid<M> = Grid<Box<dyn Widget<Msg = M>>>;
+
+/// Number of columns, rows and children with column- and row-spans
+#[derive(Clone, Copy, Debug, Default)]
+struct GridDim {
+    cols: usize,
+    rows: usize,
+    col_spans: usize,
+    row_spans: usize,
+}
+
+/// A generic grid widget
+///
+/// Each child is assigned a cell position via [`GridChildInfo`], optionally
+/// spanning multiple columns and/or rows. Cells may be empty; multiple
+/// children should not occupy the same cell. The number of columns and rows is
+/// determined by the children.
+///
+/// As with [`List`], this is essentially a [`Vec`] (of `(GridChildInfo, W)`
+/// pairs) which also implements the [`Widget`] trait. Children are stored (and
+/// messages indexed) in insertion order, which need not match cell order.
+///
+/// For fixed configurations of child widgets, the `grid` layout of
+/// [`make_widget`] can be used instead.
+///
+/// Configuring, resizing, drawing and event handling are all O(n) in the
+/// number of children.
+///
+/// # Panics
+///
+/// Methods adding children or changing cell positions panic if any child's
+/// [`GridChildInfo`] spans no columns or rows (`col_end <= col` or
+/// `row_end <= row`).
+///
+/// [`List`]: super::List
+/// [`make_widget`]: ../macros/index.html#the-make_widget-macro
+#[derive(Clone, Default, Debug, Widget)]
+#[handler(send=noauto, msg=(usize, <W as event::Handler>::Msg))]
+#[widget(children=noauto)]
+pub struct Grid<W: Widget> {
+    first_id: WidgetId,
+    #[widget_core]
+    core: CoreData,
+    widgets: Vec<(GridChildInfo, W)>,
+    data: layout::DynGridStorage,
+    dim: GridDim,
+}
+
+impl<W: Widget> WidgetChildren for Grid<W> {
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.first_id
+    }
+    fn record_first_id(&mut self, id: WidgetId) {
+        self.first_id = id;
+    }
+    #[inline]
+    fn num_children(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.1.as_widget())
+    }
+    #[inline]
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets.get_mut(index).map(|w| w.1.as_widget_mut())
+    }
+}
+
+impl<W: Widget> Layout for Grid<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let dim = (self.dim.cols, self.dim.rows);
+        let span = (SizeRules::EMPTY, 0, 0);
+        let spans = (
+            vec![span; self.dim.col_spans],
+            vec![span; self.dim.row_spans],
+        );
+        let mut solver = layout::GridSolver::<Vec<_>, Vec<_>, _>::new_with_spans(
+            axis,
+            dim,
+            spans,
+            &mut self.data,
+        );
+        for (info, child) in self.widgets.iter_mut() {
+            solver.for_child(&mut self.data, *info, |axis| {
+                child.size_rules(size_handle, axis)
+            });
+        }
+        solver.finish(&mut self.data)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let dim = (self.dim.cols, self.dim.rows);
+        let mut setter =
+            layout::GridSetter::<Vec<_>, Vec<_>, _>::new(rect, dim, align, &mut self.data);
+
+        for (info, child) in self.widgets.iter_mut() {
+            let align = AlignHints::NONE.with_dir(align.dir);
+            child.set_rect(mgr, setter.child_rect(&mut self.data, *info), align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        for (_, child) in &self.widgets {
+            if let Some(id) = child.find_id(coord) {
+                return Some(id);
+            }
+        }
+
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        let rect = draw_handle.target_rect();
+        for (_, child) in &self.widgets {
+            if child.rect().intersection(&rect).is_some() {
+                child.draw(draw_handle, mgr, disabled);
+            }
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for Grid<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            for (i, (_, child)) in self.widgets.iter_mut().enumerate() {
+                if id <= child.id() {
+                    let r = child.send(mgr, id, event);
+                    return match Response::try_from(r) {
+                        Ok(r) => r,
+                        Err(msg) => Response::Msg((i, msg)),
+                    };
+                }
+            }
+        }
+
+        Response::Unhandled
+    }
+}
+
+impl<W: Widget> Grid<W> {
+    /// Construct a new instance
+    pub fn new(widgets: Vec<(GridChildInfo, W)>) -> Self {
+        let mut grid = Grid {
+            first_id: Default::default(),
+            core: Default::default(),
+            widgets,
+            data: Default::default(),
+            dim: Default::default(),
+        };
+        grid.calc_dim();
+        grid
+    }
+
+    fn calc_dim(&mut self) {
+        let mut dim = GridDim::default();
+        for (info, _) in &self.widgets {
+            assert!(
+                info.col_end > info.col && info.row_end > info.row,
+                "Grid: child spans no cells: {:?}",
+                info
+            );
+            dim.cols = dim.cols.max(usize::conv(info.col_end));
+            dim.rows = dim.rows.max(usize::conv(info.row_end));
+            if info.col_end > info.col + 1 {
+                dim.col_spans += 1;
+            }
+            if info.row_end > info.row + 1 {
+                dim.row_spans += 1;
+            }
+        }
+        self.dim = dim;
+    }
+
+    /// Get the number of columns and rows
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.dim.cols, self.dim.rows)
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.widgets.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements to be inserted
+    /// into the grid. See documentation of [`Vec::reserve`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.widgets.reserve(additional);
+    }
+
+    /// Remove all child widgets
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn clear(&mut self) -> TkAction {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        self.widgets.clear();
+        self.calc_dim();
+        action
+    }
+
+    /// Append a child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, info: GridChildInfo, widget: W) -> TkAction {
+        self.widgets.push((info, widget));
+        self.calc_dim();
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Returns `None` if there are no children.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self) -> (Option<(GridChildInfo, W)>, TkAction) {
+        let action = match self.widgets.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        let r = self.widgets.pop();
+        self.calc_dim();
+        (r, action)
+    }
+
+    /// Inserts a child widget position `index`
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn insert(&mut self, index: usize, info: GridChildInfo, widget: W) -> TkAction {
+        self.widgets.insert(index, (info, widget));
+        self.calc_dim();
+        TkAction::RECONFIGURE
+    }
+
+    /// Removes the child widget at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> ((GridChildInfo, W), TkAction) {
+        let r = self.widgets.remove(index);
+        self.calc_dim();
+        (r, TkAction::RECONFIGURE)
+    }
+
+    /// Replace the child at `index`
+    ///
+    /// The child's cell position is unchanged.
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
+        std::mem::swap(&mut widget, &mut self.widgets[index].1);
+        (widget, TkAction::RECONFIGURE)
+    }
+
+    /// Get the cell position of the child at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn cell_info(&self, index: usize) -> GridChildInfo {
+        self.widgets[index].0
+    }
+
+    /// Move the child at `index` to a new cell position
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [resize action](Manager::send_action).
+    pub fn set_cell_info(&mut self, index: usize, info: GridChildInfo) -> TkAction {
+        self.widgets[index].0 = info;
+        self.calc_dim();
+        TkAction::RESIZE
+    }
+
+    /// Append child widgets from an iterator
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widgets
+    /// are added.
+    pub fn extend<T: IntoIterator<Item = (GridChildInfo, W)>>(&mut self, iter: T) -> TkAction {
+        let len = self.widgets.len();
+        self.widgets.extend(iter);
+        self.calc_dim();
+        match len == self.widgets.len() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    /// Retain only widgets satisfying predicate `f`
+    ///
+    /// See documentation of [`Vec::retain`].
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widgets
+    /// are removed.
+    pub fn retain<F: FnMut(&(GridChildInfo, W)) -> bool>(&mut self, f: F) -> TkAction {
+        let len = self.widgets.len();
+        self.widgets.retain(f);
+        self.calc_dim();
+        match len == self.widgets.len() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        }
+    }
+
+    /// Iterate over childern, with cell positions
+    pub fn iter(&self) -> impl Iterator<Item = &(GridChildInfo, W)> {
+        self.widgets.iter()
+    }
+}
+
+impl<W: Widget> Index<usize> for Grid<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index].1
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for Grid<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index].1
+    }
+}
+
+#[test]
+fn dimensions() {
+    use super::Filler;
+    let cell = GridChildInfo::new;
+    let mut grid = Grid::new(vec![
+        (cell(0, 0).with_span(2, 1), Filler::new()),
+        (cell(2, 0).with_span(1, 3), Filler::new()),
+    ]);
+    assert_eq!(grid.dimensions(), (3, 3));
+    assert_eq!((grid.dim.col_spans, grid.dim.row_spans), (1, 1));
+
+    let _ = grid.insert(0, cell(0, 3), Filler::new());
+    assert_eq!(grid.dimensions(), (3, 4));
+    assert_eq!(grid.cell_info(1), cell(0, 0).with_span(2, 1));
+
+    let _ = grid.set_cell_info(1, cell(4, 0).with_span(2, 2));
+    assert_eq!(grid.dimensions(), (6, 4));
+    assert_eq!((grid.dim.col_spans, grid.dim.row_spans), (1, 2));
+
+    let _ = grid.remove(2);
+    assert_eq!(grid.dimensions(), (6, 4));
+    assert_eq!((grid.dim.col_spans, grid.dim.row_spans), (1, 1));
+    let _ = grid.clear();
+    assert_eq!(grid.dimensions(), (0, 0));
+}
+
+#[test]
+#[should_panic]
+fn empty_span() {
+    let info = GridChildInfo {
+        col: 1,
+        col_end: 1,
+        row: 0,
+        row_end: 1,
+    };
+    let _ = Grid::new(vec![(info, super::Filler::new())]);
+}
+
+#[test]
+fn layout() {
+    use super::Filler;
+    use kas::layout::{solve_headless, HeadlessSizeHandle};
+    let cell = GridChildInfo::new;
+    let mut grid = Grid::new(vec![
+        (cell(0, 0).with_span(2, 1), Filler::new()),
+        (cell(0, 1), Filler::new()),
+        (cell(1, 1), Filler::new()),
+    ]);
+    let mut size_handle = HeadlessSizeHandle::new(1.0);
+    let rects = solve_headless(&mut grid, &mut size_handle, Size(200, 100));
+    let rect = |x, y, w, h| Some(Rect::new(Coord(x, y), Size(w, h)));
+    assert_eq!(rects.get_path(&[0]), rect(0, 0, 200, 50));
+    assert_eq!(rects.get_path(&[1]), rect(0, 50, 100, 50));
+    assert_eq!(rects.get_path(&[2]), rect(100, 50, 100, 50));
+
+    // Move the spanning child to span both rows of the second column
+    let _ = grid.set_cell_info(0, cell(1, 0).with_span(1, 2));
+    let _ = grid.set_cell_info(2, cell(0, 0));
+    let rects = solve_headless(&mut grid, &mut size_handle, Size(200, 100));
+    assert_eq!(rects.get_path(&[0]), rect(100, 0, 100, 100));
+    assert_eq!(rects.get_path(&[1]), rect(0, 50, 100, 50));
+    assert_eq!(rects.get_path(&[2]), rect(0, 0, 100, 50));
+}
@@ -6,24 +6,68 @@
 //! Text widgets
 
 use kas::draw::TextClass;
+use kas::event::components::{PrimaryTracker, TextInput, TextInputAction};
+use kas::event::Command;
+use kas::geom::Vec2;
 use kas::text::format::{EditableText, FormattableText};
+use kas::text::{Elide, ElidedText, SelectionHelper};
 use kas::{event, prelude::*};
+use unicode_segmentation::GraphemeCursor;
 
 /// A text label
 ///
 /// This type is generic over the text type. Some aliases are available:
 /// [`StrLabel`], [`StringLabel`], [`AccelLabel`].
+///
+/// Labels are optionally selectable; see [`Label::selectable`].
+///
+/// Labels optionally support elision; see [`Label::with_elide`].
+///
+/// A `Label<Markdown>` draws links as formatted text only; for clickable
+/// links use `MarkdownLabel` (requires the `markdown` feature).
 #[derive(Clone, Default, Debug, Widget)]
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
 pub struct Label<T: FormattableText + 'static> {
     #[widget_core]
     core: CoreData,
     label: Text<T>,
+    selectable: bool,
+    selection: SelectionHelper,
+    input_handler: TextInput,
+    primary: PrimaryTracker,
+    elide: ElidedText,
+}
+
+impl<T: FormattableText + 'static> WidgetConfig for Label<T> {
+    fn cursor_icon(&self) -> event::CursorIcon {
+        if self.selectable {
+            event::CursorIcon::Text
+        } else {
+            event::CursorIcon::Default
+        }
+    }
 }
 
 impl<T: FormattableText + 'static> Layout for Label<T> {
     #[inline]
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
-        size_handle.text_bound(&mut self.label, TextClass::Label, axis)
+        let class = self.class();
+        let rules = size_handle.text_bound(&mut self.label, class, axis);
+        if self.elide.mode() == Elide::None {
+            return rules;
+        }
+        if axis.is_vertical() {
+            return rules;
+        }
+        // Allow shrinking down to the ellipsis
+        let min = i32::conv_ceil(self.elide.min_width(&self.label));
+        SizeRules::new(
+            min.min(rules.min_size()),
+            rules.ideal_size(),
+            rules.margins(),
+            rules.stretch(),
+        )
     }
 
     fn set_rect(&mut self, _: &mut Manager, rect: Rect, align: AlignHints) {
@@ -32,33 +76,34 @@ impl<T: FormattableText + 'static> Layout for Label<T> {
             env.set_bounds(rect.size.into());
             env.set_align(align.unwrap_or(Align::Default, Align::Centre));
         });
+        self.update_elide();
     }
 
     #[cfg(feature = "min_spec")]
     default fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
-        draw_handle.text_effects(
-            self.core.rect.pos,
-            Offset::ZERO,
-            &self.label,
-            TextClass::Label,
-        );
+        if self.draw_special(draw_handle) {
+            return;
+        }
+        draw_handle.text_effects(self.core.rect.pos, Offset::ZERO, &self.label, self.class());
     }
     #[cfg(not(feature = "min_spec"))]
     fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
-        draw_handle.text_effects(
-            self.core.rect.pos,
-            Offset::ZERO,
-            &self.label,
-            TextClass::Label,
-        );
+        if self.draw_special(draw_handle) {
+            return;
+        }
+        draw_handle.text_effects(self.core.rect.pos, Offset::ZERO, &self.label, self.class());
     }
 }
 
 #[cfg(feature = "min_spec")]
 impl Layout for AccelLabel {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, _: bool) {
+        if self.draw_special(draw_handle) {
+            return;
+        }
         let state = mgr.show_accel_labels();
-        draw_handle.text_accel(self.core.rect.pos, &self.label, state, TextClass::Label);
+        let class = self.class();
+        draw_handle.text_accel(self.core.rect.pos, &self.label, state, class);
     }
 }
 
@@ -66,13 +111,69 @@ impl Layout for AccelLabel {
 #[cfg(feature = "min_spec")]
 impl<'a> Layout for Label<&'a str> {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
-        draw_handle.text(self.core.rect.pos, &self.label, TextClass::Label);
+        if self.draw_special(draw_handle) {
+            return;
+        }
+        draw_handle.text(self.core.rect.pos, &self.label, self.class());
     }
 }
 #[cfg(feature = "min_spec")]
 impl Layout for StringLabel {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
-        draw_handle.text(self.core.rect.pos, &self.label, TextClass::Label);
+        if self.draw_special(draw_handle) {
+            return;
+        }
+        draw_handle.text(self.core.rect.pos, &self.label, self.class());
+    }
+}
+
+impl<T: FormattableText + 'static> event::Handler for Label<T> {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        if !self.selectable {
+            return Response::Unhandled;
+        }
+        if self.primary.pre_event(&event) {
+            self.update_primary(mgr);
+        }
+        let selection = self.selection.range();
+        let r = match event {
+            Event::LostSelFocus => {
+                self.selection.set_empty();
+                mgr.redraw(self.id());
+                Response::None
+            }
+            Event::Command(cmd, shift) => self.control_key(mgr, cmd, shift),
+            event => match self.input_handler.handle(mgr, self.id(), event) {
+                TextInputAction::None | TextInputAction::Pan(_) => Response::None,
+                TextInputAction::Unhandled => Response::Unhandled,
+                TextInputAction::Cursor(coord, anchor, clear, repeats) => {
+                    let rel_pos = (coord - self.core.rect.pos).into();
+                    // When elided, hit-test the displayed text
+                    let index = match self.elide.text() {
+                        Some(text) => self.elide.to_original(text.text_index_nearest(rel_pos)),
+                        None => self.label.text_index_nearest(rel_pos),
+                    };
+                    self.selection.set_edit_pos(index);
+                    if anchor {
+                        self.selection.set_anchor();
+                    }
+                    if clear {
+                        self.selection.set_empty();
+                    }
+                    if repeats > 1 {
+                        self.selection.expand(&self.label, repeats);
+                    }
+                    mgr.redraw(self.id());
+                    Response::None
+                }
+            },
+        };
+        if self.primary.post_event(self.selection.range() != selection) {
+            self.update_primary(mgr);
+        }
+        r
     }
 }
 
@@ -103,16 +204,189 @@ impl<T: FormattableText + 'static> Label<T> {
         Label {
             core: Default::default(),
             label: Text::new_multi(label),
+            selectable: false,
+            selection: Default::default(),
+            input_handler: Default::default(),
+            primary: Default::default(),
+            elide: Default::default(),
         }
     }
 
+    /// Set the elision mode (inline)
+    ///
+    /// When not [`Elide::None`], the label is restricted to a single line.
+    /// If the label is assigned less width than its text requires, the text
+    /// is shortened on grapheme boundaries and an ellipsis ("…") inserted.
+    /// Formatting is not applied to elided text. Only the visible part of any
+    /// selection is shown.
+    ///
+    /// The full text remains available via [`HasStr::get_str`].
+    /// Default: [`Elide::None`].
+    #[inline]
+    pub fn with_elide(mut self, mode: Elide) -> Self {
+        self.elide.set_mode(mode);
+        self
+    }
+
+    /// Set the elision mode
+    pub fn set_elide(&mut self, mode: Elide) -> TkAction {
+        self.elide.set_mode(mode);
+        TkAction::RESIZE
+    }
+
+    /// Get whether the text is currently elided
+    #[inline]
+    pub fn is_elided(&self) -> bool {
+        self.elide.is_elided()
+    }
+
+    /// Set whether text is selectable (inline)
+    ///
+    /// When selectable, text may be selected with the mouse or touch, or via
+    /// the keyboard (while the label has focus), and copied to the clipboard.
+    /// Selected text is also set as the primary selection once selection
+    /// completes. Default: not selectable.
+    #[inline]
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Get whether text is selectable
+    #[inline]
+    pub fn is_selectable(&self) -> bool {
+        self.selectable
+    }
+
+    /// Set whether text is selectable
+    pub fn set_selectable(&mut self, selectable: bool) -> TkAction {
+        self.selectable = selectable;
+        if !selectable && !self.selection.is_empty() {
+            self.selection.set_pos(0);
+            return TkAction::REDRAW;
+        }
+        TkAction::empty()
+    }
+
+    /// Get the selected text
+    ///
+    /// This is empty when nothing is selected. When the text is elided, this
+    /// excludes text hidden by the ellipsis.
+    pub fn selected_str(&self) -> &str {
+        let range = self.elide.clamp_range(self.selection.range());
+        &self.label.as_str()[range]
+    }
+
     /// Set text in an existing `Label`
     ///
+    /// Any selection is cleared.
+    ///
     /// Note: this must not be called before fonts have been initialised
     /// (usually done by the theme when the main loop starts).
     pub fn set_text(&mut self, text: T) -> TkAction {
-        kas::text::util::set_text_and_prepare(&mut self.label, text, self.core.rect.size)
+        self.selection.set_pos(0);
+        let action =
+            kas::text::util::set_text_and_prepare(&mut self.label, text, self.core.rect.size);
+        self.update_elide();
+        action
+    }
+
+    /// Set the primary selection from a non-empty selection
+    fn update_primary(&mut self, mgr: &mut Manager) {
+        if !self.selection.is_empty() {
+            mgr.set_primary(self.selected_str().into());
+        }
+    }
+
+    /// The class of text used, depending on the elision mode
+    fn class(&self) -> TextClass {
+        match self.elide.mode() {
+            Elide::None => TextClass::Label,
+            _ => TextClass::LabelFixed,
+        }
+    }
+
+    fn update_elide(&mut self) {
+        let width = Vec2::from(self.core.rect.size).0;
+        self.elide.update(&self.label, width);
     }
+
+    /// Draw elided or selected text, if applicable
+    fn draw_special(&self, draw_handle: &mut dyn DrawHandle) -> bool {
+        if let Some(text) = self.elide.text() {
+            let range = self.elide.to_elided_range(self.selection.range());
+            if range.is_empty() {
+                draw_handle.text(self.core.rect.pos, text, self.class());
+            } else {
+                let bounds = text.env().bounds.into();
+                draw_handle.text_selected(
+                    self.core.rect.pos,
+                    bounds,
+                    Offset::ZERO,
+                    text,
+                    range,
+                    self.class(),
+                );
+            }
+        } else if !self.selection.is_empty() {
+            let bounds = self.label.env().bounds.into();
+            draw_handle.text_selected(
+                self.core.rect.pos,
+                bounds,
+                Offset::ZERO,
+                &self.label,
+                self.selection.range(),
+                self.class(),
+            );
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn control_key(&mut self, mgr: &mut Manager, cmd: Command, shift: bool) -> Response<VoidMsg> {
+        let string = self.label.as_str();
+        let pos = self.selection.edit_pos();
+        let pos = match cmd {
+            Command::Left => prev_grapheme(string, pos),
+            Command::Right => next_grapheme(string, pos),
+            Command::Home => self.label.find_line(pos).map(|r| r.1.start).unwrap_or(0),
+            Command::End => self
+                .label
+                .find_line(pos)
+                .map(|r| r.1.end)
+                .unwrap_or(string.len()),
+            Command::SelectAll => {
+                self.selection.set_sel_pos(0);
+                self.selection.set_edit_pos(string.len());
+                mgr.redraw(self.id());
+                return Response::None;
+            }
+            Command::Copy if !self.selection.is_empty() => {
+                mgr.set_clipboard(self.selected_str().into());
+                return Response::None;
+            }
+            _ => return Response::Unhandled,
+        };
+        self.selection.set_edit_pos(pos);
+        if !shift {
+            self.selection.set_empty();
+        }
+        mgr.redraw(self.id());
+        Response::None
+    }
+}
+
+/// The grapheme boundary before `pos`, or 0
+fn prev_grapheme(string: &str, pos: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(pos, string.len(), true);
+    cursor.prev_boundary(string, 0).unwrap().unwrap_or(0)
+}
+
+/// The grapheme boundary after `pos`, or `pos` at the end of `string`
+fn next_grapheme(string: &str, pos: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(pos, string.len(), true);
+    cursor.next_boundary(string, 0).unwrap().unwrap_or(pos)
 }
 
 impl<T: FormattableText + 'static> HasStr for Label<T> {
@@ -123,7 +397,11 @@ impl<T: FormattableText + 'static> HasStr for Label<T> {
 
 impl<T: FormattableText + EditableText + 'static> HasString for Label<T> {
     fn set_string(&mut self, string: String) -> TkAction {
-        kas::text::util::set_string_and_prepare(&mut self.label, string, self.core.rect.size)
+        self.selection.set_pos(0);
+        let action =
+            kas::text::util::set_string_and_prepare(&mut self.label, string, self.core.rect.size);
+        self.update_elide();
+        action
     }
 }
 
@@ -159,6 +437,40 @@ impl SetAccel for AccelLabel {
         if self.label.text().keys() != string.keys() {
             action |= TkAction::RECONFIGURE;
         }
-        action | kas::text::util::set_text_and_prepare(&mut self.label, string, self.core.rect.size)
+        action | self.set_text(string)
     }
 }
+
+#[test]
+fn grapheme_movement() {
+    // "e" + combining acute accent, then a family emoji (ZWJ sequence)
+    let s = "ae\u{301}\u{1F468}\u{200D}\u{1F469}b";
+    assert_eq!(next_grapheme(s, 0), 1);
+    assert_eq!(next_grapheme(s, 1), 4);
+    assert_eq!(next_grapheme(s, 4), 15);
+    assert_eq!(next_grapheme(s, 15), 16);
+    assert_eq!(next_grapheme(s, 16), 16);
+    assert_eq!(prev_grapheme(s, 16), 15);
+    assert_eq!(prev_grapheme(s, 15), 4);
+    assert_eq!(prev_grapheme(s, 4), 1);
+    assert_eq!(prev_grapheme(s, 0), 0);
+}
+
+#[test]
+fn selection() {
+    let mut label = StrLabel::new("héllo wörld").selectable(true);
+    assert!(label.is_selectable());
+    assert_eq!(label.selected_str(), "");
+
+    label.selection.set_sel_pos(7);
+    label.selection.set_edit_pos(13);
+    assert_eq!(label.selected_str(), "wörld");
+    // The selection may be made in either direction
+    label.selection.set_sel_pos(6);
+    label.selection.set_edit_pos(0);
+    assert_eq!(label.selected_str(), "héllo");
+
+    assert_eq!(label.set_selectable(false), TkAction::REDRAW);
+    assert_eq!(label.selected_str(), "");
+    assert_eq!(label.set_selectable(false), TkAction::empty());
+}
@@ -7,7 +7,7 @@
 
 use std::ops::{Index, IndexMut};
 
-use kas::dir::{Down, Right};
+use kas::dir::{Down, LayoutDir, Right};
 use kas::layout::{self, RulesSetter, RulesSolver};
 use kas::{event, prelude::*};
 
@@ -96,6 +96,7 @@ pub struct List<D: Directional, W: Widget> {
     widgets: Vec<W>,
     data: layout::DynRowStorage,
     direction: D,
+    layout_dir: LayoutDir,
 }
 
 impl<D: Directional, W: Widget> WidgetChildren for List<D, W> {
@@ -134,18 +135,19 @@ impl<D: Directional, W: Widget> Layout for List<D, W> {
 
     fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         self.core.rect = rect;
+        self.layout_dir = align.dir;
         let dim = (self.direction, self.widgets.len());
         let mut setter = layout::RowSetter::<D, Vec<i32>, _>::new(rect, dim, align, &mut self.data);
 
         for (n, child) in self.widgets.iter_mut().enumerate() {
-            let align = AlignHints::default();
+            let align = AlignHints::NONE.with_dir(align.dir);
             child.set_rect(mgr, setter.child_rect(&mut self.data, n), align);
         }
     }
 
     fn spatial_range(&self) -> (usize, usize) {
         let last = self.num_children().wrapping_sub(1);
-        match self.direction.is_reversed() {
+        match self.layout_dir.is_reversed(self.direction) {
             false => (0, last),
             true => (last, 0),
         }
@@ -156,7 +158,7 @@ impl<D: Directional, W: Widget> Layout for List<D, W> {
             return None;
         }
 
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         if let Some(child) = solver.find_child(&self.widgets, coord) {
             return child.find_id(coord);
         }
@@ -166,7 +168,7 @@ impl<D: Directional, W: Widget> Layout for List<D, W> {
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         solver.for_children(&self.widgets, draw_handle.target_rect(), |w| {
             w.draw(draw_handle, mgr, disabled)
         });
@@ -204,6 +206,7 @@ impl<D: Directional + Default, W: Widget> List<D, W> {
             widgets,
             data: Default::default(),
             direction: Default::default(),
+            layout_dir: Default::default(),
         }
     }
 }
@@ -217,6 +220,7 @@ impl<D: Directional, W: Widget> List<D, W> {
             widgets,
             data: Default::default(),
             direction,
+            layout_dir: Default::default(),
         }
     }
 
@@ -225,6 +229,17 @@ impl<D: Directional, W: Widget> List<D, W> {
         self.direction.as_direction()
     }
 
+    /// Set the direction of contents
+    ///
+    /// Triggers a [resize action](Manager::send_action) if changed.
+    pub fn set_direction(&mut self, direction: D) -> TkAction {
+        if direction.as_direction() == self.direction.as_direction() {
+            return TkAction::empty();
+        }
+        self.direction = direction;
+        TkAction::RESIZE
+    }
+
     /// True if there are no child widgets
     pub fn is_empty(&self) -> bool {
         self.widgets.is_empty()
@@ -0,0 +1,93 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Localised label
+
+use super::AccelLabel;
+use kas::i18n::Message;
+use kas::{event, prelude::*};
+
+/// A localised label
+///
+/// The label's text is resolved from a [`Message`] through the active
+/// catalogue (see [`kas::i18n`]) whenever the widget is configured, including
+/// after switching language via [`Manager::set_language`].
+///
+/// As with [`AccelLabel`], the text may include accelerator-key markers.
+#[derive(Clone, Debug, Default, Widget)]
+#[widget(config=noauto)]
+pub struct LocalisedLabel {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    label: AccelLabel,
+    message: Message,
+}
+
+impl LocalisedLabel {
+    /// Construct from a [`Message`] or message identifier
+    ///
+    /// Until configured, the untranslated message is shown.
+    pub fn new<M: Into<Message>>(message: M) -> Self {
+        let message = message.into();
+        LocalisedLabel {
+            core: Default::default(),
+            label: AccelLabel::new(message.resolve(None).into()),
+            message,
+        }
+    }
+
+    /// Get the message
+    #[inline]
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Set the message
+    ///
+    /// The message is resolved immediately using the active catalogue.
+    pub fn set_message(&mut self, mgr: &mut Manager, message: Message) {
+        self.message = message;
+        let text = mgr.translate(&self.message);
+        *mgr |= self.label.set_accel_string(text.into());
+    }
+
+    /// Get the accelerator keys
+    #[inline]
+    pub fn keys(&self) -> &[event::VirtualKeyCode] {
+        self.label.keys()
+    }
+}
+
+impl WidgetConfig for LocalisedLabel {
+    fn configure(&mut self, mgr: &mut Manager) {
+        let text = mgr.translate(&self.message);
+        // This is called before resizing, so the result may be ignored
+        let _ = self.label.set_text(text.into());
+    }
+}
+
+impl Layout for LocalisedLabel {
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.label.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.label.set_rect(mgr, rect, align);
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.label.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl HasStr for LocalisedLabel {
+    fn get_str(&self) -> &str {
+        self.label.get_str()
+    }
+}
@@ -0,0 +1,272 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Markdown label with hyperlinks
+
+use std::ops::Range;
+
+use kas::draw::TextClass;
+use kas::event::{self, Command, GrabMode};
+use kas::geom::Vec2;
+use kas::prelude::*;
+use kas::text::format::{Markdown, MarkdownError};
+use kas::text::TextDisplay;
+use pulldown_cmark::{Event as MdEvent, Parser, Tag};
+
+#[derive(Clone, Debug)]
+struct Link {
+    range: Range<usize>,
+    target: String,
+}
+
+/// Find links in `input`, locating each within the formatted `text`
+///
+/// Since formatting only removes mark-up and inserts separators, the text of
+/// each parser event appears (in order) within `text`. We locate each in turn,
+/// thus the range of a link is that of the events within it. Links without
+/// text are skipped.
+fn find_links(input: &str, text: &str) -> Vec<Link> {
+    let mut links = vec![];
+    // Start (within text) and target of the current link
+    let mut current: Option<(Option<usize>, String)> = None;
+    let mut index = 0;
+    for event in Parser::new(input) {
+        match event {
+            MdEvent::Start(Tag::Link(_, target, _)) => {
+                current = Some((None, target.to_string()));
+            }
+            MdEvent::Text(s) | MdEvent::Code(s) if !s.is_empty() => {
+                if let Some(start) = text[index..].find(&*s).map(|i| index + i) {
+                    index = start + s.len();
+                    if let Some((ref mut link_start, _)) = current {
+                        link_start.get_or_insert(start);
+                    }
+                }
+            }
+            MdEvent::End(Tag::Link(..)) => {
+                if let Some((Some(start), target)) = current.take() {
+                    let range = start..index;
+                    links.push(Link { range, target });
+                }
+            }
+            _ => (),
+        }
+    }
+    links
+}
+
+/// A Markdown label with clickable links
+///
+/// Links may be activated by clicking. When the label has keyboard navigation
+/// focus, one link is highlighted: the arrow keys and [`Command::Link`] cycle
+/// through links (Shift+[`Command::Link`] cycles in reverse) and the
+/// highlighted link is activated via Return or Space.
+///
+/// On activation, the link's target is returned as a message, to be handled
+/// by the parent (e.g. by opening a URL or navigating internally).
+///
+/// Only text over links is interactive; the mouse cursor changes to a hand
+/// when hovering over a link.
+///
+/// This is a separate widget from `Label<Markdown>` since a [`Label`] never
+/// emits messages (its message type is [`VoidMsg`] for all text types).
+/// Link targets are not retained by [`Markdown`], hence the input is parsed
+/// a second time to locate links.
+///
+/// [`Label`]: super::Label
+#[derive(Clone, Debug, Widget)]
+#[widget(config=noauto)]
+#[handler(handle=noauto)]
+pub struct MarkdownLabel {
+    #[widget_core]
+    core: CoreData,
+    label: Text<Markdown>,
+    links: Vec<Link>,
+    focus: usize,
+    press: Option<usize>,
+}
+
+impl MarkdownLabel {
+    /// Construct by parsing Markdown `input`
+    pub fn new(input: &str) -> Result<Self, MarkdownError> {
+        let text = Markdown::new(input)?;
+        let links = find_links(input, text.as_str());
+        Ok(MarkdownLabel {
+            core: Default::default(),
+            label: Text::new_multi(text),
+            links,
+            focus: 0,
+            press: None,
+        })
+    }
+
+    /// Set text by parsing Markdown `input`
+    ///
+    /// Note: this must not be called before fonts have been initialised
+    /// (usually done by the theme when the main loop starts).
+    pub fn set_markdown(&mut self, input: &str) -> Result<TkAction, MarkdownError> {
+        let text = Markdown::new(input)?;
+        let links = find_links(input, text.as_str());
+        let mut action = TkAction::empty();
+        if links.is_empty() != self.links.is_empty() {
+            // key_nav has changed
+            action |= TkAction::RECONFIGURE;
+        }
+        self.links = links;
+        self.focus = 0;
+        self.press = None;
+        let size = self.core.rect.size;
+        Ok(action | kas::text::util::set_text_and_prepare(&mut self.label, text, size))
+    }
+
+    /// Get the number of links
+    #[inline]
+    pub fn num_links(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Get the target of link `index`, if any
+    pub fn link_target(&self, index: usize) -> Option<&str> {
+        self.links.get(index).map(|link| link.target.as_str())
+    }
+
+    /// Find the link under `coord`, if any
+    pub fn link_at(&self, coord: Coord) -> Option<usize> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        let pos = Vec2::from(coord - self.rect().pos);
+        let text: &TextDisplay = self.label.as_ref();
+        self.links.iter().position(|link| {
+            text.highlight_lines(link.range.clone())
+                .iter()
+                .any(|(p1, p2)| Vec2::from(*p1).le(pos) && pos.lt(Vec2::from(*p2)))
+        })
+    }
+
+    fn cycle_focus(&mut self, mgr: &mut Manager, reverse: bool) -> Response<String> {
+        let len = self.links.len();
+        if len == 0 {
+            return Response::Unhandled;
+        }
+        self.focus = match reverse {
+            false => (self.focus + 1) % len,
+            true => (self.focus + len - 1) % len,
+        };
+        mgr.redraw(self.id());
+        Response::None
+    }
+
+    fn activate(&mut self, index: usize) -> Response<String> {
+        match self.links.get(index) {
+            Some(link) => {
+                self.focus = index;
+                Response::Msg(link.target.clone())
+            }
+            None => Response::Unhandled,
+        }
+    }
+}
+
+impl WidgetConfig for MarkdownLabel {
+    fn key_nav(&self) -> bool {
+        !self.links.is_empty()
+    }
+
+    fn cursor_icon(&self) -> event::CursorIcon {
+        // Since find_id only matches links, this applies only over links
+        event::CursorIcon::Hand
+    }
+}
+
+impl Layout for MarkdownLabel {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        size_handle.text_bound(&mut self.label, TextClass::Label, axis)
+    }
+
+    fn set_rect(&mut self, _: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.label.update_env(|env| {
+            env.set_bounds(rect.size.into());
+            env.set_align(align.unwrap_or(Align::Default, Align::Centre));
+        });
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.link_at(coord).map(|_| self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, _: bool) {
+        let pos = self.core.rect.pos;
+        if mgr.nav_focus(self.id()) {
+            if let Some(link) = self.links.get(self.focus) {
+                let bounds = self.label.env().bounds.into();
+                let ranges = [link.range.clone()];
+                draw_handle.text_highlights(
+                    pos,
+                    bounds,
+                    Offset::ZERO,
+                    self.label.as_ref(),
+                    &ranges,
+                );
+            }
+        }
+        draw_handle.text_effects(pos, Offset::ZERO, &self.label, TextClass::Label);
+    }
+}
+
+impl event::Handler for MarkdownLabel {
+    type Msg = String;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Activate => self.activate(self.focus),
+            Event::Command(cmd, shift) => match cmd {
+                Command::Left | Command::Up => self.cycle_focus(mgr, true),
+                Command::Right | Command::Down => self.cycle_focus(mgr, false),
+                Command::Link => self.cycle_focus(mgr, shift),
+                _ => Response::Unhandled,
+            },
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                match self.link_at(coord) {
+                    Some(index) => {
+                        mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                        self.press = Some(index);
+                        Response::None
+                    }
+                    None => Response::Unhandled,
+                }
+            }
+            Event::PressMove { .. } => Response::None,
+            Event::PressEnd { end_id, coord, .. } => match self.press.take() {
+                Some(index) if end_id == Some(self.id()) && self.link_at(coord) == Some(index) => {
+                    self.activate(index)
+                }
+                _ => Response::None,
+            },
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+impl HasStr for MarkdownLabel {
+    fn get_str(&self) -> &str {
+        self.label.as_str()
+    }
+}
+
+#[test]
+fn links() {
+    let input = "See docs: [docs](url1), or [more *docs*](url2).";
+    let text = Markdown::new(input).unwrap();
+    let text = text.as_str();
+    let links = find_links(input, text);
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].range.start, text.find("docs,").unwrap());
+    assert_eq!(&text[links[0].range.clone()], "docs");
+    assert_eq!(links[0].target, "url1");
+    assert_eq!(&text[links[1].range.clone()], "more docs");
+    assert_eq!(links[1].target, "url2");
+}
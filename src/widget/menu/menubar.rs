@@ -55,9 +55,9 @@ impl<W: Menu, D: Directional> Layout for MenuBar<W, D> {
         self.bar.size_rules(size_handle, axis)
     }
 
-    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, _: AlignHints) {
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         self.core_data_mut().rect = rect;
-        let align = AlignHints::new(Some(Align::Default), Some(Align::Default));
+        let align = AlignHints::new(Some(Align::Default), Some(Align::Default)).with_dir(align.dir);
         self.bar.set_rect(mgr, rect, align);
     }
 
@@ -181,7 +181,7 @@ impl<W: Menu<Msg = M>, D: Directional, M> event::Handler for MenuBar<W, D> {
             Event::Command(cmd, _) => {
                 // Arrow keys can switch to the next / previous menu.
                 let is_vert = self.bar.direction().is_vertical();
-                let reverse = self.bar.direction().is_reversed()
+                let reverse = mgr.layout_dir().is_reversed(self.bar.direction())
                     ^ match cmd {
                         Command::Left if !is_vert => true,
                         Command::Right if !is_vert => false,
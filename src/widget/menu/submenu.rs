@@ -75,7 +75,7 @@ impl<D: Directional, W: Menu> SubMenu<D, W> {
             let id = mgr.add_popup(kas::Popup {
                 id: self.list.id(),
                 parent: self.id(),
-                direction: self.direction.as_direction(),
+                direction: mgr.layout_dir().resolve(self.direction),
             });
             self.popup_id = Some(id);
             mgr.next_nav_focus(self, false);
@@ -158,7 +158,7 @@ impl<D: Directional, M, W: Menu<Msg = M>> event::Handler for SubMenu<D, W> {
                 debug_assert_eq!(Some(id), self.popup_id);
                 self.popup_id = None;
             }
-            Event::Command(cmd, _) => match (self.direction.as_direction(), cmd) {
+            Event::Command(cmd, _) => match (mgr.layout_dir().resolve(self.direction), cmd) {
                 (Direction::Left, Command::Left) => self.open_menu(mgr),
                 (Direction::Right, Command::Right) => self.open_menu(mgr),
                 (Direction::Up, Command::Up) => self.open_menu(mgr),
@@ -197,7 +197,8 @@ impl<D: Directional, W: Menu> event::SendEvent for SubMenu<D, W> {
                 Response::Unhandled => match event {
                     Event::Command(key, _) if self.popup_id.is_some() => {
                         if self.popup_id.is_some() {
-                            let dir = self.direction.as_direction();
+                            // Keys are physical; resolve logical directions
+                            let dir = mgr.layout_dir().resolve(self.direction);
                             let inner_vert = self.list.direction().is_vertical();
                             let next = |mgr: &mut Manager, s, clr, rev| {
                                 if clr {
@@ -205,7 +206,7 @@ impl<D: Directional, W: Menu> event::SendEvent for SubMenu<D, W> {
                                 }
                                 mgr.next_nav_focus(s, rev);
                             };
-                            let rev = self.list.direction().is_reversed();
+                            let rev = mgr.layout_dir().is_reversed(self.list.direction());
                             use Direction::*;
                             match key {
                                 Command::Left if !inner_vert => next(mgr, self, false, !rev),
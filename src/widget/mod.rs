@@ -18,7 +18,12 @@
 //! -   [`Frame`]: a simple frame around a single child
 //! -   [`ScrollRegion`]: may be larger on the inside than the outside
 //! -   [`Stack`]: a stack of widgets in the same rect (TODO: `TabbedStack`)
+//! -   [`Overlay`]: a base widget with other widgets drawn over it
+//! -   [`Constrained`]: applies size constraints to a single child
 //! -   [`List`]: a dynamic row / column of children
+//! -   [`Responsive`]: a [`List`] whose direction depends on the available width
+//! -   [`Flow`]: a dynamic row of children, wrapping onto new lines
+//! -   [`Grid`]: a dynamic grid of children, with optional cell-spans
 //! -   [`Splitter`]: similar to [`List`] but with resizing handles
 //! -   [`Window`] is usually the root widget and has special handling for
 //!     pop-ups and callbacks
@@ -36,14 +41,19 @@
 //! -   [`CheckBox`]: a checkable box
 //! -   [`RadioBox`]: a checkable box bound to a group
 //! -   [`EditBox`]: a text-editing box
+//! -   [`CodeEdit`]: a code-editing box with line numbers and highlighting
 //! -   [`ScrollBar`]: a scrollbar
 //! -   [`Slider`]: a slider
+//! -   [`SpinBox`]: a numeric entry box with up/down buttons
 //!
 //! ## Static widgets
 //!
 //! -   [`Filler`]: an empty widget, sometimes used to fill space
 //! -   [`Separator`]: a visible bar to separate things
 //! -   [`Label`]: a simple text label
+//! -   [`LocalisedLabel`]: a label translated via the active message catalogue
+//! -   [`MarkdownLabel`]: a Markdown label with clickable links (requires the
+//!     `markdown` feature)
 //!
 //! ## Components
 //!
@@ -54,22 +64,33 @@
 
 mod button;
 mod checkbox;
+mod code_edit;
 mod combobox;
+mod constrained;
 mod dialog;
 mod drag;
 mod editbox;
 mod filler;
+mod flow;
 mod frame;
+mod grid;
 mod label;
 mod list;
+mod localised_label;
+#[cfg(feature = "markdown")]
+mod markdown_label;
 mod menu;
+mod overlay;
 mod progress;
 mod radiobox;
 mod reserve;
+mod responsive;
 mod scroll;
 mod scrollbar;
 mod separator;
 mod slider;
+mod spelling;
+mod spinbox;
 mod splitter;
 mod stack;
 mod window;
@@ -78,22 +99,33 @@ pub mod view;
 
 pub use button::TextButton;
 pub use checkbox::{CheckBox, CheckBoxBare};
+pub use code_edit::{CodeEdit, Highlighter};
 pub use combobox::ComboBox;
+pub use constrained::Constrained;
 pub use dialog::MessageBox;
 pub use drag::DragHandle;
 pub use editbox::{EditBox, EditField, EditGuard};
 pub use filler::Filler;
+pub use flow::{BoxFlow, Flow};
 pub use frame::Frame;
+pub use grid::{BoxGrid, Grid};
 pub use label::{AccelLabel, Label, StrLabel, StringLabel};
 pub use list::*;
+pub use localised_label::LocalisedLabel;
+#[cfg(feature = "markdown")]
+pub use markdown_label::MarkdownLabel;
 pub use menu::*;
+pub use overlay::{BoxOverlay, Overlay, OverlayAnchor};
 pub use progress::ProgressBar;
 pub use radiobox::{RadioBox, RadioBoxBare};
 pub use reserve::{Reserve, ReserveP};
+pub use responsive::{BoxResponsive, Responsive};
 pub use scroll::{ScrollComponent, ScrollRegion};
 pub use scrollbar::{ScrollBar, ScrollBarRegion, ScrollBars, Scrollable};
 pub use separator::Separator;
 pub use slider::{Slider, SliderType};
+pub use spelling::{SpellChecker, WordList};
+pub use spinbox::{SpinBox, SpinGuard};
 pub use splitter::*;
-pub use stack::{BoxStack, RefStack, Stack};
+pub use stack::{BoxStack, RefStack, Stack, StackTransition};
 pub use window::Window;
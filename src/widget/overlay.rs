@@ -0,0 +1,399 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widgets drawn over a base widget
+
+use kas::dir::LayoutDir;
+use kas::draw::ClipRegion;
+use kas::{event, prelude::*};
+
+/// An overlay of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Overlay`] type.
+pub type BoxOverlay<M> = Overlay<Box<dyn Widget<Msg = M>>>;
+
+/// Placement of a layer within an [`Overlay`]
+///
+/// The layer is aligned within the overlay's rect according to `horiz` and
+/// `vert`, then translated by `offset` (in logical pixels). With
+/// [`Align::Stretch`], the layer fills the available space on that axis;
+/// otherwise it takes its ideal size. As with [`AlignHints`], horizontal
+/// alignment is logical: with right-to-left layout direction, both the
+/// alignment and the horizontal offset are mirrored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OverlayAnchor {
+    /// Horizontal alignment
+    pub horiz: Align,
+    /// Vertical alignment
+    pub vert: Align,
+    /// Translation (in logical pixels)
+    pub offset: Offset,
+}
+
+impl Default for OverlayAnchor {
+    fn default() -> Self {
+        OverlayAnchor::new(Align::Centre, Align::Centre)
+    }
+}
+
+impl OverlayAnchor {
+    /// Construct with the given alignment
+    pub const fn new(horiz: Align, vert: Align) -> Self {
+        OverlayAnchor {
+            horiz,
+            vert,
+            offset: Offset::ZERO,
+        }
+    }
+
+    /// Set the offset (inline)
+    #[inline]
+    pub fn with_offset(mut self, offset: Offset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Place a layer of `ideal` size within `rect`
+    fn place(&self, rect: Rect, ideal: Size, dir: LayoutDir, scale_factor: f32) -> Rect {
+        let mut size = Size(ideal.0.min(rect.size.0), ideal.1.min(rect.size.1));
+        if self.horiz == Align::Stretch {
+            size.0 = rect.size.0;
+        }
+        if self.vert == Align::Stretch {
+            size.1 = rect.size.1;
+        }
+        let hints = AlignHints::new(Some(self.horiz), Some(self.vert)).with_dir(dir);
+        let rect = hints
+            .complete(Align::Centre, Align::Centre)
+            .aligned_rect(size, rect);
+
+        let mut x = i32::conv_nearest(self.offset.0 as f32 * scale_factor);
+        let y = i32::conv_nearest(self.offset.1 as f32 * scale_factor);
+        if dir.is_rtl() {
+            x = -x;
+        }
+        rect + Offset(x, y)
+    }
+}
+
+/// A base widget with other widgets (layers) drawn over it
+///
+/// The overlay has the size of its base widget, though it is at least as
+/// large as the minimum size of each layer. Each layer is placed according to
+/// an [`OverlayAnchor`]; layers may be translated beyond the overlay's rect
+/// (e.g. a badge overhanging a corner), although events are only received
+/// within the rects of the overlay's ancestors.
+///
+/// Layers are drawn over the base in z-order (insertion order; the last layer
+/// is topmost), each within its own clip region (see [`ClipRegion::Overlay`]).
+/// Hit-testing ([`Layout::find_id`]) respects this order: the topmost layer
+/// under the cursor receives events, otherwise the base widget.
+///
+/// Messages are tagged with the index of the child: `0` for the base and
+/// `i + 1` for layer `i`.
+///
+/// Example uses include badges, floating action buttons and in-place loading
+/// indicators.
+#[derive(Clone, Default, Debug, Widget)]
+#[handler(send=noauto, msg=(usize, <W as event::Handler>::Msg))]
+#[widget(children=noauto)]
+pub struct Overlay<W: Widget> {
+    first_id: WidgetId,
+    #[widget_core]
+    core: CoreData,
+    base: W,
+    layers: Vec<(OverlayAnchor, W)>,
+    /// Ideal size of each layer
+    ideal: Vec<Size>,
+}
+
+impl<W: Widget> WidgetChildren for Overlay<W> {
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.first_id
+    }
+    fn record_first_id(&mut self, id: WidgetId) {
+        self.first_id = id;
+    }
+    #[inline]
+    fn num_children(&self) -> usize {
+        1 + self.layers.len()
+    }
+    #[inline]
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        match index {
+            0 => Some(self.base.as_widget()),
+            i => self.layers.get(i - 1).map(|w| w.1.as_widget()),
+        }
+    }
+    #[inline]
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        match index {
+            0 => Some(self.base.as_widget_mut()),
+            i => self.layers.get_mut(i - 1).map(|w| w.1.as_widget_mut()),
+        }
+    }
+}
+
+impl<W: Widget> Layout for Overlay<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = self.base.size_rules(size_handle, axis);
+        self.ideal.resize(self.layers.len(), Size::ZERO);
+        for (i, (_, child)) in self.layers.iter_mut().enumerate() {
+            let child_rules = child.size_rules(size_handle, axis);
+            match axis.is_vertical() {
+                false => self.ideal[i].0 = child_rules.ideal_size(),
+                true => self.ideal[i].1 = child_rules.ideal_size(),
+            }
+            let min = rules.min_size().max(child_rules.min_size());
+            let ideal = rules.ideal_size().max(min);
+            rules = SizeRules::new(min, ideal, rules.margins(), rules.stretch());
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.base.set_rect(mgr, rect, align);
+
+        let scale_factor = mgr.size_handle(|sh| sh.scale_factor());
+        for (i, (anchor, child)) in self.layers.iter_mut().enumerate() {
+            let child_rect = anchor.place(rect, self.ideal[i], align.dir, scale_factor);
+            child.set_rect(mgr, child_rect, AlignHints::NONE.with_dir(align.dir));
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        for (_, child) in self.layers.iter().rev() {
+            if let Some(id) = child.find_id(coord) {
+                return Some(id);
+            }
+        }
+
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.base.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.base.draw(draw_handle, mgr, disabled);
+        let clip_rect = draw_handle.target_rect();
+        draw_layers(&self.layers, draw_handle, clip_rect, mgr, disabled);
+    }
+}
+
+/// Draw each layer in its own clip region
+///
+/// Each region is created within that of the layer below, thus is drawn above
+/// it (nesting only affects depth, not clipping). Layers are clipped to the
+/// parent's region (e.g. the viewport of a scroll region); layers entirely
+/// outside this are skipped.
+fn draw_layers<W: Widget>(
+    layers: &[(OverlayAnchor, W)],
+    draw_handle: &mut dyn DrawHandle,
+    clip_rect: Rect,
+    mgr: &event::ManagerState,
+    disabled: bool,
+) {
+    for (i, (_, child)) in layers.iter().enumerate() {
+        if let Some(rect) = child.rect().intersection(&clip_rect) {
+            let rest = &layers[i + 1..];
+            let class = ClipRegion::Overlay;
+            draw_handle.clip_region(rect, Offset::ZERO, class, &mut |draw_handle| {
+                child.draw(draw_handle, mgr, disabled);
+                draw_layers(rest, draw_handle, clip_rect, mgr, disabled);
+            });
+            return;
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for Overlay<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if !self.is_disabled() {
+            if id <= self.base.id() {
+                let r = self.base.send(mgr, id, event);
+                return match Response::try_from(r) {
+                    Ok(r) => r,
+                    Err(msg) => Response::Msg((0, msg)),
+                };
+            }
+            for (i, (_, child)) in self.layers.iter_mut().enumerate() {
+                if id <= child.id() {
+                    let r = child.send(mgr, id, event);
+                    return match Response::try_from(r) {
+                        Ok(r) => r,
+                        Err(msg) => Response::Msg((i + 1, msg)),
+                    };
+                }
+            }
+        }
+
+        Response::Unhandled
+    }
+}
+
+impl<W: Widget> Overlay<W> {
+    /// Construct with a base widget and no layers
+    pub fn new(base: W) -> Self {
+        Overlay {
+            first_id: Default::default(),
+            core: Default::default(),
+            base,
+            layers: vec![],
+            ideal: vec![],
+        }
+    }
+
+    /// Add a layer above existing layers (inline)
+    ///
+    /// This method should only be used before the widget is configured; see
+    /// also [`Overlay::push`].
+    pub fn with_layer(mut self, anchor: OverlayAnchor, widget: W) -> Self {
+        self.layers.push((anchor, widget));
+        self
+    }
+
+    /// Get a reference to the base widget
+    pub fn base(&self) -> &W {
+        &self.base
+    }
+
+    /// Get a mutable reference to the base widget
+    pub fn base_mut(&mut self) -> &mut W {
+        &mut self.base
+    }
+
+    /// True if there are no layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the number of layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Get a reference to the layer at `index`, if any
+    pub fn layer(&self, index: usize) -> Option<&W> {
+        self.layers.get(index).map(|w| &w.1)
+    }
+
+    /// Get a mutable reference to the layer at `index`, if any
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut W> {
+        self.layers.get_mut(index).map(|w| &mut w.1)
+    }
+
+    /// Add a layer above existing layers
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, anchor: OverlayAnchor, widget: W) -> TkAction {
+        self.layers.push((anchor, widget));
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the topmost layer
+    ///
+    /// Returns `None` if there are no layers.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self) -> (Option<(OverlayAnchor, W)>, TkAction) {
+        let action = match self.layers.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        (self.layers.pop(), action)
+    }
+
+    /// Inserts a layer at position `index` in the z-order
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn insert(&mut self, index: usize, anchor: OverlayAnchor, widget: W) -> TkAction {
+        self.layers.insert(index, (anchor, widget));
+        TkAction::RECONFIGURE
+    }
+
+    /// Removes the layer at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> ((OverlayAnchor, W), TkAction) {
+        let r = self.layers.remove(index);
+        (r, TkAction::RECONFIGURE)
+    }
+
+    /// Get the anchor of the layer at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn anchor(&self, index: usize) -> OverlayAnchor {
+        self.layers[index].0
+    }
+
+    /// Set the anchor of the layer at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [set-size action](Manager::send_action) if changed.
+    pub fn set_anchor(&mut self, index: usize, anchor: OverlayAnchor) -> TkAction {
+        if anchor == self.layers[index].0 {
+            return TkAction::empty();
+        }
+        self.layers[index].0 = anchor;
+        TkAction::SET_SIZE
+    }
+}
+
+#[test]
+fn place() {
+    let rect = Rect::new(Coord(10, 10), Size(100, 50));
+    let ideal = Size(20, 20);
+
+    let anchor = OverlayAnchor::new(Align::BR, Align::TL).with_offset(Offset(5, -5));
+    let r = anchor.place(rect, ideal, LayoutDir::Ltr, 2.0);
+    assert_eq!(r, Rect::new(Coord(100, 0), Size(20, 20)));
+    let r = anchor.place(rect, ideal, LayoutDir::Rtl, 1.0);
+    assert_eq!(r, Rect::new(Coord(5, 5), Size(20, 20)));
+
+    let anchor = OverlayAnchor::new(Align::Stretch, Align::Centre);
+    let r = anchor.place(rect, Size(200, 20), LayoutDir::Ltr, 1.0);
+    assert_eq!(r, Rect::new(Coord(10, 25), Size(100, 20)));
+}
+
+#[test]
+fn find_id() {
+    use crate::layout::{solve_headless, HeadlessSizeHandle};
+    use crate::widget::{Filler, ReserveP};
+
+    fn fixed(size: i32) -> ReserveP<Filler> {
+        let f: fn(&mut dyn SizeHandle, AxisInfo) -> SizeRules = match size {
+            100 => |_, _| SizeRules::fixed(100, (0, 0)),
+            _ => |_, _| SizeRules::fixed(40, (0, 0)),
+        };
+        ReserveP::new(Filler::with(Stretch::None), f)
+    }
+    let tl = OverlayAnchor::new(Align::TL, Align::TL);
+    let mut overlay = Overlay::new(fixed(100))
+        .with_layer(tl, fixed(40))
+        .with_layer(tl.with_offset(Offset(20, 20)), fixed(40));
+    let mut size_handle = HeadlessSizeHandle::new(1.0);
+    solve_headless(&mut overlay, &mut size_handle, Size(100, 100));
+
+    let base = overlay.base().inner.id();
+    let layer0 = overlay.layer(0).unwrap().inner.id();
+    let layer1 = overlay.layer(1).unwrap().inner.id();
+    assert_eq!(overlay.find_id(Coord(10, 10)), Some(layer0));
+    assert_eq!(overlay.find_id(Coord(30, 30)), Some(layer1));
+    assert_eq!(overlay.find_id(Coord(50, 50)), Some(layer1));
+    assert_eq!(overlay.find_id(Coord(70, 70)), Some(base));
+    assert_eq!(overlay.find_id(Coord(130, 30)), None);
+}
@@ -0,0 +1,316 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A row or column, chosen according to the available width
+
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use super::List;
+use kas::{event, prelude::*};
+
+/// A responsive row/column of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Responsive`] type.
+pub type BoxResponsive<M> = Responsive<Box<dyn Widget<Msg = M>>>;
+
+/// A list of widgets whose direction depends on the available width
+///
+/// This is a [`List`] with direction chosen at [`Layout::set_rect`] time
+/// according to a set of width *breakpoints*. For example, a row may be used
+/// when at least 800 (logical) pixels are available and a column otherwise:
+/// ```
+/// # use kas::dir::Direction;
+/// # use kas::event::VoidMsg;
+/// # use kas::widget::BoxResponsive;
+/// # let widgets = vec![];
+/// let list: BoxResponsive<VoidMsg> =
+///     BoxResponsive::new(Direction::Down, widgets).with_breakpoint(800.0, Direction::Right);
+/// ```
+///
+/// To avoid flapping between arrangements when the width is close to a
+/// breakpoint, the width must pass the breakpoint by a margin (the
+/// *hysteresis*) before the arrangement changes. Additionally, an arrangement
+/// is not selected when the width is less than its minimum width.
+///
+/// Changing arrangement only affects layout: child widgets are not replaced or
+/// reconfigured, thus their state (including keyboard focus and scroll
+/// position) is preserved.
+#[derive(Clone, Debug, Widget)]
+#[handler(msg = <List<Direction, W> as Handler>::Msg)]
+pub struct Responsive<W: Widget> {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    list: List<Direction, W>,
+    /// Minimum width (in logical pixels) and direction of each arrangement,
+    /// sorted by increasing width
+    arrangements: Vec<(f32, Direction)>,
+    /// Minimum width (in physical pixels) of each arrangement
+    min_widths: Vec<i32>,
+    hysteresis: f32,
+    active: usize,
+}
+
+impl<W: Widget> Responsive<W> {
+    /// Construct
+    ///
+    /// The `direction` is used when no breakpoint is reached; breakpoints may
+    /// be added with [`Responsive::with_breakpoint`].
+    pub fn new(direction: Direction, widgets: Vec<W>) -> Self {
+        Responsive {
+            core: Default::default(),
+            list: List::new_with_direction(direction, widgets),
+            arrangements: vec![(0.0, direction)],
+            min_widths: vec![],
+            hysteresis: 16.0,
+            active: 0,
+        }
+    }
+
+    /// Add a breakpoint (inline)
+    ///
+    /// The given `direction` is used when the available width is at least
+    /// `min_width` (in logical pixels) and no larger breakpoint is reached.
+    pub fn with_breakpoint(mut self, min_width: f32, direction: Direction) -> Self {
+        let index = self
+            .arrangements
+            .iter()
+            .position(|a| a.0 > min_width)
+            .unwrap_or(self.arrangements.len());
+        self.arrangements.insert(index, (min_width, direction));
+        self.min_widths.clear();
+        self
+    }
+
+    /// Set the hysteresis (inline)
+    ///
+    /// Breakpoints must be passed by this margin (in logical pixels) before the
+    /// arrangement changes. Default: 16.
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Get the current direction
+    pub fn direction(&self) -> Direction {
+        self.list.direction()
+    }
+
+    /// Remove all child widgets
+    ///
+    /// See [`List::clear`].
+    pub fn clear(&mut self) -> TkAction {
+        self.min_widths.clear();
+        self.list.clear()
+    }
+
+    /// Append a child widget
+    ///
+    /// See [`List::push`].
+    pub fn push(&mut self, widget: W) -> TkAction {
+        self.min_widths.clear();
+        self.list.push(widget)
+    }
+
+    /// Remove the last child widget
+    ///
+    /// See [`List::pop`].
+    pub fn pop(&mut self) -> (Option<W>, TkAction) {
+        self.min_widths.clear();
+        self.list.pop()
+    }
+
+    /// Inserts a child widget position `index`
+    ///
+    /// See [`List::insert`].
+    pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
+        self.min_widths.clear();
+        self.list.insert(index, widget)
+    }
+
+    /// Removes the child widget at position `index`
+    ///
+    /// See [`List::remove`].
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        self.min_widths.clear();
+        self.list.remove(index)
+    }
+
+    /// Replace the child at `index`
+    ///
+    /// See [`List::replace`].
+    pub fn replace(&mut self, index: usize, widget: W) -> (W, TkAction) {
+        self.min_widths.clear();
+        self.list.replace(index, widget)
+    }
+
+    /// Append child widgets from an iterator
+    ///
+    /// See [`List::extend`].
+    pub fn extend<T: IntoIterator<Item = W>>(&mut self, iter: T) -> TkAction {
+        self.min_widths.clear();
+        self.list.extend(iter)
+    }
+
+    /// Retain only widgets satisfying predicate `f`
+    ///
+    /// See [`List::retain`].
+    pub fn retain<F: FnMut(&W) -> bool>(&mut self, f: F) -> TkAction {
+        self.min_widths.clear();
+        self.list.retain(f)
+    }
+
+    fn set_arrangement(&mut self, index: usize) {
+        self.active = index;
+        let _ = self.list.set_direction(self.arrangements[index].1);
+    }
+
+    /// Select an arrangement for the given `width`
+    fn select(&self, width: i32, scale_factor: f32) -> usize {
+        let width = width as f32;
+        let hysteresis = self.hysteresis * scale_factor;
+        let mut index = 0;
+        for (i, (min, _)) in self.arrangements.iter().enumerate().skip(1) {
+            let min_width = self.min_widths.get(i).cloned().unwrap_or(0);
+            let threshold = match i <= self.active {
+                true => min * scale_factor - hysteresis,
+                false => min * scale_factor + hysteresis,
+            };
+            if width >= threshold && width >= min_width as f32 {
+                index = i;
+            }
+        }
+        index
+    }
+}
+
+impl<W: Widget> Layout for Responsive<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        if axis.is_horizontal() {
+            // We allow any arrangement: the minimum width is that of the
+            // narrowest and the ideal that of the widest. The active
+            // arrangement is solved last, since the list stores its rules.
+            let active = self.active;
+            let len = self.arrangements.len();
+            self.min_widths.resize(len, 0);
+            let (mut min, mut ideal) = (i32::MAX, 0);
+            let mut rules = SizeRules::EMPTY;
+            for i in (0..len).filter(|i| *i != active).chain(iter::once(active)) {
+                self.set_arrangement(i);
+                rules = self.list.size_rules(size_handle, axis);
+                self.min_widths[i] = rules.min_size();
+                min = min.min(rules.min_size());
+                ideal = ideal.max(rules.ideal_size());
+            }
+            return SizeRules::new(min, ideal, rules.margins(), rules.stretch());
+        }
+
+        if let Some(width) = axis.other() {
+            let index = self.select(width, size_handle.scale_factor());
+            if index != self.active {
+                // The list's horizontal rules must match its direction
+                self.set_arrangement(index);
+                let axis = AxisInfo::new(false, None).with_dir(axis.dir());
+                self.list.size_rules(size_handle, axis);
+            }
+        }
+        self.list.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        let scale_factor = mgr.size_handle(|sh| sh.scale_factor());
+        let index = self.select(rect.size.0, scale_factor);
+        if index != self.active {
+            // Rules were solved for another width; re-solve for this one
+            self.set_arrangement(index);
+            let list = &mut self.list;
+            let width = rect.size.0;
+            mgr.size_handle(|sh| {
+                list.size_rules(sh, AxisInfo::new(false, None).with_dir(align.dir));
+                list.size_rules(sh, AxisInfo::new(true, Some(width)).with_dir(align.dir));
+            });
+        }
+        self.core.rect = rect;
+        self.list.set_rect(mgr, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.list.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.list.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<W: Widget> std::ops::Deref for Responsive<W> {
+    type Target = List<Direction, W>;
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+impl<W: Widget> Index<usize> for Responsive<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.list[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for Responsive<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.list[index]
+    }
+}
+
+#[test]
+fn select() {
+    let mut r = BoxResponsive::<VoidMsg>::new(Direction::Down, vec![])
+        .with_breakpoint(800.0, Direction::Right)
+        .with_hysteresis(10.0);
+    assert_eq!(r.select(700, 1.0), 0);
+    assert_eq!(r.select(805, 1.0), 0);
+    assert_eq!(r.select(810, 1.0), 1);
+    assert_eq!(r.select(1610, 2.0), 0);
+
+    r.set_arrangement(1);
+    assert_eq!(r.direction(), Direction::Right);
+    assert_eq!(r.select(795, 1.0), 1);
+    assert_eq!(r.select(789, 1.0), 0);
+
+    r.min_widths = vec![100, 900];
+    assert_eq!(r.select(850, 1.0), 0);
+}
+
+#[test]
+fn modify() {
+    let mut r = BoxResponsive::<VoidMsg>::new(Direction::Down, vec![])
+        .with_breakpoint(800.0, Direction::Right);
+    r.set_arrangement(1);
+    r.min_widths = vec![100, 900];
+
+    let action = r.push(Box::new(crate::widget::Filler::new()));
+    assert_eq!(action, TkAction::RECONFIGURE);
+    assert_eq!(r.len(), 1);
+    assert!(r.min_widths.is_empty());
+    // The list's direction is still controlled by the active arrangement
+    assert_eq!(r.direction(), Direction::Right);
+    assert_eq!(r.active, 1);
+
+    r.min_widths = vec![100, 900];
+    let (_, action) = r.remove(0);
+    assert_eq!(action, TkAction::RECONFIGURE);
+    assert!(r.is_empty());
+    assert!(r.min_widths.is_empty());
+}
@@ -462,22 +462,29 @@ impl<W: Scrollable> Layout for ScrollBars<W> {
             child_size.0 -= bar_width;
         }
 
-        let child_rect = Rect::new(pos, child_size);
+        // With right-to-left layout, the vertical bar is on the left
+        let mut child_pos = pos;
+        let mut vert_x = rect.pos2().0 - bar_width;
+        if align.dir.is_rtl() && self.show_bars.1 {
+            child_pos.0 += bar_width;
+            vert_x = pos.0;
+        }
+
+        let child_rect = Rect::new(child_pos, child_size);
         self.inner.set_rect(mgr, child_rect, align);
         let max_scroll_offset = self.inner.max_scroll_offset();
+        let bar_align = AlignHints::NONE.with_dir(align.dir);
 
         if self.show_bars.0 {
-            let pos = Coord(pos.0, rect.pos2().1 - bar_width);
+            let pos = Coord(child_pos.0, rect.pos2().1 - bar_width);
             let size = Size::new(child_size.0, bar_width);
-            self.horiz_bar
-                .set_rect(mgr, Rect { pos, size }, AlignHints::NONE);
+            self.horiz_bar.set_rect(mgr, Rect { pos, size }, bar_align);
             let _ = self.horiz_bar.set_limits(max_scroll_offset.0, rect.size.0);
         }
         if self.show_bars.1 {
-            let pos = Coord(rect.pos2().0 - bar_width, pos.1);
+            let pos = Coord(vert_x, pos.1);
             let size = Size::new(bar_width, self.core.rect.size.1);
-            self.vert_bar
-                .set_rect(mgr, Rect { pos, size }, AlignHints::NONE);
+            self.vert_bar.set_rect(mgr, Rect { pos, size }, bar_align);
             let _ = self.vert_bar.set_limits(max_scroll_offset.1, rect.size.1);
         }
     }
@@ -31,6 +31,24 @@ pub trait SliderType:
     /// Also note that this method is not required to preserve precision
     /// (e.g. `u128::mul_64` may drop some low-order bits with large numbers).
     fn mul_f64(self, scalar: f64) -> Self;
+
+    /// Add `rhs`, returning `None` on overflow
+    ///
+    /// The default implementation uses `self + rhs`, which may panic or wrap
+    /// on overflow; implementations for bounded types should override this.
+    #[inline]
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    /// Subtract `rhs`, returning `None` on overflow
+    ///
+    /// The default implementation uses `self - rhs`, which may panic or wrap
+    /// on overflow; implementations for bounded types should override this.
+    #[inline]
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(self - rhs)
+    }
 }
 
 impl SliderType for f64 {
@@ -62,6 +80,12 @@ macro_rules! impl_slider_ty {
                 assert!(<$ty>::MIN as f64 <= r && r <= <$ty>::MAX as f64);
                 r as $ty
             }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_sub(self, rhs)
+            }
         }
     };
     ($ty:ty, $($tt:ty),*) => {
@@ -79,6 +103,12 @@ impl SliderType for Duration {
     fn mul_f64(self, scalar: f64) -> Self {
         self.mul_f64(scalar)
     }
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Duration::checked_add(self, rhs)
+    }
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Duration::checked_sub(self, rhs)
+    }
 }
 
 /// A slider
@@ -0,0 +1,289 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Spell checking
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+use kas::draw::TextClass;
+use kas::event::{self, GrabMode};
+use kas::i18n::Message;
+use kas::prelude::*;
+
+/// A spell checker, as used by [`EditField`](super::EditField)
+///
+/// A checker is usually shared between edit fields via
+/// `Rc<RefCell<dyn SpellChecker>>`; see
+/// [`EditField::with_spell_checker`](super::EditField::with_spell_checker).
+pub trait SpellChecker: Debug {
+    /// Check whether `word` is spelled correctly
+    fn check(&self, word: &str) -> bool;
+
+    /// Get suggested replacements for `word`
+    ///
+    /// The default implementation suggests nothing.
+    fn suggest(&self, word: &str) -> Vec<String> {
+        let _ = word;
+        vec![]
+    }
+
+    /// Add `word` to the dictionary
+    fn add_word(&mut self, word: &str);
+}
+
+/// A simple spell checker using a list of words
+///
+/// Words are matched case-insensitively. Suggestions are those words in the
+/// list one edit away (insertion, deletion, substitution or transposition of
+/// a single character).
+#[derive(Clone, Debug, Default)]
+pub struct WordList {
+    words: BTreeSet<String>,
+}
+
+impl WordList {
+    /// Construct an empty list
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct from a list of `words`
+    pub fn from_words<I: IntoIterator<Item = S>, S: AsRef<str>>(words: I) -> Self {
+        let words = words.into_iter();
+        let words = words.map(|word| word.as_ref().to_lowercase()).collect();
+        WordList { words }
+    }
+
+    /// Get the number of words
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// True if the list is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Maximum number of suggestions from [`WordList`]
+const MAX_SUGGESTIONS: usize = 8;
+
+impl SpellChecker for WordList {
+    fn check(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    fn suggest(&self, word: &str) -> Vec<String> {
+        let lower: Vec<char> = word.to_lowercase().chars().collect();
+        let capitalise = word.chars().next().map(char::is_uppercase).unwrap_or(false);
+        let suggestions = self.words.iter().filter(|w| {
+            let w: Vec<char> = w.chars().collect();
+            one_edit_apart(&lower, &w)
+        });
+        suggestions
+            .take(MAX_SUGGESTIONS)
+            .map(|w| match capitalise {
+                false => w.clone(),
+                true => {
+                    let mut chars = w.chars();
+                    let first = chars.next().into_iter().flat_map(char::to_uppercase);
+                    first.chain(chars).collect()
+                }
+            })
+            .collect()
+    }
+
+    fn add_word(&mut self, word: &str) {
+        self.words.insert(word.to_lowercase());
+    }
+}
+
+fn one_edit_apart(a: &[char], b: &[char]) -> bool {
+    let (a, b) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if b.len() - a.len() > 1 {
+        return false;
+    }
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    if a.len() == b.len() {
+        if prefix == a.len() {
+            return false; // identical
+        }
+        // Substitution or transposition
+        let i = prefix;
+        a[i + 1..] == b[i + 1..]
+            || (i + 1 < a.len() && a[i] == b[i + 1] && a[i + 1] == b[i] && a[i + 2..] == b[i + 2..])
+    } else {
+        // Insertion
+        a[prefix..] == b[prefix + 1..]
+    }
+}
+
+/// Find misspelled words in `text`
+pub(crate) fn find_misspelled(checker: &dyn SpellChecker, text: &str) -> Vec<Range<usize>> {
+    text.split_word_bound_indices()
+        .filter(|(_, word)| word.chars().any(char::is_alphabetic))
+        .filter(|(_, word)| !checker.check(word))
+        .map(|(i, word)| i..i + word.len())
+        .collect()
+}
+
+/// Pop-up menu of spelling suggestions, used by [`EditBox`](super::EditBox)
+///
+/// Entries are the suggestions followed by "Add to dictionary". The message
+/// is the index of the activated entry.
+#[derive(Clone, Debug, Default, Widget)]
+#[handler(handle=noauto)]
+pub(crate) struct SpellMenu {
+    #[widget_core]
+    core: CoreData,
+    entries: Vec<Text<String>>,
+    frame_offset: Offset,
+    frame_size: Size,
+    row_height: i32,
+    highlighted: Option<usize>,
+}
+
+impl SpellMenu {
+    /// Set entries from `suggestions`
+    ///
+    /// The final entry is translated using `mgr`.
+    pub fn set_suggestions(&mut self, mgr: &Manager, suggestions: Vec<String>) -> TkAction {
+        self.entries = suggestions.into_iter().map(Text::new_single).collect();
+        let add = mgr.translate(&Message::new("Add to dictionary"));
+        self.entries.push(Text::new_single(add));
+        self.highlighted = Some(0);
+        TkAction::RESIZE
+    }
+
+    /// Get suggestion `index`, or `None` for "Add to dictionary"
+    pub fn suggestion(&self, index: usize) -> Option<&str> {
+        if index + 1 < self.entries.len() {
+            Some(self.entries[index].as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Get the highlighted entry
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    /// Move the highlight by one entry
+    pub fn move_highlight(&mut self, mgr: &mut Manager, reverse: bool) {
+        let last = self.entries.len().saturating_sub(1);
+        self.highlighted = Some(match (self.highlighted, reverse) {
+            (None, false) => 0,
+            (None, true) => last,
+            (Some(i), false) => (i + 1).min(last),
+            (Some(i), true) => i.saturating_sub(1),
+        });
+        mgr.redraw(self.id());
+    }
+
+    /// Highlight the entry under `coord`, if any
+    pub fn highlight_at(&mut self, mgr: &mut Manager, coord: Coord) {
+        let index = self.index_at(coord);
+        if index.is_some() && index != self.highlighted {
+            self.highlighted = index;
+            mgr.redraw(self.id());
+        }
+    }
+
+    fn index_at(&self, coord: Coord) -> Option<usize> {
+        if !self.rect().contains(coord) || self.row_height <= 0 {
+            return None;
+        }
+        let index = usize::conv((coord.1 - self.rect().pos.1) / self.row_height);
+        Some(index.min(self.entries.len().saturating_sub(1)))
+    }
+}
+
+impl Layout for SpellMenu {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let frame_rules = size_handle.menu_frame(axis.is_vertical());
+        let mut rules = SizeRules::EMPTY;
+        for text in &mut self.entries {
+            let text_rules = size_handle.text_bound(text, TextClass::LabelFixed, axis);
+            let (entry_rules, offset, size) = frame_rules.surround(text_rules);
+            self.frame_offset.set_component(axis, offset);
+            self.frame_size.set_component(axis, size);
+            if axis.is_horizontal() {
+                rules.max_with(entry_rules);
+            } else {
+                rules.append(entry_rules);
+            }
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, _: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let n = i32::conv(self.entries.len().max(1));
+        self.row_height = rect.size.1 / n;
+        let size = Size(rect.size.0, self.row_height) - self.frame_size;
+        for text in &mut self.entries {
+            text.update_env(|env| {
+                env.set_bounds(size.into());
+                env.set_align(align.unwrap_or(Align::Default, Align::Centre));
+            });
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let state = self.input_state(mgr, disabled);
+        let mut rect = self.core.rect;
+        rect.size.1 = self.row_height;
+        for (i, text) in self.entries.iter().enumerate() {
+            let mut state = state;
+            state.hover = self.highlighted == Some(i);
+            draw_handle.menu_entry(rect, state);
+            draw_handle.text(rect.pos + self.frame_offset, text, TextClass::Label);
+            rect.pos.1 += self.row_height;
+        }
+    }
+}
+
+impl event::Handler for SpellMenu {
+    type Msg = usize;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Activate => Response::none_or_msg(self.highlighted),
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None);
+                self.highlight_at(mgr, coord);
+                Response::None
+            }
+            Event::PressMove { coord, .. } => {
+                self.highlight_at(mgr, coord);
+                Response::None
+            }
+            Event::PressEnd { end_id, coord, .. } if end_id == Some(self.id()) => {
+                Response::none_or_msg(self.index_at(coord))
+            }
+            Event::PressEnd { .. } => Response::None,
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+#[test]
+fn word_list() {
+    let mut list = WordList::from_words(&["hello", "world", "word"]);
+    assert!(list.check("Hello"));
+    assert!(!list.check("wrold"));
+    assert_eq!(list.suggest("wrold"), vec!["world".to_string()]);
+    assert_eq!(list.suggest("Wor"), vec!["Word".to_string()]);
+    assert_eq!(find_misspelled(&list, "Hello, wrold!"), vec![7..12]);
+    list.add_word("wrold");
+    assert!(find_misspelled(&list, "Hello, wrold!").is_empty());
+}
@@ -0,0 +1,327 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! `SpinBox` control
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{EditBox, EditField, EditGuard, SliderType, TextButton};
+use kas::event::{self, Command, ScrollDelta};
+use kas::macros::*;
+use kas::prelude::*;
+
+/// An [`EditGuard`] for numeric input
+///
+/// Input is parsed on each edit. Unparsable or out-of-range input sets the
+/// error state of the [`EditField`]; valid input updates the value and is
+/// returned as a message. On activation and loss of focus, the text is reset
+/// to the last valid value.
+#[derive(Clone, Debug)]
+pub struct SpinGuard<T: SliderType> {
+    value: T,
+    range: (T, T),
+}
+
+impl<T: SliderType + FromStr + Display> SpinGuard<T> {
+    /// Construct, with the given `min` and `max` values
+    ///
+    /// The initial value is `min`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    pub fn new(min: T, max: T) -> Self {
+        assert!(min <= max);
+        SpinGuard {
+            value: min,
+            range: (min, max),
+        }
+    }
+
+    /// Get the last valid value
+    #[inline]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    fn clamp(&self, value: T) -> T {
+        if value < self.range.0 {
+            self.range.0
+        } else if value > self.range.1 {
+            self.range.1
+        } else {
+            value
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<T> {
+        let value = text.trim().parse::<T>().ok()?;
+        if self.range.0 <= value && value <= self.range.1 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: SliderType + FromStr + Display> EditGuard for SpinGuard<T> {
+    type Msg = T;
+
+    fn activate(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
+        Self::focus_lost(edit, mgr)
+    }
+
+    fn focus_lost(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
+        let text = edit.guard.value.to_string();
+        if edit.get_str() != text {
+            *mgr |= edit.set_string(text);
+        }
+        edit.set_error_state(false);
+        None
+    }
+
+    fn edit(edit: &mut EditField<Self>, _: &mut Manager) -> Option<Self::Msg> {
+        let value = edit.guard.parse(edit.get_str());
+        edit.set_error_state(value.is_none());
+        match value {
+            Some(value) if value != edit.guard.value => {
+                edit.guard.value = value;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Step `value` up or down by `step` (or 16 times `step` if `page`)
+///
+/// The result is limited to `range`, including on overflow.
+fn step<T: SliderType>(value: T, step: T, range: (T, T), up: bool, page: bool) -> T {
+    let mut step = step;
+    if page {
+        // Page steps are 16 times larger (unless this overflows)
+        for _ in 0..4 {
+            step = step.checked_add(step).unwrap_or(step);
+        }
+    }
+    let (min, max) = range;
+    // On overflow or passing the end of the range, use the bound
+    match up {
+        false => value.checked_sub(step).filter(|v| *v >= min).unwrap_or(min),
+        true => value.checked_add(step).filter(|v| *v <= max).unwrap_or(max),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, VoidMsg)]
+enum SpinBtn {
+    Up,
+    Down,
+}
+
+/// A numeric entry box with up/down buttons
+///
+/// The value may be typed directly or stepped via the buttons, the arrow
+/// and page up/down keys, or the mouse wheel. Arrow keys step the value by
+/// `step` and page up/down keys by `step * 16`.
+///
+/// Unparsable or out-of-range input is indicated via the error state (see
+/// [`EditField::set_error_state`]) and is reverted when the box loses focus.
+///
+/// A message is emitted each time the value changes.
+#[derive(Clone, Debug, Widget)]
+#[layout(grid)]
+#[handler(noauto)]
+pub struct SpinBox<T: SliderType + FromStr + Display> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    #[widget(row = 0, col = 0, rspan = 2)]
+    edit: EditBox<SpinGuard<T>>,
+    #[widget(row = 0, col = 1)]
+    up: TextButton<SpinBtn>,
+    #[widget(row = 1, col = 1)]
+    down: TextButton<SpinBtn>,
+    step: T,
+}
+
+impl<T: SliderType + FromStr + Display> SpinBox<T> {
+    /// Construct a spin box
+    ///
+    /// Values vary between the given `min` and `max`, with the given `step`.
+    ///
+    /// The initial value defaults to the range's lower bound but may be
+    /// specified via [`SpinBox::with_value`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    #[inline]
+    pub fn new(min: T, max: T, step: T) -> Self {
+        let guard = SpinGuard::new(min, max);
+        SpinBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            edit: EditBox::new(min.to_string()).with_guard(guard),
+            up: TextButton::new_msg("▲", SpinBtn::Up),
+            down: TextButton::new_msg("▼", SpinBtn::Down),
+            step,
+        }
+    }
+
+    /// Set the initial value
+    #[inline]
+    pub fn with_value(mut self, value: T) -> Self {
+        let _ = self.set_value(value);
+        self
+    }
+
+    /// Get the current value
+    ///
+    /// This is the last valid value entered.
+    #[inline]
+    pub fn value(&self) -> T {
+        self.edit.guard.value
+    }
+
+    /// Set the value
+    ///
+    /// The value is clamped to the allowed range. Any error state is cleared.
+    pub fn set_value(&mut self, value: T) -> TkAction {
+        let value = self.edit.guard.clamp(value);
+        self.edit.guard.value = value;
+        self.edit.set_error_state(false);
+        let text = value.to_string();
+        if self.edit.get_str() != text {
+            self.edit.set_string(text)
+        } else {
+            TkAction::REDRAW
+        }
+    }
+
+    /// Get the allowed range: `(min, max)`
+    #[inline]
+    pub fn range(&self) -> (T, T) {
+        self.edit.guard.range
+    }
+
+    fn step_value(&mut self, mgr: &mut Manager, up: bool, page: bool) -> Response<T> {
+        let old = self.edit.guard.value;
+        let value = step(old, self.step, self.edit.guard.range, up, page);
+        *mgr |= self.set_value(value);
+        if value != old {
+            Response::Msg(value)
+        } else {
+            Response::None
+        }
+    }
+
+    fn step_command(&mut self, mgr: &mut Manager, cmd: Command) -> Response<T> {
+        match cmd {
+            Command::Up => self.step_value(mgr, true, false),
+            Command::Down => self.step_value(mgr, false, false),
+            Command::PageUp => self.step_value(mgr, true, true),
+            Command::PageDown => self.step_value(mgr, false, true),
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+impl<T: SliderType + FromStr + Display> event::Handler for SpinBox<T> {
+    type Msg = T;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::Command(cmd, _) => self.step_command(mgr, cmd),
+            Event::Scroll(delta) => {
+                let up = match delta {
+                    ScrollDelta::LineDelta(_, y) if y != 0.0 => y > 0.0,
+                    ScrollDelta::PixelDelta(d) if d.1 != 0 => d.1 > 0,
+                    _ => return Response::Unhandled,
+                };
+                self.step_value(mgr, up, false)
+            }
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+impl<T: SliderType + FromStr + Display> event::SendEvent for SpinBox<T> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled;
+        }
+
+        let r = if id <= self.edit.id() {
+            // The edit field would otherwise use these keys to move the cursor
+            if let Event::Command(cmd, _) = event {
+                match cmd {
+                    Command::Up | Command::Down | Command::PageUp | Command::PageDown => {
+                        return self.step_command(mgr, cmd);
+                    }
+                    _ => (),
+                }
+            }
+            self.edit.send(mgr, id, event.clone())
+        } else {
+            let r = if id <= self.up.id() {
+                self.up.send(mgr, id, event.clone())
+            } else if id <= self.down.id() {
+                self.down.send(mgr, id, event.clone())
+            } else {
+                return Manager::handle_generic(self, mgr, event);
+            };
+            match r {
+                Response::None | Response::Update => Response::None,
+                Response::Unhandled => Response::Unhandled,
+                Response::Focus(rect) => Response::Focus(rect),
+                Response::Msg(btn) => return self.step_value(mgr, btn == SpinBtn::Up, false),
+            }
+        };
+
+        // Step keys and scrolling are handled here if not used by the child
+        match r {
+            Response::Unhandled => self.handle(mgr, event),
+            r => r,
+        }
+    }
+}
+
+#[test]
+fn spin_guard_parse_clamp() {
+    let guard = SpinGuard::new(-5i32, 10);
+    assert_eq!(guard.value(), -5);
+    assert_eq!(guard.parse("3"), Some(3));
+    assert_eq!(guard.parse(" 10 "), Some(10));
+    assert_eq!(guard.parse("11"), None);
+    assert_eq!(guard.parse("-6"), None);
+    assert_eq!(guard.parse("x"), None);
+    assert_eq!(guard.parse(""), None);
+    assert_eq!(guard.clamp(-100), -5);
+    assert_eq!(guard.clamp(4), 4);
+    assert_eq!(guard.clamp(100), 10);
+}
+
+#[test]
+#[should_panic]
+fn spin_guard_bad_range() {
+    let _ = SpinGuard::new(1u8, 0);
+}
+
+#[test]
+fn spin_step() {
+    assert_eq!(step(5i32, 2, (0, 100), true, false), 7);
+    assert_eq!(step(5i32, 2, (0, 100), false, false), 3);
+    assert_eq!(step(5i32, 2, (0, 100), true, true), 37);
+    assert_eq!(step(5i32, 2, (0, 100), false, true), 0);
+    assert_eq!(step(99i32, 2, (0, 100), true, false), 100);
+    // Overflow is limited to the range
+    assert_eq!(step(250u8, 10, (0, 255), true, false), 255);
+    assert_eq!(step(3u8, 10, (0, 255), false, false), 0);
+    // The page step saturates rather than overflowing
+    assert_eq!(step(0u8, 100, (0, 255), true, true), 200);
+    assert_eq!(step(0.5f32, 0.25, (0.0, 1.0), true, false), 0.75);
+}
@@ -9,7 +9,7 @@ use log::warn;
 use std::ops::{Index, IndexMut};
 
 use super::DragHandle;
-use kas::dir::{Down, Right};
+use kas::dir::{Down, LayoutDir, Right};
 use kas::event;
 use kas::layout::{self, RulesSetter, RulesSolver};
 use kas::prelude::*;
@@ -81,6 +81,7 @@ pub struct Splitter<D: Directional, W: Widget> {
     handles: Vec<DragHandle>,
     data: layout::DynRowStorage,
     direction: D,
+    layout_dir: LayoutDir,
 }
 
 impl<D: Directional, W: Widget> WidgetChildren for Splitter<D, W> {
@@ -146,6 +147,7 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
 
     fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         self.core.rect = rect;
+        self.layout_dir = align.dir;
         if self.widgets.len() == 0 {
             return;
         }
@@ -162,7 +164,7 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
         let mut n = 0;
         loop {
             assert!(n < self.widgets.len());
-            let align = AlignHints::default();
+            let align = AlignHints::NONE.with_dir(self.layout_dir);
             self.widgets[n].set_rect(mgr, setter.child_rect(&mut self.data, n << 1), align);
 
             if n >= self.handles.len() {
@@ -172,7 +174,7 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
             // TODO(opt): calculate all maximal sizes simultaneously
             let index = (n << 1) + 1;
             let track = setter.maximal_rect_of(&mut self.data, index);
-            self.handles[n].set_rect(mgr, track, AlignHints::default());
+            self.handles[n].set_rect(mgr, track, AlignHints::NONE);
             let handle = setter.child_rect(&mut self.data, index);
             let _ = self.handles[n].set_size_and_offset(handle.size, handle.pos - track.pos);
 
@@ -189,12 +191,12 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
         // widgets, so there's no harm (and only a small performance loss) in
         // calling it twice.
 
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         if let Some(child) = solver.find_child(&self.widgets, coord) {
             return child.find_id(coord).or(Some(self.id()));
         }
 
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         if let Some(child) = solver.find_child(&self.handles, coord) {
             return child.find_id(coord).or(Some(self.id()));
         }
@@ -205,13 +207,13 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         // as with find_id, there's not much harm in invoking the solver twice
 
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         let disabled = disabled || self.is_disabled();
         solver.for_children(&self.widgets, draw_handle.target_rect(), |w| {
             w.draw(draw_handle, mgr, disabled)
         });
 
-        let solver = layout::RowPositionSolver::new(self.direction);
+        let solver = layout::RowPositionSolver::new(self.layout_dir.resolve(self.direction));
         solver.for_children(&self.handles, draw_handle.target_rect(), |w| {
             draw_handle.separator(w.rect())
         });
@@ -275,6 +277,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             handles,
             data: Default::default(),
             direction,
+            layout_dir: Default::default(),
         }
     }
 
@@ -284,12 +287,19 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         let index = 2 * n + 1;
 
         let hrect = self.handles[n].rect();
-        let width1 = (hrect.pos - self.core.rect.pos).extract(self.direction);
-        let width2 = (self.core.rect.size - hrect.size).extract(self.direction) - width1;
+        let mut width1 = (hrect.pos - self.core.rect.pos).extract(self.direction);
+        let mut width2 = (self.core.rect.size - hrect.size).extract(self.direction) - width1;
+        if self.layout_dir.is_reversed(self.direction) {
+            std::mem::swap(&mut width1, &mut width2);
+        }
 
         let dim = (self.direction, self.num_children());
-        let mut setter =
-            layout::RowSetter::<D, Vec<i32>, _>::new_unsolved(self.core.rect, dim, &mut self.data);
+        let mut setter = layout::RowSetter::<D, Vec<i32>, _>::new_unsolved(
+            self.core.rect,
+            dim,
+            self.layout_dir,
+            &mut self.data,
+        );
         setter.solve_range(&mut self.data, 0..index, width1);
         setter.solve_range(&mut self.data, (index + 1)..dim.1, width2);
         setter.update_offsets(&mut self.data);
@@ -297,7 +307,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         let mut n = 0;
         loop {
             assert!(n < self.widgets.len());
-            let align = AlignHints::default();
+            let align = AlignHints::NONE.with_dir(self.layout_dir);
             self.widgets[n].set_rect(mgr, setter.child_rect(&mut self.data, n << 1), align);
 
             if n >= self.handles.len() {
@@ -306,7 +316,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
 
             let index = (n << 1) + 1;
             let track = self.handles[n].track();
-            self.handles[n].set_rect(mgr, track, AlignHints::default());
+            self.handles[n].set_rect(mgr, track, AlignHints::NONE);
             let handle = setter.child_rect(&mut self.data, index);
             let _ = self.handles[n].set_size_and_offset(handle.size, handle.pos - track.pos);
 
@@ -8,6 +8,9 @@
 use std::fmt::Debug;
 use std::ops::{Index, IndexMut};
 
+use kas::anim::{self, Animated, Easing};
+use kas::dir::LayoutDir;
+use kas::draw::ClipRegion;
 use kas::{event, prelude::*};
 
 /// A stack of boxed widgets
@@ -20,11 +23,32 @@ pub type BoxStack<M> = Stack<Box<dyn Widget<Msg = M>>>;
 /// This is a parametrisation of [`Stack`].
 pub type RefStack<'a, M> = Stack<&'a mut dyn Widget<Msg = M>>;
 
+/// Transition used when changing the active widget of a [`Stack`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StackTransition {
+    /// Switch immediately
+    None,
+    /// Slide the new widget in, pushing the old widget out
+    ///
+    /// When moving to a higher index, the new widget enters from the right
+    /// (from the left given a right-to-left layout direction).
+    Slide,
+}
+
+impl Default for StackTransition {
+    fn default() -> Self {
+        StackTransition::None
+    }
+}
+
 /// A stack of widgets
 ///
 /// A stack consists a set of child widgets, all of equal size.
 /// Only a single member is visible at a time.
 ///
+/// Changing the active widget may be animated; see [`StackTransition`].
+/// Transitions are disabled when [`kas::event::Config::reduce_motion`] is set.
+///
 /// This may only be parametrised with a single widget type; [`BoxStack`] is
 /// a parametrisation allowing run-time polymorphism of child widgets.
 ///
@@ -39,6 +63,10 @@ pub struct Stack<W: Widget> {
     core: CoreData,
     widgets: Vec<W>,
     active: usize,
+    transition: StackTransition,
+    /// Previously-active widget and transition progress
+    anim: Option<(usize, Animated<f32>)>,
+    dir: LayoutDir,
 }
 
 impl<W: Widget> WidgetChildren for Stack<W> {
@@ -74,6 +102,7 @@ impl<W: Widget> Layout for Stack<W> {
 
     fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         self.core.rect = rect;
+        self.dir = align.dir;
         for child in &mut self.widgets {
             child.set_rect(mgr, rect, align);
         }
@@ -88,12 +117,56 @@ impl<W: Widget> Layout for Stack<W> {
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
+        if let Some((old, ref anim)) = self.anim {
+            // Request frames while animating and one more to clear anim
+            mgr.request_animation_frame(self.id());
+            let animate = self.transition != StackTransition::None && !mgr.config_reduce_motion();
+            if animate && anim.is_running() {
+                self.draw_transition(draw_handle, mgr, disabled, old, anim.value());
+                return;
+            }
+        }
         if self.active < self.widgets.len() {
             self.widgets[self.active].draw(draw_handle, mgr, disabled);
         }
     }
 }
 
+impl<W: Widget> Stack<W> {
+    fn draw_transition(
+        &self,
+        draw_handle: &mut dyn DrawHandle,
+        mgr: &event::ManagerState,
+        disabled: bool,
+        old_index: usize,
+        progress: f32,
+    ) {
+        let rect = self.core.rect;
+        let old = self.widgets.get(old_index);
+        let new = self.widgets.get(self.active);
+        let width = rect.size.0;
+        let shift = i32::conv_nearest(progress * width as f32);
+        let rest = width - shift;
+        // For each widget: the horizontal position and width of the part
+        // of our rect showing the widget, and the widget's translation
+        let from_right = (self.active > old_index) != self.dir.is_rtl();
+        let x0 = rect.pos.0;
+        let (old_part, new_part) = match from_right {
+            true => ((x0, rest, -shift), (x0 + rest, shift, rest)),
+            false => ((x0 + shift, rest, shift), (x0, shift, -rest)),
+        };
+        for &(child, (x, w, dx)) in &[(old, old_part), (new, new_part)] {
+            if let Some(child) = child {
+                let clip = Rect::new(Coord(x, rect.pos.1), Size(w, rect.size.1));
+                let class = ClipRegion::Scroll;
+                draw_handle.clip_region(clip, Offset(-dx, 0), class, &mut |draw_handle| {
+                    child.draw(draw_handle, mgr, disabled)
+                });
+            }
+        }
+    }
+}
+
 impl<W: Widget> event::SendEvent for Stack<W> {
     fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
         if !self.is_disabled() {
@@ -110,6 +183,16 @@ impl<W: Widget> event::SendEvent for Stack<W> {
             }
         }
 
+        if id == self.id() && event == Event::AnimationFrame {
+            let done = match self.anim {
+                Some((_, ref anim)) => !anim.is_running() || mgr.config_reduce_motion(),
+                None => false,
+            };
+            if done {
+                self.anim = None;
+            }
+            return Response::None;
+        }
         Response::Unhandled
     }
 }
@@ -125,9 +208,25 @@ impl<W: Widget> Stack<W> {
             core: Default::default(),
             widgets,
             active,
+            transition: Default::default(),
+            anim: None,
+            dir: Default::default(),
         }
     }
 
+    /// Set the transition used by [`Stack::set_active`] (inline)
+    #[inline]
+    pub fn with_transition(mut self, transition: StackTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Set the transition used by [`Stack::set_active`]
+    #[inline]
+    pub fn set_transition(&mut self, transition: StackTransition) {
+        self.transition = transition;
+    }
+
     /// Get the index of the active widget
     pub fn active_index(&self) -> usize {
         self.active
@@ -138,10 +237,17 @@ impl<W: Widget> Stack<W> {
     /// It is not required that `active < self.len()`; if not, no widget will be
     /// drawn or respond to events, but the stack will still size as required by
     /// child widgets.
+    ///
+    /// The change is animated according to the [`StackTransition`] in use.
     pub fn set_active(&mut self, active: usize) -> TkAction {
         if self.active == active {
             TkAction::empty()
         } else {
+            if self.transition != StackTransition::None {
+                let mut anim = Animated::new(0.0).with_easing(Easing::EaseInOut);
+                anim.set(1.0, anim::DEFAULT_DURATION);
+                self.anim = Some((self.active, anim));
+            }
             self.active = active;
             TkAction::REGION_MOVED
         }
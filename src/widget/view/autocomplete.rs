@@ -0,0 +1,377 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Autocomplete widget
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::{DefaultView, Filter, FilteredList, ListMsg, ListView, SelectionMode};
+use super::{SimpleCaseInsensitiveFilter, View};
+use kas::data::ListData;
+use kas::event::{self, Command, UpdateHandle};
+use kas::prelude::*;
+use kas::widget::{EditBox, MenuFrame};
+use kas::WindowId;
+
+type Popup<T, F, V> = MenuFrame<ListView<kas::dir::Down, Rc<FilteredList<T, F>>, V>>;
+
+/// A text-edit box with a pop-up list of completions
+///
+/// As the user types, the pop-up shows those items of the `data` list which
+/// match a [`Filter`] constructed from the input text. The up and down arrow
+/// keys move the highlight within this list and the return key accepts the
+/// highlighted item; an item may also be accepted by clicking it.
+///
+/// On acceptance, the edit box's text is replaced with the item and the
+/// item's key and value are returned as a message.
+#[derive(Clone, Widget)]
+#[handler(noauto)]
+pub struct Autocomplete<
+    T: ListData + 'static,
+    F: Filter<T::Item>,
+    V: View<T::Key, T::Item> = DefaultView,
+> where
+    T::Item: ToString,
+{
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    edit: EditBox,
+    #[widget]
+    popup: Popup<T, F, V>,
+    popup_id: Option<WindowId>,
+    /// Index of the highlighted item within the filtered list
+    highlight: Option<usize>,
+    make_filter: Rc<dyn Fn(&str) -> F>,
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item>> Debug for Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Autocomplete {{ core: {:?}, edit: {:?}, popup: {:?}, popup_id: {:?}, highlight: {:?}, make_filter: <fn> }}",
+            self.core, self.edit, self.popup, self.popup_id, self.highlight,
+        )
+    }
+}
+
+impl<T: ListData> Autocomplete<T, SimpleCaseInsensitiveFilter>
+where
+    T::Item: ToString,
+    SimpleCaseInsensitiveFilter: Filter<T::Item>,
+{
+    /// Construct with case-insensitive matching over `data`
+    #[inline]
+    pub fn new(data: T) -> Self {
+        Self::new_with_filter(data, |text| SimpleCaseInsensitiveFilter::new(text))
+    }
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item> + Default> Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    /// Construct with a custom filter
+    ///
+    /// The closure `make_filter` constructs a filter from the input text.
+    #[inline]
+    pub fn new_with_filter<MF: Fn(&str) -> F + 'static>(data: T, make_filter: MF) -> Self {
+        Self::new_with_view(V::default(), data, make_filter)
+    }
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item>> Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    /// Construct with a custom filter and view
+    ///
+    /// The closure `make_filter` constructs a filter from the input text.
+    pub fn new_with_view<MF: Fn(&str) -> F + 'static>(view: V, data: T, make_filter: MF) -> Self {
+        let filtered = Rc::new(FilteredList::new(data, make_filter("")));
+        let list = ListView::new_with_dir_view(kas::dir::Down, view, filtered)
+            .with_selection_mode(SelectionMode::Single);
+        Autocomplete {
+            core: Default::default(),
+            edit: EditBox::new(""),
+            popup: MenuFrame::new(list),
+            popup_id: None,
+            highlight: None,
+            make_filter: Rc::new(make_filter),
+        }
+    }
+
+    /// Access the unfiltered data
+    pub fn data(&self) -> &T {
+        &self.popup.inner.data().data
+    }
+
+    /// Get the key of the highlighted item, if any
+    pub fn highlighted(&self) -> Option<&T::Key> {
+        self.popup.inner.selected_iter().next()
+    }
+
+    fn list(&mut self) -> &mut ListView<kas::dir::Down, Rc<FilteredList<T, F>>, V> {
+        &mut self.popup.inner
+    }
+
+    fn close_popup(&mut self, mgr: &mut Manager) {
+        if let Some(id) = self.popup_id {
+            mgr.close_window(id);
+        }
+    }
+
+    /// Apply the filter for `text`, highlighting the first match
+    ///
+    /// Nothing is highlighted when `text` is empty. Returns the filtered
+    /// list's update handle and the index of the highlighted item.
+    fn apply_filter(&mut self, text: &str) -> (UpdateHandle, Option<usize>) {
+        let filter = (self.make_filter)(text);
+        let handle = self.list().data().set_filter(filter);
+
+        let first = self.list().data().iter_vec(1).into_iter().next();
+        self.list().clear_selected();
+        self.highlight = None;
+        match first {
+            Some((key, _)) if !text.is_empty() => {
+                let _ = self.list().select(key);
+                self.highlight = Some(0);
+            }
+            _ => (),
+        }
+        (handle, self.highlight)
+    }
+
+    /// Re-apply the filter after the text was edited
+    fn refilter(&mut self, mgr: &mut Manager) {
+        let text = self.edit.get_str().to_string();
+        let (handle, highlight) = self.apply_filter(&text);
+        mgr.trigger_update(handle, 0);
+
+        match highlight {
+            Some(index) => {
+                self.list().scroll_to_index(mgr, index);
+                if self.popup_id.is_none() {
+                    let id = mgr.add_popup(kas::Popup {
+                        id: self.popup.id(),
+                        parent: self.id(),
+                        direction: Direction::Down,
+                    });
+                    self.popup_id = Some(id);
+                }
+                mgr.redraw(self.popup.id());
+            }
+            None => self.close_popup(mgr),
+        }
+    }
+
+    /// Step the highlight by one item, returning the new index
+    ///
+    /// The highlight stops at the first and last items.
+    fn step_highlight(&mut self, reverse: bool) -> Option<usize> {
+        let len = self.list().data().len();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.highlight, reverse) {
+            (None, false) => 0,
+            (None, true) => len - 1,
+            (Some(i), false) => (i + 1).min(len - 1),
+            (Some(i), true) => i.min(len).saturating_sub(1),
+        };
+        let item = self
+            .list()
+            .data()
+            .iter_vec_from(index, 1)
+            .into_iter()
+            .next();
+        let (key, _) = item?;
+        self.highlight = Some(index);
+        let _ = self.list().select(key);
+        Some(index)
+    }
+
+    /// Move the highlight by one item
+    fn move_highlight(&mut self, mgr: &mut Manager, reverse: bool) {
+        if let Some(index) = self.step_highlight(reverse) {
+            self.list().scroll_to_index(mgr, index);
+            mgr.redraw(self.popup.id());
+        }
+    }
+
+    /// Accept the item at `key`
+    fn accept(&mut self, mgr: &mut Manager, key: T::Key) -> Response<(T::Key, T::Item)> {
+        self.close_popup(mgr);
+        match self.list().get_value(&key) {
+            Some(item) => {
+                *mgr |= self.edit.set_string(item.to_string());
+                Response::Msg((key, item))
+            }
+            None => Response::None,
+        }
+    }
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item>> Layout for Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.edit.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.edit.set_rect(mgr, rect, align);
+    }
+
+    fn spatial_range(&self) -> (usize, usize) {
+        // The list is a pop-up, hence excluded
+        (0, 0)
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.edit.find_id(coord).or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.edit.draw(draw_handle, mgr, disabled);
+    }
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item>> event::Handler
+    for Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    type Msg = (T::Key, T::Item);
+
+    fn handle(&mut self, _: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::PopupRemoved(id) => {
+                debug_assert_eq!(Some(id), self.popup_id);
+                self.popup_id = None;
+                Response::None
+            }
+            _ => Response::Unhandled,
+        }
+    }
+}
+
+impl<T: ListData, F: Filter<T::Item>, V: View<T::Key, T::Item>> event::SendEvent
+    for Autocomplete<T, F, V>
+where
+    T::Item: ToString,
+{
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled;
+        }
+
+        if id <= self.edit.id() {
+            // While the pop-up is open, navigation keys apply to the list
+            if let (Some(_), Event::Command(cmd, _)) = (self.popup_id, &event) {
+                match cmd {
+                    Command::Up | Command::Down => {
+                        self.move_highlight(mgr, *cmd == Command::Up);
+                        return Response::None;
+                    }
+                    Command::Return => {
+                        if let Some(key) = self.highlighted().cloned() {
+                            return self.accept(mgr, key);
+                        }
+                    }
+                    Command::Escape => {
+                        self.close_popup(mgr);
+                        return Response::None;
+                    }
+                    _ => (),
+                }
+            }
+            match self.edit.send(mgr, id, event) {
+                Response::Update => {
+                    self.refilter(mgr);
+                    Response::Update
+                }
+                r => r.void_into(),
+            }
+        } else if id <= self.popup.id() {
+            match self.popup.send(mgr, id, event) {
+                Response::Msg(ListMsg::Select(key)) => self.accept(mgr, key),
+                Response::Msg(_) | Response::None | Response::Update => Response::None,
+                Response::Unhandled => Response::Unhandled,
+                Response::Focus(rect) => Response::Focus(rect),
+            }
+        } else {
+            Manager::handle_generic(self, mgr, event)
+        }
+    }
+}
+
+#[test]
+fn filter_and_highlight() {
+    let data = vec!["apple", "banana", "cherry", "grape", "pineapple"].into_boxed_slice();
+    let mut ac = Autocomplete::new(data);
+    assert_eq!(ac.highlighted(), None);
+
+    // Matching is case-insensitive; the first match is highlighted
+    let (_, highlight) = ac.apply_filter("AP");
+    assert_eq!(highlight, Some(0));
+    assert_eq!(ac.list().data().len(), 3);
+    assert_eq!(ac.highlighted(), Some(&0));
+
+    // The highlight stops at the last and first matches
+    assert_eq!(ac.step_highlight(false), Some(1));
+    assert_eq!(ac.highlighted(), Some(&3));
+    assert_eq!(ac.step_highlight(false), Some(2));
+    assert_eq!(ac.step_highlight(false), Some(2));
+    assert_eq!(ac.highlighted(), Some(&4));
+    assert_eq!(ac.step_highlight(true), Some(1));
+    assert_eq!(ac.step_highlight(true), Some(0));
+    assert_eq!(ac.step_highlight(true), Some(0));
+    assert_eq!(ac.highlighted(), Some(&0));
+
+    // Re-filtering resets the highlight
+    ac.step_highlight(false);
+    let (_, highlight) = ac.apply_filter("ch");
+    assert_eq!(highlight, Some(0));
+    assert_eq!(ac.highlighted(), Some(&2));
+
+    // Empty input matches all items but highlights none
+    let (_, highlight) = ac.apply_filter("");
+    assert_eq!(highlight, None);
+    assert_eq!(ac.list().data().len(), 5);
+    assert_eq!(ac.highlighted(), None);
+    assert_eq!(ac.step_highlight(true), Some(4));
+    assert_eq!(ac.highlighted(), Some(&4));
+
+    // Nothing to highlight without matches
+    let (_, highlight) = ac.apply_filter("xyz");
+    assert_eq!(highlight, None);
+    assert_eq!(ac.step_highlight(false), None);
+    assert_eq!(ac.highlighted(), None);
+}
+
+#[test]
+fn highlight_beyond_chunk() {
+    // More items than are fetched per chunk when searching by key
+    let data: Vec<String> = (0..200).map(|i| format!("item {}", i)).collect();
+    let mut ac = Autocomplete::new(data.into_boxed_slice());
+    let (_, highlight) = ac.apply_filter("item");
+    assert_eq!(highlight, Some(0));
+    for i in 1..150 {
+        assert_eq!(ac.step_highlight(false), Some(i));
+    }
+    assert_eq!(ac.highlighted(), Some(&149));
+    assert_eq!(ac.list().data().iter_vec_from(149, 1)[0].1, "item 149");
+}
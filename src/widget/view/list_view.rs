@@ -268,6 +268,40 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> ListView<D, T, V> {
         *mgr |= TkAction::SET_SIZE;
     }
 
+    /// Scroll to make the item at `key` visible
+    ///
+    /// Does nothing if `key` is not found. This is `O(n)` in the number of
+    /// data items; where the index is known, prefer
+    /// [`ListView::scroll_to_index`].
+    pub fn scroll_to(&mut self, mgr: &mut Manager, key: &T::Key) {
+        if let Some(index) = find_index(&self.data, key) {
+            self.scroll_to_index(mgr, index);
+        }
+    }
+
+    /// Scroll to make the item at `index` visible
+    ///
+    /// Does nothing if `index` is out of bounds.
+    pub fn scroll_to_index(&mut self, mgr: &mut Manager, index: usize) {
+        if index >= self.data.len() {
+            return;
+        }
+        let len = self.widgets.len().min(self.data.len());
+        let mut skip = match self.direction.is_vertical() {
+            false => Offset(self.child_skip, 0),
+            true => Offset(0, self.child_skip),
+        };
+        let mut pos = self.core.rect.pos + self.offset;
+        if self.align_hints.dir.is_reversed(self.direction) {
+            pos += skip * i32::conv(len.max(1) - 1);
+            skip = skip * -1;
+        }
+        let rect = Rect::new(pos + skip * i32::conv(index), self.child_size);
+        let (_, action) = self.scroll.focus_rect(rect, self.core.rect);
+        *mgr |= action;
+        self.update_widgets(mgr);
+    }
+
     /// Get the direction of contents
     pub fn direction(&self) -> Direction {
         self.direction.as_direction()
@@ -294,7 +328,7 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> ListView<D, T, V> {
             true => Offset(0, self.child_skip),
         };
         let mut pos_start = self.core.rect.pos + self.offset;
-        if self.direction.is_reversed() {
+        if self.align_hints.dir.is_reversed(self.direction) {
             pos_start += skip * i32::conv(len - 1);
             skip = skip * -1;
         }
@@ -473,6 +507,7 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> Layout for ListView<
                         size_handle,
                         Some(child_size.0),
                         Some(child_size.1),
+                        align.dir,
                     );
                     self.widgets.push(WidgetData { key, widget });
                 }
@@ -483,6 +518,7 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> Layout for ListView<
                         size_handle,
                         Some(child_size.0),
                         Some(child_size.1),
+                        align.dir,
                     );
                     self.widgets.push(WidgetData { key: None, widget });
                 }
@@ -497,7 +533,7 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> Layout for ListView<
 
     fn spatial_range(&self) -> (usize, usize) {
         let last = self.num_children().wrapping_sub(1);
-        match self.direction.is_reversed() {
+        match self.align_hints.dir.is_reversed(self.direction) {
             false => (0, last),
             true => (last, 0),
         }
@@ -650,3 +686,48 @@ impl<D: Directional, T: ListData, V: View<T::Key, T::Item>> SendEvent for ListVi
         }
     }
 }
+
+/// Find the index of `key` within `data`
+///
+/// Items are fetched in chunks, thus only those up to the first chunk
+/// containing `key` are cloned.
+fn find_index<T: ListData + ?Sized>(data: &T, key: &T::Key) -> Option<usize> {
+    const CHUNK: usize = 64;
+    let mut start = 0;
+    while start < data.len() {
+        let items = data.iter_vec_from(start, CHUNK);
+        if let Some(i) = items.iter().position(|item| item.0 == *key) {
+            return Some(start + i);
+        }
+        start += CHUNK;
+    }
+    None
+}
+
+#[test]
+fn find_index_chunks() {
+    use super::{FilteredList, SimpleCaseInsensitiveFilter};
+    use std::rc::Rc;
+
+    // Keys of the filtered list differ from indices: key 2i is at index i
+    let items: Vec<String> = (0..300)
+        .map(|i| match i % 2 {
+            0 => format!("a{}", i),
+            _ => format!("b{}", i),
+        })
+        .collect();
+    let data = Rc::new(FilteredList::new(
+        items.into_boxed_slice(),
+        SimpleCaseInsensitiveFilter::new("a"),
+    ));
+    assert_eq!(data.len(), 150);
+    assert_eq!(find_index(&data, &0), Some(0));
+    assert_eq!(find_index(&data, &126), Some(63));
+    assert_eq!(find_index(&data, &128), Some(64));
+    assert_eq!(find_index(&data, &298), Some(149));
+    assert_eq!(find_index(&data, &1), None);
+    assert_eq!(find_index(&data, &300), None);
+
+    let empty: &[String] = &[];
+    assert_eq!(find_index(empty, &0), None);
+}
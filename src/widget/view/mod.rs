@@ -28,19 +28,23 @@
 //! -   [`ListView`] creates a scrollable list view over a [`ListData`] object.
 //!     Performance is potentially bounded by O(v) in all operations where `v`
 //!     is the number of visible items (depending on the [`ListData`] object).
+//! -   [`Autocomplete`] is an edit box with a pop-up [`ListView`] over
+//!     [`ListData`], filtered as the user types
 
 #[allow(unused)]
 use kas::data::{ListData, SingleData};
 
+mod autocomplete;
 mod filter;
 mod list_view;
 mod shared_data;
 mod single_view;
 mod view_widget;
 
+pub use autocomplete::Autocomplete;
 pub use filter::{Filter, FilteredList, SimpleCaseInsensitiveFilter};
 pub use list_view::{ListMsg, ListView, SelectionMode};
 pub use shared_data::SharedRc;
 pub use single_view::SingleView;
-pub use view_widget::{CheckBoxView, RadioBoxBareView, RadioBoxView, SliderView};
+pub use view_widget::{CheckBoxView, RadioBoxBareView, RadioBoxView, SliderView, SpinBoxView};
 pub use view_widget::{DefaultView, View, WidgetView};
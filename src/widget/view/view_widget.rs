@@ -10,8 +10,9 @@
 use kas::event::UpdateHandle;
 use kas::prelude::*;
 use kas::widget::*;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 /// View widget constructor
 ///
@@ -310,3 +311,32 @@ impl<K, T: SliderType, D: Directional> View<K, T> for SliderView<T, D> {
         Some(widget.value())
     }
 }
+
+/// [`SpinBox`] view widget constructor
+#[derive(Clone, Debug, Default)]
+pub struct SpinBoxView<T: SliderType> {
+    min: T,
+    max: T,
+    step: T,
+}
+impl<T: SliderType> SpinBoxView<T> {
+    /// Construct, with given `min`, `max` and `step` (see [`SpinBox::new`])
+    pub fn new(min: T, max: T, step: T) -> Self {
+        SpinBoxView { min, max, step }
+    }
+}
+impl<K, T: SliderType + FromStr + Display> View<K, T> for SpinBoxView<T> {
+    type Widget = SpinBox<T>;
+    fn default(&self) -> Self::Widget {
+        SpinBox::new(self.min, self.max, self.step)
+    }
+    fn new(&self, _: K, data: T) -> Self::Widget {
+        SpinBox::new(self.min, self.max, self.step).with_value(data)
+    }
+    fn set(&self, widget: &mut Self::Widget, _: K, data: T) -> TkAction {
+        widget.set_value(data)
+    }
+    fn get(&self, widget: &Self::Widget, _: &K) -> Option<T> {
+        Some(widget.value())
+    }
+}
@@ -7,16 +7,40 @@
 
 use smallvec::SmallVec;
 use std::fmt::{self, Debug};
+use std::time::Instant;
 
+use kas::anim::{self, Animated};
 use kas::draw::ClipRegion;
 use kas::event::{self, UpdateHandle};
 use kas::layout;
 use kas::prelude::*;
 use kas::{Future, WindowId};
 
+/// Open/close animation state of a pop-up
+#[derive(Clone, Debug)]
+struct Reveal {
+    /// Direction in which the pop-up is revealed (away from its parent)
+    dir: Direction,
+    /// Fraction of the pop-up shown, from 0 to 1
+    shown: Animated<f32>,
+}
+
+impl Reveal {
+    fn new() -> Self {
+        Reveal {
+            dir: Direction::Down,
+            shown: Animated::new(0.0),
+        }
+    }
+}
+
 /// The main instantiation of the [`Window`] trait.
+///
+/// Pop-ups (including menus) are revealed and hidden with a short animation
+/// unless [`kas::event::Config::reduce_motion`] is set.
 #[derive(Widget)]
 #[handler(send=noauto, generics = <M: Into<VoidMsg>> where W: Widget<Msg = M>)]
+#[widget(config=noauto)]
 pub struct Window<W: Widget + 'static> {
     #[widget_core]
     core: CoreData,
@@ -24,7 +48,9 @@ pub struct Window<W: Widget + 'static> {
     title: String,
     #[widget]
     w: W,
-    popups: SmallVec<[(WindowId, kas::Popup); 16]>,
+    popups: SmallVec<[(WindowId, kas::Popup, Reveal); 16]>,
+    /// Pop-ups which have been removed but are still animating
+    closing: SmallVec<[(kas::Popup, Reveal); 4]>,
     drop: Option<(Box<dyn FnMut(&mut W)>, UpdateHandle)>,
 }
 
@@ -52,7 +78,8 @@ impl<W: Widget + Clone> Clone for Window<W> {
             title: self.title.clone(),
             w: self.w.clone(),
             popups: Default::default(), // these are temporary; don't clone
-            drop: None,                 // we cannot clone this!
+            closing: Default::default(),
+            drop: None, // we cannot clone this!
         }
     }
 }
@@ -66,6 +93,7 @@ impl<W: Widget> Window<W> {
             title: title.to_string(),
             w,
             popups: Default::default(),
+            closing: Default::default(),
             drop: None,
         }
     }
@@ -135,25 +163,88 @@ impl<W: Widget> Layout for Window<W> {
         if !self.rect().contains(coord) {
             return None;
         }
-        for popup in self.popups.iter().rev() {
-            if let Some(id) = self.w.find_leaf(popup.1.id).and_then(|w| w.find_id(coord)) {
+        for (_, popup, reveal) in self.popups.iter().rev() {
+            let w = match self.w.find_leaf(popup.id) {
+                Some(w) => w,
+                None => continue,
+            };
+            // While being revealed, only the visible part may be hit
+            let mut coord = coord;
+            if reveal.shown.is_running() {
+                let shown = reveal.shown.value().max(0.0).min(1.0);
+                let (rect, offset) = reveal_rect(w.rect(), reveal.dir, shown);
+                if !rect.contains(coord) {
+                    continue;
+                }
+                coord = coord + offset;
+            }
+            if let Some(id) = w.find_id(coord) {
                 return Some(id);
             }
         }
         self.w.find_id(coord).or(Some(self.id()))
     }
 
-    #[inline]
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
         self.w.draw(draw_handle, mgr, disabled);
-        for popup in &self.popups {
+        let now = Instant::now();
+        let mut animating = false;
+        let closing = self.closing.iter().map(|(popup, reveal)| (popup, reveal));
+        let open = self.popups.iter().map(|(_, popup, reveal)| (popup, reveal));
+        for (popup, reveal) in closing.chain(open) {
+            let w = match self.w.find_leaf(popup.id) {
+                Some(w) => w,
+                None => continue,
+            };
+            // Pop-up rects are set in window coordinates (see resize_popup)
+            let (rect, offset) = if reveal.shown.is_running_at(now) && !mgr.config_reduce_motion() {
+                animating = true;
+                let shown = reveal.shown.value_at(now).max(0.0).min(1.0);
+                reveal_rect(w.rect(), reveal.dir, shown)
+            } else if reveal.shown.target() == 0.0 {
+                continue;
+            } else {
+                (self.core.rect, Offset::ZERO)
+            };
             let class = ClipRegion::Popup;
-            draw_handle.clip_region(self.core.rect, Offset::ZERO, class, &mut |draw_handle| {
-                self.find_leaf(popup.1.id)
-                    .map(|w| w.draw(draw_handle, mgr, disabled));
+            draw_handle.clip_region(rect, offset, class, &mut |draw_handle| {
+                w.draw(draw_handle, mgr, disabled)
             });
         }
+        if animating {
+            mgr.request_animation_frame(self.id());
+        }
+    }
+}
+
+/// Get the visible part of pop-up `rect` and the translation of its contents
+fn reveal_rect(rect: Rect, dir: Direction, shown: f32) -> (Rect, Offset) {
+    let (pos, size) = (rect.pos, rect.size);
+    let len = match dir.is_vertical() {
+        false => size.0,
+        true => size.1,
+    };
+    let shown = i32::conv_nearest(len as f32 * shown);
+    let hidden = len - shown;
+    match dir {
+        Direction::Right => (Rect::new(pos, Size(shown, size.1)), Offset(hidden, 0)),
+        Direction::Down => (Rect::new(pos, Size(size.0, shown)), Offset(0, hidden)),
+        Direction::Left => (
+            Rect::new(Coord(pos.0 + hidden, pos.1), Size(shown, size.1)),
+            Offset(-hidden, 0),
+        ),
+        Direction::Up => (
+            Rect::new(Coord(pos.0, pos.1 + hidden), Size(size.0, shown)),
+            Offset(0, -hidden),
+        ),
+    }
+}
+
+impl<W: Widget> WidgetConfig for Window<W> {
+    fn configure(&mut self, _: &mut Manager) {
+        // Widget identifiers may have changed
+        self.closing.clear();
     }
 }
 
@@ -162,6 +253,16 @@ impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> event::SendEvent for Window
         if !self.is_disabled() && id <= self.w.id() {
             return self.w.send(mgr, id, event).into();
         }
+        if id == self.id() && event == Event::AnimationFrame {
+            let now = Instant::now();
+            let len = self.closing.len();
+            self.closing
+                .retain(|(_, reveal)| reveal.shown.is_running_at(now));
+            if self.closing.len() != len {
+                mgr.redraw(self.id());
+            }
+            return Response::None;
+        }
         Response::Unhandled
     }
 }
@@ -176,8 +277,15 @@ impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> kas::Window for Window<W> {
     }
 
     fn add_popup(&mut self, mgr: &mut Manager, id: WindowId, popup: kas::Popup) {
+        // A pop-up may be re-opened while closing
+        self.closing.retain(|(p, _)| p.id != popup.id);
         let index = self.popups.len();
-        self.popups.push((id, popup));
+        let mut reveal = Reveal::new();
+        let self_id = self.id();
+        reveal
+            .shown
+            .animate(mgr, self_id, 1.0, anim::DEFAULT_DURATION);
+        self.popups.push((id, popup, reveal));
         self.resize_popup(mgr, index);
         mgr.send_action(TkAction::REDRAW);
     }
@@ -185,7 +293,14 @@ impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> kas::Window for Window<W> {
     fn remove_popup(&mut self, mgr: &mut Manager, id: WindowId) {
         for i in 0..self.popups.len() {
             if id == self.popups[i].0 {
-                self.popups.remove(i);
+                let (_, popup, mut reveal) = self.popups.remove(i);
+                let self_id = self.id();
+                reveal
+                    .shown
+                    .animate(mgr, self_id, 0.0, anim::DEFAULT_DURATION);
+                if reveal.shown.is_running() {
+                    self.closing.push((popup, reveal));
+                }
                 mgr.send_action(TkAction::REGION_MOVED);
                 return;
             }
@@ -231,11 +346,12 @@ impl<W: Widget> Window<W> {
         // Notation: p=point/coord, s=size, m=margin
         // r=window/root rect, c=anchor rect
         let r = self.core.rect;
-        let popup = &mut self.popups[index].1;
+        let (_, popup, reveal) = &mut self.popups[index];
 
         let c = find_rect(self.w.as_widget(), popup.parent).unwrap();
         let widget = self.w.find_leaf_mut(popup.id).unwrap();
-        let mut cache = mgr.size_handle(|sh| layout::SolveCache::find_constraints(widget, sh));
+        let dir = mgr.layout_dir();
+        let mut cache = mgr.size_handle(|sh| layout::SolveCache::find_constraints(widget, sh, dir));
         let ideal = cache.ideal(false);
         let m = cache.margins();
 
@@ -274,6 +390,14 @@ impl<W: Widget> Window<W> {
             Rect::new(Coord(x, y), Size::new(w, h))
         };
 
+        // Reveal away from the parent
+        reveal.dir = match popup.direction.is_horizontal() {
+            false if rect.pos.1 < c.pos.1 => Direction::Up,
+            false => Direction::Down,
+            true if rect.pos.0 < c.pos.0 => Direction::Left,
+            true => Direction::Right,
+        };
+
         cache.apply_rect(widget, mgr, rect, false);
     }
 }